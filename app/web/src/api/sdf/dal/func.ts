@@ -141,6 +141,7 @@ export interface FuncSummary {
 export interface FuncCode {
   funcId: FuncId;
   code: string;
+  normalized?: boolean;
 }
 
 export interface AttributeArgumentBinding {
@@ -1,5 +1,6 @@
 // The actor entitiy that initiates an activitiy--this could represent be a
 // person, service, etc.
 export type ActorView =
+  | { kind: "machine"; label: string; pk: string }
   | { kind: "system"; label: string; email?: string }
   | { kind: "user"; label: string; id?: string; email?: string };
@@ -20,4 +20,6 @@ export interface Prop {
   // this is for input sources
   eligibleToSendData: boolean;
   hidden: boolean;
+  // whether the prop must be set, distinct from having a default value
+  required: boolean;
 }
@@ -777,11 +777,11 @@ export const useFuncStore = () => {
           }
         },
 
-        async SAVE_FUNC(func: FuncCode) {
+        async SAVE_FUNC(func: FuncCode, normalize = false) {
           return new ApiRequest<FuncCode>({
             method: "put",
             url: API_PREFIX.concat([{ funcId: func.funcId }, "code"]),
-            params: { code: func.code },
+            params: { code: func.code, normalize },
             onFail: () => {
               changeSetsStore.creatingChangeSet = false;
             },
@@ -901,9 +901,21 @@ export const useFuncStore = () => {
           },
           {
             eventType: "FuncCodeSaved",
-            callback: ({ generated, funcCode: { funcId } }, metadata) => {
+            callback: (
+              { generated, funcCode: { funcId, code, normalized } },
+              metadata,
+            ) => {
               if (metadata.change_set_id !== selectedChangeSetId) return;
 
+              // The backend rewrote the code we sent, so show what it actually stored
+              if (normalized) {
+                const func = this.funcCodeById[funcId];
+                if (func) func.code = code;
+                if (this.editingFuncLatestCode[funcId] !== undefined) {
+                  this.editingFuncLatestCode[funcId] = code;
+                }
+              }
+
               // TODO we update every time *any* function is generated unless you are in the
               // function editor. That's because we can't tell from here if the function is
               // the asset function editor from here (and we can't useAssetStore() because
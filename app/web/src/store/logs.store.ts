@@ -3,6 +3,7 @@ import * as _ from "lodash-es";
 import { ApiRequest, addStoreHooks } from "@si/vue-lib/pinia";
 import { useWorkspacesStore } from "@/store/workspaces.store";
 import { ChangeSetId } from "@/api/sdf/dal/change_set";
+import { ActorView } from "@/api/sdf/dal/history_actor";
 import keyedDebouncer from "@/utils/keyedDebouncer";
 import { useChangeSetsStore } from "./change_sets.store";
 import { UserId } from "./auth.store";
@@ -26,6 +27,7 @@ export type AuditLogHeaderOptions = {
 
 interface AuditLogCommon {
   title: string;
+  actor: ActorView;
   userId?: UserId;
   userEmail?: string;
   kind: string;
@@ -122,7 +124,12 @@ export const useLogsStore = (forceChangeSetId?: ChangeSetId) => {
                   (log) =>
                     ({
                       title: log.title,
-                      userName: log.userName ?? "System",
+                      actor: log.actor,
+                      userName:
+                        log.userName ??
+                        (log.actor.kind === "machine"
+                          ? log.actor.label
+                          : "System"),
                       userId: log.userId,
                       userEmail: log.userEmail,
                       kind: log.kind,
@@ -216,6 +216,20 @@ export type WsEventPayloadMap = {
         fromComponentId: string;
         toComponentId: string;
       };
+  DependentValuesCycleDetected: {
+    values: {
+      attributeValueId: string;
+      componentId?: ComponentId;
+      description?: string;
+    }[];
+  };
+  DependentValuesUpdateFinished: {
+    changeSetId: ChangeSetId;
+    succeeded: number;
+    failed: number;
+    completed: boolean;
+    durationMs: number;
+  };
 
   ManagementFuncExecuted: {
     managerComponentId: string;
@@ -349,7 +363,9 @@ export type WsEventPayloadMap = {
   FuncCodeSaved: {
     funcCode: {
       funcId: FuncId;
-      // it sends the code but, we are not going to touch it
+      code: string;
+      // only true when the save asked for the code to be normalized and it changed
+      normalized?: boolean;
     };
     generated?: boolean;
   };
@@ -25,6 +25,13 @@ export interface DependentValuesUpdateStatusUpdate {
   timestamp: Date;
 }
 
+export interface DependentValuesUpdateProgressStatusUpdate {
+  kind: "dependentValueUpdateProgress";
+  completed: number;
+  total: number;
+  timestamp: Date;
+}
+
 export interface RebaseStatusUpdate {
   kind: "rebase";
   status: StatusMessageState;
@@ -33,6 +40,7 @@ export interface RebaseStatusUpdate {
 
 export type StatusUpdate =
   | DependentValuesUpdateStatusUpdate
+  | DependentValuesUpdateProgressStatusUpdate
   | RebaseStatusUpdate;
 
 export type GlobalUpdateStatus = {
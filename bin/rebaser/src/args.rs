@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{num::NonZeroUsize, path::PathBuf};
 
 use clap::{ArgAction, Parser};
 use rebaser_server::{Config, ConfigError, ConfigFile, StandardConfigFile};
@@ -55,6 +55,10 @@ pub(crate) struct Args {
     )]
     pub(crate) log_json: bool,
 
+    /// Sets the number of Tokio runtime worker threads [default: one per CPU core]
+    #[arg(long, env = "SI_WORKER_THREADS", hide_env_values = true)]
+    pub(crate) worker_threads: Option<NonZeroUsize>,
+
     /// PostgreSQL connection pool dbname [example: myapp]
     #[arg(long)]
     pub(crate) pg_dbname: Option<String>,
@@ -11,10 +11,11 @@ const BIN_NAME: &str = env!("CARGO_BIN_NAME");
 const LIB_NAME: &str = concat!(env!("CARGO_BIN_NAME"), "_server");
 
 fn main() -> Result<()> {
-    rt::block_on(BIN_NAME, async_main())
+    let args = args::parse();
+    rt::block_on_with_worker_threads(BIN_NAME, args.worker_threads, async_main(args))
 }
 
-async fn async_main() -> Result<()> {
+async fn async_main(args: args::Args) -> Result<()> {
     let main_tracker = TaskTracker::new();
     let main_token = CancellationToken::new();
     let layer_db_tracker = TaskTracker::new();
@@ -23,7 +24,6 @@ async fn async_main() -> Result<()> {
     let telemetry_token = CancellationToken::new();
 
     color_eyre::install()?;
-    let args = args::parse();
     let (mut telemetry, telemetry_shutdown) = {
         let config = TelemetryConfig::builder()
             .force_color(args.force_color.then_some(true))
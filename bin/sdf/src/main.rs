@@ -18,10 +18,11 @@ const LIB_NAME: &str = concat!(env!("CARGO_BIN_NAME"), "_server");
 const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60 * 10);
 
 fn main() -> Result<()> {
-    rt::block_on(BIN_NAME, async_main())
+    let args = args::parse();
+    rt::block_on_with_worker_threads(BIN_NAME, args.worker_threads, async_main(args))
 }
 
-async fn async_main() -> Result<()> {
+async fn async_main(args: args::Args) -> Result<()> {
     let main_tracker = TaskTracker::new();
     let main_token = CancellationToken::new();
     let helping_tasks_tracker = TaskTracker::new();
@@ -30,7 +31,6 @@ async fn async_main() -> Result<()> {
     let telemetry_token = CancellationToken::new();
 
     color_eyre::install()?;
-    let args = args::parse();
     let (mut telemetry, telemetry_shutdown) = {
         let config = TelemetryConfig::builder()
             .force_color(args.force_color.then_some(true))
@@ -103,6 +103,18 @@ async fn async_main() -> Result<()> {
                 telemetry_shutdown,
             )
             .await
+        } else if config.migration_mode().is_dry_run() {
+            migrate_dry_run_and_quit(
+                config,
+                main_tracker,
+                main_token,
+                helping_tasks_tracker,
+                helping_tasks_token,
+                telemetry_tracker,
+                telemetry_token,
+                telemetry_shutdown,
+            )
+            .await
         } else {
             run_server(
                 config,
@@ -155,6 +167,9 @@ async fn run_server(
         server.run().await
     });
 
+    // Shutdown order matters: the server stops accepting requests and drains in-flight work
+    // first, then the helping tasks (e.g. job processors) that work relies on are drained, and
+    // telemetry is flushed last so it can report on both.
     shutdown::graceful()
         .group(main_tracker, main_token)
         .group(helping_tasks_tracker, helping_tasks_token)
@@ -194,6 +209,34 @@ async fn migrate_and_quit(
         .map_err(Into::into)
 }
 
+#[inline]
+#[allow(clippy::too_many_arguments)]
+async fn migrate_dry_run_and_quit(
+    config: Config,
+    main_tracker: TaskTracker,
+    main_token: CancellationToken,
+    helping_tasks_tracker: TaskTracker,
+    helping_tasks_token: CancellationToken,
+    telemetry_tracker: TaskTracker,
+    telemetry_token: CancellationToken,
+    telemetry_shutdown: TelemetryShutdownGuard,
+) -> Result<()> {
+    let migrator =
+        Migrator::from_config(config, &helping_tasks_tracker, helping_tasks_token.clone()).await?;
+
+    let handle = main_tracker.spawn(migrator.dry_run());
+
+    shutdown::graceful_with_handle(handle)
+        .group(main_tracker, main_token)
+        .group(helping_tasks_tracker, helping_tasks_token)
+        .group(telemetry_tracker, telemetry_token)
+        .telemetry_guard(telemetry_shutdown.into_future())
+        .timeout(GRACEFUL_SHUTDOWN_TIMEOUT)
+        .wait()
+        .await
+        .map_err(Into::into)
+}
+
 #[inline]
 async fn generate_veritech_key_pair(
     secret_key_path: PathBuf,
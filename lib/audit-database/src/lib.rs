@@ -39,6 +39,7 @@ use si_events::audit_log::AuditLogMetadata;
 use si_events::ulid;
 use si_events::Actor;
 use si_events::ChangeSetId;
+use si_events::MachinePk;
 use si_events::UserPk;
 use si_events::WorkspacePk;
 use telemetry::prelude::*;
@@ -53,7 +54,7 @@ pub use config::AuditDatabaseConfig;
 pub use config::DBNAME;
 pub use context::AuditDatabaseContext;
 pub use context::AuditDatabaseContextError;
-pub use migrate::{migrate, AuditDatabaseMigrationError};
+pub use migrate::{migrate, pending_migrations, AuditDatabaseMigrationError};
 
 #[allow(missing_docs)]
 #[remain::sorted]
@@ -76,6 +77,9 @@ type Result<T> = std::result::Result<T, AuditDatabaseError>;
 /// A row in the audit logs table of the audit database.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AuditLogRow {
+    /// The primary key of the row. Rows are append-only and keys only ever increase, so it doubles
+    /// as the cursor for [`AuditLogRow::list_paginated`].
+    pub pk: i64,
     /// Indicates the workspace that the row belongs to.
     pub workspace_id: WorkspacePk,
     /// The [kind](AuditLogKind) of the [`AuditLog`] (converted into a string because enum discriminants are not
@@ -87,8 +91,10 @@ pub struct AuditLogRow {
     pub title: String,
     /// The identifier of the change set, which will only be empty for actions taken outside of the workspace.
     pub change_set_id: Option<ChangeSetId>,
-    /// The identifier of the user. If this is empty, it is the system user.
+    /// The identifier of the user. If this and `machine_id` are empty, it is the system user.
     pub user_id: Option<UserPk>,
+    /// The identifier of the machine, if the [`AuditLog`] was performed by a machine identity rather than a user.
+    pub machine_id: Option<MachinePk>,
     /// The entity name.
     pub entity_name: Option<String>,
     /// The entity type.
@@ -119,9 +125,10 @@ impl AuditLogRow {
         entity_name: Option<String>,
     ) -> Result<()> {
         let kind_as_string = kind.to_string();
-        let user_id = match actor {
-            Actor::System => None,
-            Actor::User(user_id) => Some(user_id),
+        let (user_id, machine_id) = match actor {
+            Actor::System => (None, None),
+            Actor::User(user_id) => (Some(user_id), None),
+            Actor::Machine(machine_id) => (None, Some(machine_id)),
         };
 
         let metadata = AuditLogMetadata::from(kind);
@@ -143,7 +150,8 @@ impl AuditLogRow {
                     user_id,
                     entity_name,
                     entity_type,
-                    metadata
+                    metadata,
+                    machine_id
                 ) VALUES (
                     $1,
                     $2,
@@ -153,7 +161,8 @@ impl AuditLogRow {
                     $6,
                     $7,
                     $8,
-                    $9
+                    $9,
+                    $10
                 ) RETURNING *",
                 &[
                     &workspace_id.to_string(),
@@ -165,6 +174,7 @@ impl AuditLogRow {
                     &entity_name,
                     &entity_type,
                     &serialized_metadata,
+                    &machine_id.map(|id| id.to_string()),
                 ],
             )
             .await?;
@@ -216,6 +226,61 @@ impl AuditLogRow {
 
         Ok((result, can_load_more))
     }
+
+    /// Lists a page of at most `limit` rows for the workspace, newest first. Only rows older than
+    /// `before` (a [`pk`](Self::pk) returned as the cursor of the previous page) are included, if
+    /// provided. Returns the rows along with the cursor for the next page, which is `None` once
+    /// there are no older rows. A `limit` of zero is treated as one, since an empty page would
+    /// look like the end of the history.
+    #[instrument(
+        name = "audit_log.database.list_paginated",
+        level = "debug",
+        skip_all,
+        fields(
+            si.workspace.id = %workspace_id,
+        ),
+    )]
+    pub async fn list_paginated(
+        context: &AuditDatabaseContext,
+        workspace_id: WorkspacePk,
+        before: Option<i64>,
+        limit: usize,
+    ) -> Result<(Vec<Self>, Option<i64>)> {
+        let limit = limit.max(1);
+        // Fetch one more than asked for to know whether there is another page.
+        let fetch_limit = limit as i64 + 1;
+
+        let client = context.pg_pool().get().await?;
+        let rows = client
+            .query(
+                "SELECT * from audit_logs WHERE workspace_id = $1 AND ($2::bigint IS NULL OR pk < $2) ORDER BY pk DESC LIMIT $3",
+                &[&workspace_id, &before, &fetch_limit],
+            )
+            .await?;
+
+        let mut result = Vec::with_capacity(rows.len());
+        for row in rows {
+            result.push(Self::try_from(row)?);
+        }
+
+        let next_before = if result.len() > limit {
+            result.truncate(limit);
+            result.last().map(|row| row.pk)
+        } else {
+            None
+        };
+
+        Ok((result, next_before))
+    }
+
+    /// Returns the [`Actor`] that performed the [`AuditLog`], as recorded by [`Self::insert`].
+    pub fn actor(&self) -> Actor {
+        match (self.user_id, self.machine_id) {
+            (Some(user_id), _) => Actor::User(user_id),
+            (None, Some(machine_id)) => Actor::Machine(machine_id),
+            (None, None) => Actor::System,
+        }
+    }
 }
 
 impl TryFrom<PgRow> for AuditLogRow {
@@ -240,14 +305,23 @@ impl TryFrom<PgRow> for AuditLogRow {
                 None => None,
             }
         };
+        let machine_id = {
+            let maybe_inner: Option<String> = value.try_get("machine_id")?;
+            match maybe_inner {
+                Some(inner) => Some(MachinePk::from_str(&inner)?),
+                None => None,
+            }
+        };
 
         Ok(Self {
+            pk: value.try_get("pk")?,
             workspace_id,
             kind: value.try_get("kind")?,
             timestamp: value.try_get("timestamp")?,
             title: value.try_get("title")?,
             change_set_id,
             user_id,
+            machine_id,
             entity_name: value.try_get("entity_name")?,
             entity_type: value.try_get("entity_type")?,
             metadata: value.try_get("metadata")?,
@@ -22,6 +22,18 @@ pub async fn migrate(context: &AuditDatabaseContext) -> Result<()> {
     migrate_inner(context.pg_pool()).await
 }
 
+/// Returns the names of the migrations that [`migrate`] would apply, without applying any of them.
+#[instrument(level = "info", name = "audit.init.pending_migrations", skip_all)]
+pub async fn pending_migrations(context: &AuditDatabaseContext) -> Result<Vec<String>> {
+    Ok(context
+        .pg_pool()
+        .pending_migrations(embedded::migrations::runner())
+        .await?
+        .iter()
+        .map(ToString::to_string)
+        .collect())
+}
+
 #[instrument(level = "info", name = "audit.init.migrate.inner", skip_all)]
 async fn migrate_inner(pg: &PgPool) -> Result<()> {
     pg.migrate(embedded::migrations::runner()).await?;
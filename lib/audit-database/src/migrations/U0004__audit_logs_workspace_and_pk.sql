@@ -0,0 +1 @@
+CREATE INDEX audit_logs_workspace_and_pk ON audit_logs (workspace_id, pk);
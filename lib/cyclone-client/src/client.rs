@@ -449,8 +449,8 @@ mod tests {
     use buck2_resources::Buck2Resources;
     use cyclone_core::{
         ActionRunRequest, ComponentKind, ComponentView, ComponentViewWithGeometry, FunctionResult,
-        ManagementRequest, ProgressMessage, ResolverFunctionComponent, ResolverFunctionRequest,
-        SchemaVariantDefinitionRequest, ValidationRequest,
+        ManagementRequest, Message, ProgressMessage, ResolverFunctionComponent,
+        ResolverFunctionRequest, SchemaVariantDefinitionRequest, ValidationRequest,
     };
     use cyclone_server::{Config, ConfigBuilder, Runnable as _, Server};
     use futures::StreamExt;
@@ -1160,6 +1160,59 @@ mod tests {
         }
     }
 
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn http_execute_action_run_past_request_timeout_is_killed() {
+        let mut builder = Config::builder();
+        let mut client = http_client_for_running_server(builder.enable_action_run(true)).await;
+
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: Default::default(),
+            code_base64: base64_encode(
+                r#"async function workit() {
+                    await new Promise((resolve) => setTimeout(resolve, 60000));
+                    return { status: 'ok' };
+                }"#,
+            ),
+            before: vec![],
+        };
+
+        // Start the protocol
+        let mut progress = client
+            .prepare_execution(
+                CycloneRequest::from_parts(req, Default::default())
+                    .with_timeout(Duration::from_secs(1)),
+            )
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        // The function sleeps for a minute, so the request timeout must kill it well before then
+        let fail_message = tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                match progress.next().await {
+                    Some(Ok(_)) => continue,
+                    Some(Err(crate::ExecutionError::UnexpectedMessage(Message::Fail(fail)))) => {
+                        break fail.message;
+                    }
+                    Some(Err(err)) => panic!("unexpected error while waiting for kill: {err:?}"),
+                    None => panic!("output stream ended without the execution being killed"),
+                }
+            }
+        })
+        .await
+        .expect("execution was not killed by the request timeout");
+
+        assert!(
+            fail_message.contains("ChildTimeout"),
+            "execution should fail with a timeout: {fail_message}"
+        );
+    }
+
     #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
     #[test(tokio::test)]
     async fn http_execute_schema_variant_definition() {
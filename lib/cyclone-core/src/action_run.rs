@@ -53,4 +53,8 @@ impl CycloneRequestable for ActionRunRequest {
     fn dec_run_metric(&self) {
         metric!(counter.function_run.action = -1);
     }
+
+    fn sort_before_functions(&mut self) {
+        crate::before::sort_before_functions(&mut self.before);
+    }
 }
@@ -7,4 +7,64 @@ pub struct BeforeFunction {
     pub handler: String,
     pub code_base64: String,
     pub arg: Value,
+    /// Before functions run in ascending `order`. Ties keep the order in which the functions were
+    /// provided, so payloads without an explicit order (which default to `0`) run as listed.
+    #[serde(default)]
+    pub order: i32,
+}
+
+/// Sorts before functions into the order they must run in.
+///
+/// The sort is stable: functions with the same `order` keep their relative positions.
+pub fn sort_before_functions(before: &mut [BeforeFunction]) {
+    before.sort_by_key(|before_function| before_function.order);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn before_function(handler: &str, order: i32) -> BeforeFunction {
+        BeforeFunction {
+            handler: handler.to_string(),
+            code_base64: String::new(),
+            arg: Value::Null,
+            order,
+        }
+    }
+
+    #[test]
+    fn sort_is_stable_by_order() {
+        let mut before = vec![
+            before_function("c", 1),
+            before_function("a", 0),
+            before_function("d", 1),
+            before_function("first", -1),
+            before_function("b", 0),
+        ];
+
+        sort_before_functions(&mut before);
+
+        assert_eq!(
+            vec!["first", "a", "b", "c", "d"],
+            before
+                .iter()
+                .map(|before_function| before_function.handler.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn missing_order_defaults_to_zero() {
+        let before: BeforeFunction = serde_json::from_value(json!({
+            "handler": "auth",
+            "codeBase64": "",
+            "arg": null,
+        }))
+        .expect("should deserialize");
+
+        assert_eq!(0, before.order);
+    }
 }
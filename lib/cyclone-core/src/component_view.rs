@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 #[remain::sorted]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Copy)]
@@ -31,6 +33,37 @@ impl Default for ComponentView {
     }
 }
 
+impl ComponentView {
+    /// Flattens nested objects in `properties` into a single map keyed by `/`-joined paths (e.g.
+    /// `domain/name`). Arrays, scalars and empty objects are kept whole at their path.
+    pub fn flat_properties(&self) -> HashMap<String, Value> {
+        let mut flat = HashMap::new();
+        if let Value::Object(properties) = &self.properties {
+            flatten_into(&mut flat, None, properties);
+        }
+        flat
+    }
+}
+
+fn flatten_into(
+    flat: &mut HashMap<String, Value>,
+    prefix: Option<&str>,
+    object: &Map<String, Value>,
+) {
+    for (key, value) in object {
+        let path = match prefix {
+            Some(prefix) => format!("{prefix}/{key}"),
+            None => key.to_owned(),
+        };
+        match value {
+            Value::Object(inner) if !inner.is_empty() => flatten_into(flat, Some(&path), inner),
+            _ => {
+                flat.insert(path, value.clone());
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComponentViewWithGeometry {
@@ -49,3 +82,77 @@ impl Default for ComponentViewWithGeometry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn flat_properties_joins_nested_object_keys() {
+        let view = ComponentView {
+            kind: ComponentKind::Standard,
+            properties: json!({
+                "si": { "name": "web", "color": "#ffffff" },
+                "domain": {
+                    "image": "nginx",
+                    "ports": ["80/tcp", "443/tcp"],
+                    "nested": { "deeper": { "value": 42 } },
+                    "empty": {},
+                },
+            }),
+        };
+
+        let flat = view.flat_properties();
+
+        let expected_keys: HashSet<&str> = [
+            "si/name",
+            "si/color",
+            "domain/image",
+            "domain/ports",
+            "domain/nested/deeper/value",
+            "domain/empty",
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            expected_keys,
+            flat.keys().map(String::as_str).collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            Some(&json!(["80/tcp", "443/tcp"])),
+            flat.get("domain/ports")
+        );
+        assert_eq!(Some(&json!(42)), flat.get("domain/nested/deeper/value"));
+        assert_eq!(Some(&json!({})), flat.get("domain/empty"));
+    }
+
+    #[test]
+    fn flat_properties_keeps_arrays_of_objects_whole() {
+        let view = ComponentView {
+            kind: ComponentKind::Standard,
+            properties: json!({ "tags": [{ "key": "a" }, { "key": "b" }] }),
+        };
+
+        let flat = view.flat_properties();
+
+        assert_eq!(1, flat.len());
+        assert_eq!(
+            Some(&json!([{ "key": "a" }, { "key": "b" }])),
+            flat.get("tags")
+        );
+    }
+
+    #[test]
+    fn flat_properties_of_non_object_is_empty() {
+        let view = ComponentView {
+            kind: ComponentKind::Standard,
+            properties: Value::Null,
+        };
+
+        assert!(view.flat_properties().is_empty());
+    }
+}
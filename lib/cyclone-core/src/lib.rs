@@ -38,7 +38,7 @@ pub use liveness::{LivenessStatus, LivenessStatusParseError};
 pub use management::{ManagementFuncStatus, ManagementRequest, ManagementResultSuccess};
 pub use progress::{
     FunctionResult, FunctionResultFailure, FunctionResultFailureError,
-    FunctionResultFailureErrorKind, Message, OutputStream, ProgressMessage,
+    FunctionResultFailureErrorKind, Message, OutputStream, OutputStreamEncoding, ProgressMessage,
 };
 pub use readiness::{ReadinessStatus, ReadinessStatusParseError};
 pub use request::{CycloneRequest, CycloneRequestable};
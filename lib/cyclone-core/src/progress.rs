@@ -1,3 +1,7 @@
+use std::io::{self, Read, Write};
+
+use base64::{engine::general_purpose, Engine};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use strum::Display;
 
@@ -28,11 +32,69 @@ pub struct OutputStream {
     /// Group can be used upstream (i.e. a frontend UI) to group sets of `OutputStream`s together.
     pub group: Option<String>,
     /// The contents of the output line.
+    ///
+    /// How the contents are encoded is determined by [`encoding`](Self::encoding).
     pub message: String,
     /// A timestamp in seconds since UNIX epoch.
     ///
     /// The timestamp generated locally when the message was created.
     pub timestamp: u64,
+    /// The encoding of the [`message`](Self::message).
+    ///
+    /// Absent on the wire (and so understood by older receivers) when the message is plain text.
+    #[serde(default, skip_serializing_if = "OutputStreamEncoding::is_plain")]
+    pub encoding: OutputStreamEncoding,
+}
+
+impl OutputStream {
+    /// Gzip compresses and base64 encodes the message, if it is not already compressed.
+    pub fn compress(mut self) -> io::Result<Self> {
+        if self.encoding == OutputStreamEncoding::GzipBase64 {
+            return Ok(self);
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(self.message.as_bytes())?;
+        self.message = general_purpose::STANDARD.encode(encoder.finish()?);
+        self.encoding = OutputStreamEncoding::GzipBase64;
+
+        Ok(self)
+    }
+
+    /// Restores a plain text message, decompressing it if needed.
+    pub fn decompress(mut self) -> io::Result<Self> {
+        if self.encoding == OutputStreamEncoding::Plain {
+            return Ok(self);
+        }
+
+        let compressed = general_purpose::STANDARD
+            .decode(&self.message)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let mut message = String::new();
+        GzDecoder::new(compressed.as_slice()).read_to_string(&mut message)?;
+        self.message = message;
+        self.encoding = OutputStreamEncoding::Plain;
+
+        Ok(self)
+    }
+}
+
+/// The encoding of an [`OutputStream`] message.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputStreamEncoding {
+    /// Gzip compressed, then base64 encoded.
+    GzipBase64,
+    /// Plain text.
+    #[default]
+    Plain,
+}
+
+impl OutputStreamEncoding {
+    fn is_plain(&self) -> bool {
+        *self == Self::Plain
+    }
 }
 
 /// A message produced as a function is executing.
@@ -164,3 +226,45 @@ pub struct FunctionResultFailureError {
 pub struct Fail {
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn output() -> OutputStream {
+        OutputStream {
+            stream: "stdout".to_string(),
+            execution_id: "poop".to_string(),
+            level: "info".to_string(),
+            group: None,
+            message: "the cheese is old and moldy".repeat(8),
+            timestamp: 0,
+            encoding: OutputStreamEncoding::default(),
+        }
+    }
+
+    #[test]
+    fn compress_round_trip() {
+        let compressed = output().compress().expect("should compress");
+        assert_eq!(OutputStreamEncoding::GzipBase64, compressed.encoding);
+        assert_ne!(output().message, compressed.message);
+
+        let decompressed = compressed.decompress().expect("should decompress");
+        assert_eq!(output(), decompressed);
+    }
+
+    #[test]
+    fn plain_encoding_is_omitted_on_the_wire() {
+        let value = serde_json::to_value(output()).expect("should serialize");
+        assert!(value.get("encoding").is_none());
+
+        let deserialized: OutputStream = serde_json::from_value(value).expect("should deserialize");
+        assert_eq!(OutputStreamEncoding::Plain, deserialized.encoding);
+
+        let value = serde_json::to_value(output().compress().expect("should compress"))
+            .expect("should serialize");
+        assert_eq!(json!("gzipBase64"), value["encoding"]);
+    }
+}
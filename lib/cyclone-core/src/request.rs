@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use si_crypto::SensitiveStrings;
@@ -12,6 +12,24 @@ where
 {
     request: R,
     sensitive_strings: HashSet<SensitiveString>,
+    /// An optional per-request execution timeout, serialized in milliseconds.
+    ///
+    /// When set, the executor kills the execution once it has run for this long. It never extends
+    /// an execution past the worker-level timeout (i.e. veritech's
+    /// `cyclone_client_execution_timeout`): whichever of the two elapses first wins. Requests
+    /// without a timeout are bounded only by the worker-level timeout.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "timeout_millis"
+    )]
+    timeout: Option<Duration>,
+    /// Whether the executor should compress [`OutputStream`](crate::OutputStream) messages.
+    ///
+    /// Only set this when every receiver of the output can decompress it. Defaults to `false` so
+    /// that mixed-version deployments keep exchanging plain text output.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    compress_output: bool,
 }
 
 impl<R> CycloneRequest<R>
@@ -22,9 +40,29 @@ where
         Self {
             request,
             sensitive_strings: sensitive_strings.into(),
+            timeout: None,
+            compress_output: false,
         }
     }
 
+    pub fn with_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn with_compressed_output(mut self, compress_output: bool) -> Self {
+        self.compress_output = compress_output;
+        self
+    }
+
+    pub fn compress_output(&self) -> bool {
+        self.compress_output
+    }
+
     pub fn websocket_path(&self) -> &str {
         self.request.websocket_path()
     }
@@ -34,6 +72,31 @@ where
     }
 }
 
+mod timeout_millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(timeout: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match timeout {
+            Some(timeout) => {
+                serializer.serialize_some(&u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+    }
+}
+
 pub trait CycloneRequestable {
     type Response;
 
@@ -41,4 +104,61 @@ pub trait CycloneRequestable {
     fn websocket_path(&self) -> &str;
     fn inc_run_metric(&self);
     fn dec_run_metric(&self);
+
+    /// Puts any [`BeforeFunctions`](crate::BeforeFunction) into the order they must run in.
+    /// Requests without before functions need not implement this.
+    fn sort_before_functions(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::KillExecutionRequest;
+
+    fn request() -> CycloneRequest<KillExecutionRequest> {
+        CycloneRequest::from_parts(
+            KillExecutionRequest {
+                execution_id: "poop".to_string(),
+            },
+            SensitiveStrings::default(),
+        )
+    }
+
+    #[test]
+    fn timeout_serializes_as_millis() {
+        let request = request().with_timeout(Duration::from_millis(1500));
+
+        let value = serde_json::to_value(&request).expect("should serialize");
+        assert_eq!(json!(1500), value["timeout"]);
+
+        let deserialized: CycloneRequest<KillExecutionRequest> =
+            serde_json::from_value(value).expect("should deserialize");
+        assert_eq!(Some(Duration::from_millis(1500)), deserialized.timeout());
+    }
+
+    #[test]
+    fn missing_timeout_is_none() {
+        let value = serde_json::to_value(request()).expect("should serialize");
+        assert!(value.get("timeout").is_none());
+
+        let deserialized: CycloneRequest<KillExecutionRequest> =
+            serde_json::from_value(value).expect("should deserialize");
+        assert_eq!(None, deserialized.timeout());
+    }
+
+    #[test]
+    fn compress_output_defaults_to_false() {
+        let value = serde_json::to_value(request()).expect("should serialize");
+        assert!(value.get("compressOutput").is_none());
+
+        let deserialized: CycloneRequest<KillExecutionRequest> =
+            serde_json::from_value(value).expect("should deserialize");
+        assert!(!deserialized.compress_output());
+
+        let value =
+            serde_json::to_value(request().with_compressed_output(true)).expect("should serialize");
+        assert_eq!(json!(true), value["compressOutput"]);
+    }
 }
@@ -4,7 +4,12 @@ use serde_json::Value;
 use telemetry::prelude::*;
 use telemetry_utils::metric;
 
-use crate::ComponentView;
+use crate::{
+    ComponentView, FunctionResult, FunctionResultFailure, FunctionResultFailureError,
+    FunctionResultFailureErrorKind,
+};
+
+const QUALIFICATION_STATUSES: &[&str] = &["warning", "failure", "success", "unknown"];
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -46,6 +51,76 @@ pub enum ResolverFunctionResponseType {
     Void,
 }
 
+impl ResolverFunctionResponseType {
+    /// Checks that a returned value has the shape this response type declares, returning a
+    /// message describing the mismatch if it does not.
+    ///
+    /// These checks mirror the ones performed by the language server. Response types without a
+    /// declared shape accept any value.
+    pub fn validate(&self, data: &Value) -> Result<(), String> {
+        if data.is_null() {
+            return match self {
+                Self::CodeGeneration | Self::Qualification => {
+                    Err("Return type cannot be null or undefined".to_string())
+                }
+                _ => Ok(()),
+            };
+        }
+
+        match self {
+            Self::Array if !data.is_array() => Err("Return type must be an array.".to_string()),
+            Self::Boolean if !data.is_boolean() => {
+                Err("Return type must be a boolean.".to_string())
+            }
+            Self::Integer if !(data.is_i64() || data.is_u64()) => {
+                Err("Return type must be an integer.".to_string())
+            }
+            Self::Map | Self::Object if !data.is_object() => {
+                Err("Return type must be an object.".to_string())
+            }
+            Self::String if !data.is_string() => Err("Return type must be a string.".to_string()),
+            Self::CodeGeneration => validate_code_generation(data),
+            Self::Qualification => validate_qualification(data),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn validate_code_generation(data: &Value) -> Result<(), String> {
+    let Some(object) = data.as_object() else {
+        return Err(
+            "CodeGenerations must return an object with 'format' and 'code' fields".to_string(),
+        );
+    };
+    if !object.get("format").is_some_and(Value::is_string) {
+        return Err("The format field type must be a string".to_string());
+    }
+    if !object.get("code").is_some_and(Value::is_string) {
+        return Err("The code field type must be a string".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_qualification(data: &Value) -> Result<(), String> {
+    let Some(object) = data.as_object() else {
+        return Err("A qualification must return an object.".to_string());
+    };
+    let Some(result) = object.get("result").and_then(Value::as_str) else {
+        return Err("Qualification result field type must be a string".to_string());
+    };
+    if !QUALIFICATION_STATUSES.contains(&result) {
+        return Err(
+            "Qualification result must be one of 'success' | 'warning' | 'failure'".to_string(),
+        );
+    }
+    if result != "success" && !object.get("message").is_some_and(Value::is_string) {
+        return Err("The Qualification message field type must be a string, and must be present unless the status is success".to_string());
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolverFunctionResultSuccess {
@@ -55,6 +130,25 @@ pub struct ResolverFunctionResultSuccess {
     pub timestamp: u64,
 }
 
+impl ResolverFunctionResultSuccess {
+    /// Validates the returned [`data`](Self::data) against the declared response type, turning
+    /// a mismatch into a [`FunctionResultFailure`] of kind
+    /// [`InvalidReturnType`](FunctionResultFailureErrorKind::InvalidReturnType).
+    pub fn validate(self, response_type: &ResolverFunctionResponseType) -> FunctionResult<Self> {
+        match response_type.validate(&self.data) {
+            Ok(()) => FunctionResult::Success(self),
+            Err(message) => FunctionResult::Failure(FunctionResultFailure::new(
+                self.execution_id,
+                FunctionResultFailureError {
+                    kind: FunctionResultFailureErrorKind::InvalidReturnType,
+                    message,
+                },
+                self.timestamp,
+            )),
+        }
+    }
+}
+
 impl CycloneRequestable for ResolverFunctionRequest {
     type Response = ResolverFunctionResultSuccess;
 
@@ -73,4 +167,150 @@ impl CycloneRequestable for ResolverFunctionRequest {
     fn dec_run_metric(&self) {
         metric!(counter.function_run.resolver = -1);
     }
+
+    fn sort_before_functions(&mut self) {
+        crate::before::sort_before_functions(&mut self.before);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn validate(
+        response_type: ResolverFunctionResponseType,
+        data: Value,
+    ) -> FunctionResult<ResolverFunctionResultSuccess> {
+        ResolverFunctionResultSuccess {
+            execution_id: "poop".to_string(),
+            data,
+            unset: false,
+            timestamp: 0,
+        }
+        .validate(&response_type)
+    }
+
+    fn assert_valid(response_type: ResolverFunctionResponseType, data: Value) {
+        assert!(
+            matches!(
+                validate(response_type.clone(), data.clone()),
+                FunctionResult::Success(_)
+            ),
+            "expected {data} to be a valid {response_type:?}"
+        );
+    }
+
+    fn assert_invalid(response_type: ResolverFunctionResponseType, data: Value) {
+        match validate(response_type.clone(), data.clone()) {
+            FunctionResult::Failure(failure) => {
+                assert_eq!("poop", failure.execution_id());
+                assert_eq!(
+                    FunctionResultFailureErrorKind::InvalidReturnType,
+                    failure.error().kind
+                );
+            }
+            FunctionResult::Success(_) => {
+                panic!("expected {data} to be an invalid {response_type:?}")
+            }
+        }
+    }
+
+    #[test]
+    fn array() {
+        assert_valid(ResolverFunctionResponseType::Array, json!([1, "two"]));
+        assert_valid(ResolverFunctionResponseType::Array, Value::Null);
+        assert_invalid(ResolverFunctionResponseType::Array, json!({"a": 1}));
+    }
+
+    #[test]
+    fn boolean() {
+        assert_valid(ResolverFunctionResponseType::Boolean, json!(false));
+        assert_valid(ResolverFunctionResponseType::Boolean, Value::Null);
+        assert_invalid(ResolverFunctionResponseType::Boolean, json!("true"));
+    }
+
+    #[test]
+    fn integer() {
+        assert_valid(ResolverFunctionResponseType::Integer, json!(-42));
+        assert_valid(ResolverFunctionResponseType::Integer, Value::Null);
+        assert_invalid(ResolverFunctionResponseType::Integer, json!(4.2));
+        assert_invalid(ResolverFunctionResponseType::Integer, json!("42"));
+    }
+
+    #[test]
+    fn map() {
+        assert_valid(ResolverFunctionResponseType::Map, json!({"a": "b"}));
+        assert_valid(ResolverFunctionResponseType::Map, Value::Null);
+        assert_invalid(ResolverFunctionResponseType::Map, json!("a map"));
+        assert_invalid(ResolverFunctionResponseType::Map, json!(["a", "b"]));
+    }
+
+    #[test]
+    fn object() {
+        assert_valid(ResolverFunctionResponseType::Object, json!({"a": {"b": 1}}));
+        assert_valid(ResolverFunctionResponseType::Object, Value::Null);
+        assert_invalid(ResolverFunctionResponseType::Object, json!([{"a": 1}]));
+    }
+
+    #[test]
+    fn string() {
+        assert_valid(ResolverFunctionResponseType::String, json!("poop"));
+        assert_valid(ResolverFunctionResponseType::String, Value::Null);
+        assert_invalid(ResolverFunctionResponseType::String, json!(1));
+    }
+
+    #[test]
+    fn code_generation() {
+        assert_valid(
+            ResolverFunctionResponseType::CodeGeneration,
+            json!({"format": "json", "code": "{}"}),
+        );
+        assert_invalid(ResolverFunctionResponseType::CodeGeneration, Value::Null);
+        assert_invalid(
+            ResolverFunctionResponseType::CodeGeneration,
+            json!({"format": "json"}),
+        );
+        assert_invalid(
+            ResolverFunctionResponseType::CodeGeneration,
+            json!({"format": 1, "code": "{}"}),
+        );
+    }
+
+    #[test]
+    fn qualification() {
+        assert_valid(
+            ResolverFunctionResponseType::Qualification,
+            json!({"result": "success"}),
+        );
+        assert_valid(
+            ResolverFunctionResponseType::Qualification,
+            json!({"result": "failure", "message": "it broke"}),
+        );
+        assert_invalid(ResolverFunctionResponseType::Qualification, Value::Null);
+        assert_invalid(
+            ResolverFunctionResponseType::Qualification,
+            json!({"result": "failure"}),
+        );
+        assert_invalid(
+            ResolverFunctionResponseType::Qualification,
+            json!({"result": "great"}),
+        );
+    }
+
+    #[test]
+    fn unchecked_types_accept_anything() {
+        for response_type in [
+            ResolverFunctionResponseType::Action,
+            ResolverFunctionResponseType::Identity,
+            ResolverFunctionResponseType::Json,
+            ResolverFunctionResponseType::Management,
+            ResolverFunctionResponseType::Unset,
+            ResolverFunctionResponseType::Void,
+        ] {
+            assert_valid(response_type.clone(), json!("anything"));
+            assert_valid(response_type, Value::Null);
+        }
+    }
 }
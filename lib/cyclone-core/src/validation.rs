@@ -39,4 +39,8 @@ impl CycloneRequestable for ValidationRequest {
     fn dec_run_metric(&self) {
         metric!(counter.function_run.validation = -1);
     }
+
+    fn sort_before_functions(&mut self) {
+        crate::before::sort_before_functions(&mut self.before);
+    }
 }
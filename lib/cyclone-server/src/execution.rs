@@ -33,6 +33,9 @@ use crate::WebSocketMessage;
 
 const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
 const DEFAULT_LANG_SERVER_PROCESS_TIMEOUT: Duration = Duration::from_secs(32 * 60);
+// Below this size the gzip header and base64 overhead outweigh any savings, so shorter output
+// lines are sent as plain text even when compression was requested.
+const COMPRESS_OUTPUT_MIN_BYTES: usize = 1024;
 
 pub fn new<Request, LangServerSuccess, Success>(
     lang_server_path: impl Into<PathBuf>,
@@ -80,6 +83,8 @@ pub enum ExecutionError {
     JSONDeserialize(#[source] serde_json::Error),
     #[error("failed to serialize json message")]
     JSONSerialize(#[source] serde_json::Error),
+    #[error("failed to compress output message")]
+    OutputCompress(#[source] io::Error),
     #[error("send timeout")]
     SendTimeout(#[source] tokio::time::error::Elapsed),
     #[error("unexpected websocket message type: {0:?}")]
@@ -125,7 +130,15 @@ where
         Self::ws_send_start(ws).await?;
         // Read the request message from the web socket
         let cyclone_request = Self::read_request(ws).await?;
-        let (request, sensitive_strings) = cyclone_request.into_parts();
+        let compress_output = cyclone_request.compress_output();
+        // A per-request timeout can only shorten the execution, never extend it past the lang
+        // server process timeout.
+        let lang_server_process_timeout = match cyclone_request.timeout() {
+            Some(timeout) => timeout.min(self.lang_server_process_timeout),
+            None => self.lang_server_process_timeout,
+        };
+        let (mut request, sensitive_strings) = cyclone_request.into_parts();
+        request.sort_before_functions();
 
         // Spawn lang server as a child process with handles on all i/o descriptors
         let mut command = Command::new(&self.lang_server_path);
@@ -172,8 +185,9 @@ where
             stdout,
             stderr,
             sensitive_strings: Arc::new(sensitive_strings),
+            compress_output,
             success_marker: self.success_marker,
-            lang_server_process_timeout: self.lang_server_process_timeout,
+            lang_server_process_timeout,
         })
     }
 
@@ -231,6 +245,7 @@ pub struct ExecutionStarted<LangServerSuccess, Success> {
     stdout: SiFramed<SiMessage<LangServerSuccess>>,
     stderr: FramedRead<ChildStderr, BytesLinesCodec>,
     sensitive_strings: Arc<SensitiveStrings>,
+    compress_output: bool,
     success_marker: PhantomData<Success>,
     lang_server_process_timeout: Duration,
 }
@@ -274,7 +289,15 @@ where
                 Ok(ls_msg) => match ls_msg {
                     LangServerMessage::Output(mut output) => {
                         Self::filter_output(&mut output, &self.sensitive_strings)?;
-                        Ok(Message::OutputStream(output.into()))
+                        let output: OutputStream = output.into();
+                        if self.compress_output && output.message.len() >= COMPRESS_OUTPUT_MIN_BYTES
+                        {
+                            Ok(Message::OutputStream(
+                                output.compress().map_err(ExecutionError::OutputCompress)?,
+                            ))
+                        } else {
+                            Ok(Message::OutputStream(output))
+                        }
                     }
                     LangServerMessage::Result(mut result) => {
                         Self::filter_result(&mut result, &self.sensitive_strings)?;
@@ -455,6 +478,7 @@ impl From<LangServerOutput> for OutputStream {
             group: value.group,
             message: value.message,
             timestamp: crate::timestamp(),
+            encoding: Default::default(),
         }
     }
 }
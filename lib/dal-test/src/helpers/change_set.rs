@@ -88,7 +88,7 @@ impl ChangeSetTestHelpers {
             .detect_updates_that_will_be_applied(ctx)
             .await?;
 
-        let applied_change_set = ChangeSet::apply_to_base_change_set(ctx).await?;
+        let (applied_change_set, _) = ChangeSet::apply_to_base_change_set(ctx).await?;
 
         ctx.update_visibility_and_snapshot_to_visibility(
             applied_change_set.base_change_set_id.ok_or(eyre!(
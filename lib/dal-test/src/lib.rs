@@ -684,6 +684,10 @@ pub async fn pinga_server(
     let server = pinga_server::Server::from_services(
         config.instance_id(),
         config.concurrency_limit(),
+        config.job_max_attempts(),
+        config.job_retry_base_delay(),
+        config.drain_timeout(),
+        pinga_server::JobRegistry::with_builtin_jobs().with_timeouts(config.job_timeouts()),
         services_context,
         shutdown_token,
     )
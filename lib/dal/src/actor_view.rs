@@ -7,13 +7,20 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{DalContext, HistoryActor, StandardModelError, User, UserPk};
+use crate::{DalContext, HistoryActor, MachinePk, StandardModelError, User, UserPk};
 
 /// The actor entitiy that initiates an activitiy--this could represent be a person, service, etc.
 #[remain::sorted]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum ActorView {
+    /// Represents a non-human identity (e.g. a service account) by its [`MachinePk`]
+    Machine {
+        /// A machine's ID
+        pk: MachinePk,
+        /// A display label
+        label: String,
+    },
     /// Represents a system-generated activity
     System {
         /// A display label
@@ -32,10 +39,16 @@ pub enum ActorView {
 }
 
 impl ActorView {
-    fn system_label() -> String {
+    /// The display label of the system actor.
+    pub fn system_label() -> String {
         "system".to_string()
     }
 
+    /// The display label of a machine actor, which has no name of its own.
+    pub fn machine_label(pk: MachinePk) -> String {
+        format!("machine:{pk}")
+    }
+
     /// Converts a [`HistoryActor`] and returns an `ActorView`.
     ///
     /// # Errors
@@ -57,6 +70,10 @@ impl ActorView {
                     email: Some(user.email().to_string()),
                 })
             }
+            HistoryActor::Machine(pk) => Ok(Self::Machine {
+                pk,
+                label: Self::machine_label(pk),
+            }),
             HistoryActor::SystemInit => Ok(Self::System {
                 label: Self::system_label(),
             }),
@@ -71,7 +71,9 @@ use crate::workspace_snapshot::edge_weight::{EdgeWeightKind, EdgeWeightKindDiscr
 use crate::workspace_snapshot::node_weight::{
     AttributeValueNodeWeight, NodeWeight, NodeWeightDiscriminants, NodeWeightError,
 };
-use crate::workspace_snapshot::{serde_value_to_string_type, WorkspaceSnapshotError};
+use crate::workspace_snapshot::{
+    serde_value_to_string_type, DependentValueRoot, WorkspaceSnapshotError,
+};
 use crate::{
     implement_add_edge_to, AttributePrototype, AttributePrototypeId, Component, ComponentError,
     ComponentId, DalContext, Func, FuncError, FuncId, HelperError, InputSocket, InputSocketId,
@@ -848,6 +850,29 @@ impl AttributeValue {
         Ok(Func::get_by_id_or_error(ctx, prototype_func_id).await?)
     }
 
+    /// Returns the ids of every [`AttributeValue`] that would be recomputed if the given value
+    /// changed, sorted and excluding the value itself. This builds the same
+    /// [`DependentValueGraph`] that a dependent values update would, but only reads the graph and
+    /// does not enqueue anything.
+    pub async fn dependents(
+        ctx: &DalContext,
+        attribute_value_id: AttributeValueId,
+    ) -> AttributeValueResult<Vec<AttributeValueId>> {
+        let dependent_value_graph = DependentValueGraph::new(
+            ctx,
+            vec![DependentValueRoot::Unfinished(attribute_value_id.into())],
+        )
+        .await?;
+
+        let mut dependents: Vec<AttributeValueId> = dependent_value_graph
+            .all_value_ids()
+            .into_iter()
+            .filter(|value_id| *value_id != attribute_value_id)
+            .collect();
+        dependents.sort();
+        Ok(dependents)
+    }
+
     pub async fn is_set_by_dependent_function(
         ctx: &DalContext,
         attribute_value_id: AttributeValueId,
@@ -485,6 +485,17 @@ impl DependentValueGraph {
         self.inner.all_ids()
     }
 
+    /// Returns each set of values that depend on one another in a cycle. These values will never
+    /// become independent, so their functions will never be executed.
+    pub fn value_cycles(&self) -> Vec<Vec<AttributeValueId>> {
+        self.inner.cycles()
+    }
+
+    /// The number of values remaining in the graph.
+    pub fn value_count(&self) -> usize {
+        self.inner.len()
+    }
+
     /// Indicates whether the value needs to be processed. This is useful for determining when to
     /// filter or de-duplicate values when executing from their prototype functions. If the value is
     /// marked as needing to be processed, it likely needs to execute from its prototype function.
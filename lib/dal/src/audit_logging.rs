@@ -286,6 +286,31 @@ pub async fn list(
     .await?)
 }
 
+/// The most audit logs [`list_paginated`] returns in one page, regardless of the requested limit.
+pub const MAX_AUDIT_LOG_PAGE_SIZE: usize = 200;
+
+/// Lists a page of the workspace's audit logs, across all change sets, newest first. Pass the
+/// returned cursor as `before` to get the next page; it is `None` on the last page. The `limit` is
+/// clamped to between 1 and [`MAX_AUDIT_LOG_PAGE_SIZE`], since an empty page would look like the
+/// end of the history.
+#[instrument(
+    name = "audit_logging.list_paginated",
+    level = "debug",
+    skip_all,
+    fields(limit)
+)]
+pub async fn list_paginated(
+    ctx: &DalContext,
+    audit_database_context: &AuditDatabaseContext,
+    before: Option<i64>,
+    limit: usize,
+) -> Result<(Vec<AuditLogRow>, Option<i64>)> {
+    let workspace_id = ctx.workspace_pk().map_err(Box::new)?;
+    let limit = limit.clamp(1, MAX_AUDIT_LOG_PAGE_SIZE);
+
+    Ok(AuditLogRow::list_paginated(audit_database_context, workspace_id, before, limit).await?)
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct AuditLogsPublishedPayload {
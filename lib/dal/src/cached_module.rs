@@ -1,4 +1,9 @@
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    str::FromStr,
+    sync::Arc,
+};
 
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
@@ -6,7 +11,7 @@ use postgres_types::ToSql;
 use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 use thiserror::Error;
-use tokio::task::JoinSet;
+use tokio::{sync::Semaphore, task::JoinSet};
 use ulid::Ulid;
 
 use crate::{
@@ -22,6 +27,8 @@ pub use si_id::CachedModuleId;
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum CachedModuleError {
+    #[error("builtin modules not found in module index: {0:?}")]
+    BuiltinModulesNotFound(Vec<String>),
     #[error("join error: {0}")]
     Join(#[from] tokio::task::JoinError),
     #[error("module index client error: {0}")]
@@ -46,6 +53,43 @@ pub enum CachedModuleError {
 
 pub type CachedModuleResult<T> = Result<T, CachedModuleError>;
 
+/// The default maximum number of builtin modules fetched from the module index at once.
+pub const DEFAULT_BUILTIN_FETCH_CONCURRENCY_LIMIT: usize = 8;
+
+/// Options for [`CachedModule::update_cached_modules_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct UpdateCachedModulesOptions {
+    /// Names of the builtin modules to fetch. If set to `None`, every builtin module in the module
+    /// index is considered. Modules not in the set are never fetched.
+    pub only: Option<HashSet<String>>,
+    /// The maximum number of builtin modules fetched from the module index at once. If set to
+    /// `None`, [`DEFAULT_BUILTIN_FETCH_CONCURRENCY_LIMIT`] is used.
+    pub concurrency_limit: Option<usize>,
+}
+
+/// The outcome of updating the cache for a single builtin module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstalledModuleOutcome {
+    /// The latest version of the module was already cached
+    AlreadyInstalled,
+    /// The module could not be fetched or cached
+    Failed,
+    /// The module was fetched and cached
+    Installed,
+    /// The module was fetched, but has no schema or variant to cache
+    Skipped,
+}
+
+/// Progress for a single builtin module, reported by
+/// [`CachedModule::update_cached_modules_with_progress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledModuleProgress {
+    pub name: String,
+    pub index: usize,
+    pub total: usize,
+    pub outcome: InstalledModuleOutcome,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CachedModule {
@@ -166,6 +210,31 @@ impl CachedModule {
     /// Calls out to the module index server to fetch the latest module set, and
     /// updates the cache for any new builtin modules
     pub async fn update_cached_modules(ctx: &DalContext) -> CachedModuleResult<Vec<CachedModule>> {
+        Self::update_cached_modules_with_progress(ctx, |_| {}).await
+    }
+
+    /// Like [`Self::update_cached_modules`], but calls `progress` once for every builtin module in
+    /// the module index as soon as its outcome is known. Modules that are already cached are
+    /// reported first, followed by the remaining modules in the order their downloads complete.
+    pub async fn update_cached_modules_with_progress(
+        ctx: &DalContext,
+        progress: impl Fn(InstalledModuleProgress),
+    ) -> CachedModuleResult<Vec<CachedModule>> {
+        Self::update_cached_modules_with_options(
+            ctx,
+            UpdateCachedModulesOptions::default(),
+            progress,
+        )
+        .await
+    }
+
+    /// Like [`Self::update_cached_modules_with_progress`], but only considers the builtin modules
+    /// selected by the provided [`UpdateCachedModulesOptions`].
+    pub async fn update_cached_modules_with_options(
+        ctx: &DalContext,
+        options: UpdateCachedModulesOptions,
+        progress: impl Fn(InstalledModuleProgress),
+    ) -> CachedModuleResult<Vec<CachedModule>> {
         let services_context = ctx.services_context();
         let module_index_url = services_context
             .module_index_url()
@@ -179,36 +248,85 @@ impl CachedModule {
             .await?
             .modules
             .iter()
+            .filter(|builtin| {
+                options
+                    .only
+                    .as_ref()
+                    .is_none_or(|only| only.contains(&builtin.name))
+            })
             .map(|builtin| (builtin.latest_hash.to_owned(), builtin.to_owned()))
             .collect();
 
+        if let Some(only) = &options.only {
+            let found_names: HashSet<&String> =
+                modules.values().map(|module| &module.name).collect();
+            let missing_names: Vec<String> = only
+                .iter()
+                .filter(|name| !found_names.contains(name))
+                .cloned()
+                .sorted()
+                .collect();
+            if !missing_names.is_empty() {
+                return Err(CachedModuleError::BuiltinModulesNotFound(missing_names));
+            }
+        }
+
         let hashes: Vec<_> = modules.keys().map(ToOwned::to_owned).collect();
         let uncached_hashes = CachedModule::find_missing_entries(ctx, hashes).await?;
 
-        let mut join_set = JoinSet::new();
-        for uncached_hash in &uncached_hashes {
-            let Some(module) = modules.get(uncached_hash).cloned() else {
-                continue;
-            };
-
-            let client = module_index_client.clone();
-            join_set.spawn(async move {
-                let module_id = module.id.to_owned();
-                Ok::<(ModuleDetailsResponse, Arc<Vec<u8>>), CachedModuleError>((
-                    module,
-                    Arc::new(
-                        client
-                            .get_builtin(Ulid::from_string(&module_id).unwrap_or_default())
-                            .await?,
-                    ),
-                ))
-            });
+        let total = modules.len();
+        let mut index = 0;
+        for (hash, module) in &modules {
+            if !uncached_hashes.contains(hash) {
+                progress(InstalledModuleProgress {
+                    name: module.name.to_owned(),
+                    index,
+                    total,
+                    outcome: InstalledModuleOutcome::AlreadyInstalled,
+                });
+                index += 1;
+            }
         }
 
+        let uncached_modules: Vec<ModuleDetailsResponse> = uncached_hashes
+            .iter()
+            .filter_map(|uncached_hash| modules.get(uncached_hash).cloned())
+            .collect();
+        let concurrency_limit = options
+            .concurrency_limit
+            .unwrap_or(DEFAULT_BUILTIN_FETCH_CONCURRENCY_LIMIT);
+        let mut join_set = spawn_with_concurrency_limit(
+            uncached_modules,
+            concurrency_limit,
+            |module: &ModuleDetailsResponse| {
+                let client = module_index_client.clone();
+                let module_id = Ulid::from_string(&module.id).unwrap_or_default();
+                async move { client.get_builtin(module_id).await.map(Arc::new) }
+            },
+        );
+
         let mut new_modules = vec![];
-        for res in join_set.join_all().await {
+        while let Some(res) = join_set.join_next().await {
             let (module, module_bytes) = res?;
-            if let Some(new_cached_module) = Self::insert(ctx, &module, module_bytes).await? {
+            let result = match module_bytes {
+                Ok(module_bytes) => Self::insert(ctx, &module, module_bytes).await,
+                Err(err) => Err(err.into()),
+            };
+
+            let outcome = match &result {
+                Ok(Some(_)) => InstalledModuleOutcome::Installed,
+                Ok(None) => InstalledModuleOutcome::Skipped,
+                Err(_) => InstalledModuleOutcome::Failed,
+            };
+            progress(InstalledModuleProgress {
+                name: module.name,
+                index,
+                total,
+                outcome,
+            });
+            index += 1;
+
+            if let Some(new_cached_module) = result? {
                 new_modules.push(new_cached_module);
             }
         }
@@ -391,3 +509,72 @@ impl CachedModule {
         Ok(Some(row.try_into()?))
     }
 }
+
+/// Spawns `fetch` for every item onto a [`JoinSet`], allowing at most `concurrency_limit` of the
+/// fetches to be in flight at once. Each task yields the item alongside the result of its fetch.
+fn spawn_with_concurrency_limit<T, R, F, Fut>(
+    items: impl IntoIterator<Item = T>,
+    concurrency_limit: usize,
+    fetch: F,
+) -> JoinSet<(T, R)>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(&T) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency_limit.max(1)));
+    let mut join_set = JoinSet::new();
+    for item in items {
+        let semaphore = semaphore.clone();
+        let fetch = fetch(&item);
+        join_set.spawn(async move {
+            // The semaphore is never closed, so the permit is always acquired. It is held until
+            // the fetch completes.
+            let _permit = semaphore.acquire_owned().await;
+            (item, fetch.await)
+        });
+    }
+
+    join_set
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn spawn_with_concurrency_limit_bounds_in_flight_fetches() {
+        let concurrency_limit = 3;
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let mut join_set = spawn_with_concurrency_limit(0..20, concurrency_limit, |item| {
+            let item = *item;
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                item * 2
+            }
+        });
+
+        let mut results = vec![];
+        while let Some(res) = join_set.join_next().await {
+            let (item, doubled) = res.expect("task panicked");
+            assert_eq!(item * 2, doubled);
+            results.push(item);
+        }
+        results.sort();
+
+        assert_eq!((0..20).collect::<Vec<_>>(), results);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= concurrency_limit);
+        assert_eq!(0, in_flight.load(Ordering::SeqCst));
+    }
+}
@@ -14,12 +14,14 @@ use tokio::time;
 
 use crate::billing_publish::BillingPublishError;
 use crate::slow_rt::SlowRuntimeError;
+use crate::workspace_snapshot::graph::detect_updates::Update;
 use crate::workspace_snapshot::graph::RebaseBatch;
+use crate::workspace_snapshot::node_weight::NodeWeight;
 use crate::{
     action::{ActionError, ActionId},
     ChangeSetStatus, ComponentError, DalContext, HistoryActor, HistoryEvent, HistoryEventError,
-    TransactionsError, User, UserError, UserPk, Workspace, WorkspacePk, WorkspaceSnapshot,
-    WorkspaceSnapshotError, WsEvent, WsEventError,
+    MachinePk, TransactionsError, User, UserError, UserPk, Workspace, WorkspacePk,
+    WorkspaceSnapshot, WorkspaceSnapshotError, WsEvent, WsEventError,
 };
 use crate::{
     billing_publish, Func, FuncError, Schema, SchemaError, SchemaVariant, SchemaVariantError,
@@ -37,6 +39,8 @@ const FIND_ANCESTORS_QUERY: &str = include_str!("queries/change_set/find_ancesto
 pub enum ChangeSetError {
     #[error("billing publish error: {0}")]
     BillingPublish(#[from] Box<BillingPublishError>),
+    #[error("change set {0} appears more than once in its own ancestry")]
+    ChangeSetAncestryCycle(ChangeSetId),
     #[error("change set not approved for apply. Current state: {0}")]
     ChangeSetNotApprovedForApply(ChangeSetStatus),
     #[error("change set with id {0} not found")]
@@ -55,6 +59,8 @@ pub enum ChangeSetError {
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid user actor pk")]
     InvalidActor(UserPk),
+    #[error("invalid user: machine actor ({0}) is not a user")]
+    InvalidUserMachine(MachinePk),
     #[error("invalid user system init")]
     InvalidUserSystemInit,
     #[error("tokio join error: {0}")]
@@ -65,6 +71,8 @@ pub enum ChangeSetError {
     Monotonic(#[from] ulid::MonotonicError),
     #[error("mutex error: {0}")]
     Mutex(String),
+    #[error("cannot create a change set pointing at the nil workspace snapshot address")]
+    NilSnapshotAddress,
     #[error("Changeset {0} does not have a base change set")]
     NoBaseChangeSet(ChangeSetId),
     #[error("no tenancy set in context")]
@@ -126,6 +134,8 @@ pub enum ChangeSetApplyError {
     Action(#[from] ActionError),
     #[error("action prototype not found for id: {0}")]
     ActionPrototypeNotFound(ActionId),
+    #[error("base change set ({0}) cannot receive an apply")]
+    BaseNotOpen(ChangeSetId),
     #[error("change set error: {0}")]
     ChangeSet(#[from] ChangeSetError),
     #[error("change set not found by id: {0}")]
@@ -164,6 +174,7 @@ pub struct ChangeSet {
     pub merge_requested_at: Option<DateTime<Utc>>,
     pub reviewed_by_user_id: Option<UserPk>,
     pub reviewed_at: Option<DateTime<Utc>>,
+    pub last_activity_at: DateTime<Utc>,
 }
 
 impl TryFrom<PgRow> for ChangeSet {
@@ -185,6 +196,7 @@ impl TryFrom<PgRow> for ChangeSet {
             merge_requested_at: value.try_get("merge_requested_at")?,
             reviewed_by_user_id: value.try_get("reviewed_by_user_id")?,
             reviewed_at: value.try_get("reviewed_at")?,
+            last_activity_at: value.try_get("last_activity_at")?,
         })
     }
 }
@@ -196,6 +208,12 @@ impl ChangeSet {
         base_change_set_id: Option<ChangeSetId>,
         workspace_snapshot_address: WorkspaceSnapshotAddress,
     ) -> ChangeSetResult<Self> {
+        // The nil address is only ever a transient placeholder (see `WorkspaceSnapshot::initial`),
+        // so a change set pointing at it would point at nothing.
+        if workspace_snapshot_address == WorkspaceSnapshotAddress::nil() {
+            return Err(ChangeSetError::NilSnapshotAddress);
+        }
+
         let id: Ulid = Ulid::new();
         let change_set_id: ChangeSetId = id.into();
 
@@ -258,6 +276,73 @@ impl ChangeSet {
         Ok(change_set)
     }
 
+    /// Forks a new [`ChangeSet`] from an arbitrary existing [`ChangeSet`] rather than the workspace
+    /// default. The new [`ChangeSet`] starts from the base's snapshot and uses it as its base.
+    pub async fn fork_from(
+        ctx: &DalContext,
+        base_change_set_id: ChangeSetId,
+        name: impl AsRef<str>,
+    ) -> ChangeSetResult<Self> {
+        let base_change_set = ChangeSet::find(ctx, base_change_set_id)
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound(base_change_set_id))?;
+
+        if base_change_set.workspace_snapshot_address == WorkspaceSnapshotAddress::nil() {
+            return Err(ChangeSetError::NoWorkspaceSnapshot(base_change_set_id));
+        }
+
+        let change_set = ChangeSet::new(
+            ctx,
+            name,
+            Some(base_change_set_id),
+            base_change_set.workspace_snapshot_address,
+        )
+        .await?;
+
+        Ok(change_set)
+    }
+
+    /// Creates a copy of the current [`ChangeSet`] in the provided [`DalContext`] without applying
+    /// it. The current snapshot is written to a new address and the new [`ChangeSet`] shares the
+    /// same base, so it can be rebased independently of the source.
+    pub async fn duplicate(ctx: &DalContext, name: impl AsRef<str>) -> ChangeSetResult<Self> {
+        let source_change_set = ChangeSet::find(ctx, ctx.change_set_id())
+            .await?
+            .ok_or(ChangeSetError::ChangeSetNotFound(ctx.change_set_id()))?;
+
+        // Persist any in-memory changes so that the duplicate sees exactly what the source sees.
+        let workspace_snapshot_address = ctx
+            .workspace_snapshot()
+            .map_err(Box::new)?
+            .write(ctx)
+            .await
+            .map_err(Box::new)?;
+
+        let change_set = ChangeSet::new(
+            ctx,
+            name,
+            source_change_set.base_change_set_id,
+            workspace_snapshot_address,
+        )
+        .await?;
+
+        Ok(change_set)
+    }
+
+    /// Creates and persists an editing [`ChangeSet`] on top of this one. The editing
+    /// [`ChangeSet`] uses this one as its base and starts from its snapshot.
+    pub async fn create_editing(&self, ctx: &DalContext) -> ChangeSetResult<Self> {
+        let change_set = ChangeSet::new(
+            ctx,
+            format!("{} (editing)", self.name),
+            Some(self.id),
+            self.workspace_snapshot_address,
+        )
+        .await?;
+
+        Ok(change_set)
+    }
+
     pub async fn into_frontend_type(
         &self,
         ctx: &DalContext,
@@ -329,6 +414,11 @@ impl ChangeSet {
         self.workspace_id.ok_or(ChangeSetError::NoTenancySet)
     }
 
+    /// When the [`ChangeSet`] last received a new snapshot or changed status.
+    pub fn last_activity_at(&self) -> DateTime<Utc> {
+        self.last_activity_at
+    }
+
     async fn workspace(&self, ctx: &DalContext) -> ChangeSetResult<Workspace> {
         Ok(Workspace::get_by_pk_or_error(ctx, self.workspace_id()?).await?)
     }
@@ -342,16 +432,18 @@ impl ChangeSet {
         ctx: &DalContext,
         workspace_snapshot_address: WorkspaceSnapshotAddress,
     ) -> ChangeSetResult<()> {
-        ctx.txns()
+        let row = ctx
+            .txns()
             .await?
             .pg()
-            .query_none(
-                "UPDATE change_set_pointers SET workspace_snapshot_address = $2, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+            .query_one(
+                "UPDATE change_set_pointers SET workspace_snapshot_address = $2, updated_at = CLOCK_TIMESTAMP(), last_activity_at = CLOCK_TIMESTAMP() WHERE id = $1 RETURNING last_activity_at",
                 &[&self.id, &workspace_snapshot_address],
             )
             .await?;
 
         self.workspace_snapshot_address = workspace_snapshot_address;
+        self.last_activity_at = row.try_get("last_activity_at")?;
 
         billing_publish::for_head_change_set_pointer_update(ctx, self)
             .await
@@ -365,41 +457,76 @@ impl ChangeSet {
         ctx: &DalContext,
         status: ChangeSetStatus,
     ) -> ChangeSetResult<()> {
-        ctx.txns()
+        let from_status = self.status;
+        let row = ctx
+            .txns()
             .await?
             .pg()
-            .query_none(
-                "UPDATE change_set_pointers SET status = $2, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+            .query_one(
+                "UPDATE change_set_pointers SET status = $2, updated_at = CLOCK_TIMESTAMP(), last_activity_at = CLOCK_TIMESTAMP() WHERE id = $1 RETURNING last_activity_at",
                 &[&self.id, &status.to_string()],
             )
             .await?;
 
         self.status = status;
+        self.last_activity_at = row.try_get("last_activity_at")?;
         billing_publish::for_change_set_status_update(ctx, self)
             .await
             .map_err(Box::new)?;
+        self.publish_status_changed(ctx, from_status).await?;
+        Ok(())
+    }
+
+    /// Publishes a [`WsEvent::change_set_status_changed`] on commit if the status has changed
+    /// from the provided status. This is sent alongside the more specific events for each status
+    /// transition for consumers that only care that the status changed. Every method that writes
+    /// the status must call this, either directly or through [`Self::update_status`].
+    ///
+    /// Nothing is published if the [`DalContext`] has no workspace (e.g. when migrating
+    /// snapshots), since there is no one to notify.
+    async fn publish_status_changed(
+        &self,
+        ctx: &DalContext,
+        from_status: ChangeSetStatus,
+    ) -> ChangeSetResult<()> {
+        if from_status == self.status || ctx.tenancy().workspace_pk_opt().is_none() {
+            return Ok(());
+        }
+
+        let change_set = self.into_frontend_type(ctx).await?;
+        WsEvent::change_set_status_changed(ctx, from_status, change_set)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
         Ok(())
     }
 
     pub async fn request_change_set_approval(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let user_pk = Self::extract_userid_from_context_or_error(ctx).await?;
+        let from_status = self.status;
         let status = ChangeSetStatus::NeedsApproval;
-        ctx.txns()
+        let row = ctx
+            .txns()
             .await?
             .pg()
-            .query_none(
-                "UPDATE change_set_pointers SET merge_requested_by_user_id = $2, merge_requested_at = CLOCK_TIMESTAMP(), status = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+            .query_one(
+                "UPDATE change_set_pointers SET merge_requested_by_user_id = $2, merge_requested_at = CLOCK_TIMESTAMP(), status = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1 RETURNING merge_requested_at",
                 &[&self.id, &user_pk, &status.to_string()],
             )
             .await?;
 
         self.status = status;
+        self.merge_requested_by_user_id = Some(user_pk);
+        self.merge_requested_at = row.try_get("merge_requested_at")?;
+        self.publish_status_changed(ctx, from_status).await?;
 
         Ok(())
     }
 
     /// Set the status to Open, and clear any reviewed/merge requested info
     pub async fn reopen_change_set(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+        let from_status = self.status;
         let status = ChangeSetStatus::Open;
         ctx.txns()
             .await?
@@ -417,6 +544,11 @@ impl ChangeSet {
             .await?;
 
         self.status = status;
+        self.reviewed_by_user_id = None;
+        self.reviewed_at = None;
+        self.merge_requested_by_user_id = None;
+        self.merge_requested_at = None;
+        self.publish_status_changed(ctx, from_status).await?;
 
         Ok(())
     }
@@ -504,34 +636,44 @@ impl ChangeSet {
 
     pub async fn approve_change_set_for_apply(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let user_pk = Self::extract_userid_from_context_or_error(ctx).await?;
+        let from_status = self.status;
         let status = ChangeSetStatus::Approved;
-        ctx.txns()
+        let row = ctx
+            .txns()
             .await?
             .pg()
-            .query_none(
-                "UPDATE change_set_pointers SET reviewed_by_user_id = $2, reviewed_at = CLOCK_TIMESTAMP(), status = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+            .query_one(
+                "UPDATE change_set_pointers SET reviewed_by_user_id = $2, reviewed_at = CLOCK_TIMESTAMP(), status = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1 RETURNING reviewed_at",
                 &[&self.id, &user_pk, &status.to_string()],
             )
             .await?;
 
         self.status = status;
+        self.reviewed_by_user_id = Some(user_pk);
+        self.reviewed_at = row.try_get("reviewed_at")?;
+        self.publish_status_changed(ctx, from_status).await?;
 
         Ok(())
     }
 
     pub async fn reject_change_set_for_apply(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
         let user_pk = Self::extract_userid_from_context_or_error(ctx).await?;
+        let from_status = self.status;
         let status = ChangeSetStatus::Rejected;
-        ctx.txns()
+        let row = ctx
+            .txns()
             .await?
             .pg()
-            .query_none(
-                "UPDATE change_set_pointers SET reviewed_by_user_id = $2, reviewed_at = CLOCK_TIMESTAMP(), status = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1",
+            .query_one(
+                "UPDATE change_set_pointers SET reviewed_by_user_id = $2, reviewed_at = CLOCK_TIMESTAMP(), status = $3, updated_at = CLOCK_TIMESTAMP() WHERE id = $1 RETURNING reviewed_at",
                 &[&self.id, &user_pk, &status.to_string()],
             )
             .await?;
 
         self.status = status;
+        self.reviewed_by_user_id = Some(user_pk);
+        self.reviewed_at = row.try_get("reviewed_at")?;
+        self.publish_status_changed(ctx, from_status).await?;
 
         Ok(())
     }
@@ -667,6 +809,40 @@ impl ChangeSet {
         Ok(result)
     }
 
+    /// Lists the active [`ChangeSets`](ChangeSet) in the current workspace that have not had any
+    /// activity (a new snapshot or a status change) within `older_than`. The workspace's default
+    /// change set is never considered stale.
+    pub async fn list_stale(ctx: &DalContext, older_than: Duration) -> ChangeSetResult<Vec<Self>> {
+        let mut result = vec![];
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT * from change_set_pointers
+                    WHERE workspace_id = $1
+                    AND status IN ($2, $3, $4, $5, $6)
+                    AND last_activity_at < CLOCK_TIMESTAMP() - make_interval(secs => $7)
+                    AND id != (SELECT default_change_set_id FROM workspaces WHERE pk = $1)",
+                &[
+                    &ctx.tenancy().workspace_pk_opt(),
+                    &ChangeSetStatus::Open.to_string(),
+                    &ChangeSetStatus::NeedsApproval.to_string(),
+                    &ChangeSetStatus::NeedsAbandonApproval.to_string(),
+                    &ChangeSetStatus::Approved.to_string(),
+                    &ChangeSetStatus::Rejected.to_string(),
+                    &older_than.as_secs_f64(),
+                ],
+            )
+            .await?;
+
+        for row in rows {
+            result.push(Self::try_from(row)?);
+        }
+
+        Ok(result)
+    }
+
     /// Take care when working on these change sets to set the workspace id on the dal context!!!
     pub async fn list_open_for_all_workspaces(ctx: &DalContext) -> ChangeSetResult<Vec<Self>> {
         let mut result = vec![];
@@ -694,22 +870,40 @@ impl ChangeSet {
     /// Applies the current [`ChangeSet`] in the provided [`DalContext`]. [`Actions`](Action)
     /// are enqueued as needed and only done so if the base [`ChangeSet`] is "HEAD" (i.e.
     /// the default [`ChangeSet`] of the [`Workspace`]).
+    ///
+    /// The apply is rejected if the base [`ChangeSet`] has been abandoned, applied or has failed.
+    ///
+    /// Returns the applied [`ChangeSet`] along with the ids of the [`Actions`](Action) enqueued
+    /// on "HEAD" by the apply (empty if the base [`ChangeSet`] is not "HEAD").
     #[instrument(level = "info", skip_all)]
-    pub async fn apply_to_base_change_set(ctx: &mut DalContext) -> ChangeSetApplyResult<ChangeSet> {
+    pub async fn apply_to_base_change_set(
+        ctx: &mut DalContext,
+    ) -> ChangeSetApplyResult<(ChangeSet, Vec<ActionId>)> {
         // Apply to the base change with the current change set (non-editing) and commit.
         let mut change_set_to_be_applied = Self::find(ctx, ctx.change_set_id())
             .await?
             .ok_or(ChangeSetApplyError::ChangeSetNotFound(ctx.change_set_id()))?;
+        if let Some(base_change_set_id) = change_set_to_be_applied.base_change_set_id {
+            let base_change_set = Self::find(ctx, base_change_set_id)
+                .await?
+                .ok_or(ChangeSetApplyError::ChangeSetNotFound(base_change_set_id))?;
+            if matches!(
+                base_change_set.status,
+                ChangeSetStatus::Abandoned | ChangeSetStatus::Applied | ChangeSetStatus::Failed
+            ) {
+                return Err(ChangeSetApplyError::BaseNotOpen(base_change_set_id));
+            }
+        }
         ctx.update_visibility_and_snapshot_to_visibility(ctx.change_set_id())
             .await?;
-        change_set_to_be_applied
+        let enqueued_action_ids = change_set_to_be_applied
             .apply_to_base_change_set_inner(ctx)
             .await?;
 
         // This is just to send the ws events
         ctx.blocking_commit_no_rebase().await?;
 
-        Ok(change_set_to_be_applied)
+        Ok((change_set_to_be_applied, enqueued_action_ids))
     }
 
     pub async fn detect_updates_that_will_be_applied(
@@ -739,16 +933,33 @@ impl ChangeSet {
     /// of the [`ChangeSet`] accordingly.
     ///
     /// This function neither changes the visibility nor the snapshot after performing the
-    /// aforementioned actions.
-    async fn apply_to_base_change_set_inner(&mut self, ctx: &DalContext) -> ChangeSetResult<()> {
+    /// aforementioned actions. Returns the ids of the [`Actions`](Action) enqueued on "HEAD".
+    async fn apply_to_base_change_set_inner(
+        &mut self,
+        ctx: &DalContext,
+    ) -> ChangeSetResult<Vec<ActionId>> {
         let workspace_id = self
             .workspace_id
             .ok_or(ChangeSetError::NoWorkspacePkSet(self.id))?;
         let base_change_set_id = self
             .base_change_set_id
             .ok_or(ChangeSetError::NoBaseChangeSet(self.id))?;
+        let applying_to_head =
+            base_change_set_id == ctx.get_workspace_default_change_set_id().await?;
 
+        let mut enqueued_action_ids = Vec::new();
         if let Some(rebase_batch) = self.detect_updates_that_will_be_applied(ctx).await? {
+            if applying_to_head {
+                enqueued_action_ids.extend(rebase_batch.updates().iter().filter_map(|update| {
+                    match update {
+                        Update::NewNode {
+                            node_weight: NodeWeight::Action(action_node_weight),
+                        } => Some(ActionId::from(action_node_weight.id())),
+                        _ => None,
+                    }
+                }));
+            }
+
             let updates_address = ctx.write_rebase_batch(rebase_batch).await?;
 
             let (request_id, reply_fut) = ctx
@@ -782,7 +993,7 @@ impl ChangeSet {
             .publish_on_commit(ctx)
             .await?;
 
-        Ok(())
+        Ok(enqueued_action_ids)
     }
 
     /// Returns a new [`ChangeSetId`](ChangeSet) if a new [`ChangeSet`] was created.
@@ -910,7 +1121,7 @@ impl ChangeSet {
                     Err(_) => None,
                 }
             }
-            HistoryActor::SystemInit => None,
+            HistoryActor::Machine(_) | HistoryActor::SystemInit => None,
         };
         user_id
     }
@@ -923,6 +1134,9 @@ impl ChangeSet {
                     Err(err) => return Err(ChangeSetError::User(err)),
                 }
             }
+            HistoryActor::Machine(machine_pk) => {
+                return Err(ChangeSetError::InvalidUserMachine(*machine_pk))
+            }
             HistoryActor::SystemInit => return Err(ChangeSetError::InvalidUserSystemInit),
         };
         Ok(user_id)
@@ -981,6 +1195,36 @@ impl ChangeSet {
         Ok(result)
     }
 
+    /// Walk the base change sets of the given [`ChangeSet`] up to the default change set of the
+    /// workspace ("HEAD"). The returned chain starts with the given change set and ends with
+    /// "HEAD" (or the last change set without a base, if "HEAD" is never reached).
+    pub async fn ancestry(
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
+    ) -> ChangeSetResult<Vec<ChangeSetId>> {
+        let default_change_set_id = ctx.get_workspace_default_change_set_id().await?;
+
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut maybe_current_id = Some(change_set_id);
+        while let Some(current_id) = maybe_current_id {
+            if !seen.insert(current_id) {
+                return Err(ChangeSetError::ChangeSetAncestryCycle(current_id));
+            }
+            chain.push(current_id);
+            if current_id == default_change_set_id {
+                break;
+            }
+
+            let change_set = Self::find(ctx, current_id)
+                .await?
+                .ok_or(ChangeSetError::ChangeSetNotFound(current_id))?;
+            maybe_current_id = change_set.base_change_set_id;
+        }
+
+        Ok(chain)
+    }
+
     pub async fn rename_change_set(
         ctx: &DalContext,
         change_set_id: ChangeSetId,
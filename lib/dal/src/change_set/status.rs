@@ -1,12 +1,28 @@
 use serde::{Deserialize, Serialize};
 use si_data_pg::postgres_types::ToSql;
-use strum::{AsRefStr, Display, EnumString};
+use strum::{AsRefStr, Display, EnumIter, EnumString};
 
 // NOTE(nick): if we can remove the "ToSql" trait, then we can fully move this to "si-events-rs"
 // and delete the duplicate types.
+/// The status of a [`ChangeSet`](crate::ChangeSet).
+///
+/// Statuses are stored as their [`Display`] string and read back with `try_from`, so every
+/// variant must round-trip through both. Avoid per-variant strum attributes that would make the
+/// two disagree.
 #[remain::sorted]
 #[derive(
-    AsRefStr, Deserialize, Serialize, Debug, Display, EnumString, PartialEq, Eq, Copy, Clone, ToSql,
+    AsRefStr,
+    Deserialize,
+    Serialize,
+    Debug,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Eq,
+    Copy,
+    Clone,
+    ToSql,
 )]
 pub enum ChangeSetStatus {
     /// No longer usable
@@ -59,3 +75,21 @@ impl From<ChangeSetStatus> for si_events::ChangeSetStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn every_status_round_trips_through_its_string() {
+        for status in ChangeSetStatus::iter() {
+            assert_eq!(
+                Ok(status),
+                ChangeSetStatus::try_from(status.to_string().as_str()),
+                "{status:?} does not round-trip"
+            );
+        }
+    }
+}
@@ -34,7 +34,7 @@ use crate::diagram::{
 };
 use crate::func::argument::FuncArgumentError;
 use crate::history_event::HistoryEventMetadata;
-use crate::layer_db_types::{ComponentContent, ComponentContentV2};
+use crate::layer_db_types::{ComponentContent, ComponentContentV3};
 use crate::module::{Module, ModuleError};
 use crate::prop::{PropError, PropPath};
 use crate::qualification::QualificationError;
@@ -269,12 +269,14 @@ pub struct Component {
     #[serde(flatten)]
     timestamp: Timestamp,
     to_delete: bool,
+    concurrency_limit: Option<i32>,
 }
 
-impl From<Component> for ComponentContentV2 {
+impl From<Component> for ComponentContentV3 {
     fn from(value: Component) -> Self {
         Self {
             timestamp: value.timestamp,
+            concurrency_limit: value.concurrency_limit,
         }
     }
 }
@@ -287,11 +289,12 @@ pub struct ControllingFuncData {
 }
 
 impl Component {
-    pub fn assemble(node_weight: &ComponentNodeWeight, content: ComponentContentV2) -> Self {
+    pub fn assemble(node_weight: &ComponentNodeWeight, content: ComponentContentV3) -> Self {
         Self {
             id: node_weight.id().into(),
             timestamp: content.timestamp,
             to_delete: node_weight.to_delete(),
+            concurrency_limit: content.concurrency_limit,
         }
     }
 
@@ -307,6 +310,13 @@ impl Component {
         self.to_delete
     }
 
+    /// Returns this component's override of the workspace's component concurrency limit. With an
+    /// override, a dependent values update runs at most that many of this component's values at
+    /// once, instead of holding the component back behind the workspace limit.
+    pub fn concurrency_limit(&self) -> Option<i32> {
+        self.concurrency_limit
+    }
+
     pub async fn change_status(&self, ctx: &DalContext) -> ComponentResult<ChangeStatus> {
         let status = if self.exists_in_head(ctx).await? {
             if self.to_delete() {
@@ -407,12 +417,13 @@ impl Component {
         schema_variant_id: SchemaVariantId,
         view_id: ViewId,
     ) -> ComponentResult<Self> {
-        let content = ComponentContentV2 {
+        let content = ComponentContentV3 {
             timestamp: Timestamp::now(),
+            concurrency_limit: None,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(ComponentContent::V2(content.clone()).into()),
+            Arc::new(ComponentContent::V3(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -1273,7 +1284,7 @@ impl Component {
     async fn try_get_node_weight_and_content(
         ctx: &DalContext,
         component_id: ComponentId,
-    ) -> ComponentResult<Option<(ComponentNodeWeight, ComponentContentV2)>> {
+    ) -> ComponentResult<Option<(ComponentNodeWeight, ComponentContentV3)>> {
         if let Some((component_node_weight, content_hash)) =
             Self::try_get_node_weight_and_content_hash(ctx, component_id).await?
         {
@@ -1295,7 +1306,7 @@ impl Component {
     async fn get_node_weight_and_content(
         ctx: &DalContext,
         component_id: ComponentId,
-    ) -> ComponentResult<(ComponentNodeWeight, ComponentContentV2)> {
+    ) -> ComponentResult<(ComponentNodeWeight, ComponentContentV3)> {
         Self::try_get_node_weight_and_content(ctx, component_id)
             .await?
             .ok_or(ComponentError::NotFound(component_id))
@@ -1349,7 +1360,6 @@ impl Component {
     pub async fn list(ctx: &DalContext) -> ComponentResult<Vec<Self>> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
 
-        let mut components = vec![];
         let component_category_node_id = workspace_snapshot
             .get_category_node_or_err(None, CategoryNodeKind::Component)
             .await?;
@@ -1362,22 +1372,74 @@ impl Component {
             .await?;
 
         let mut node_weights = vec![];
-        let mut hashes = vec![];
         for index in component_node_indices {
             let node_weight = workspace_snapshot
                 .get_node_weight(index)
                 .await?
                 .get_component_node_weight()?;
-            hashes.push(node_weight.content_hash());
             node_weights.push(node_weight);
         }
 
+        Self::list_from_node_weights(ctx, node_weights).await
+    }
+
+    /// List the IDs of all [`Components`](Component) using any [`SchemaVariant`] of the provided
+    /// [`SchemaId`](Schema).
+    pub async fn list_ids_for_schema(
+        ctx: &DalContext,
+        schema_id: SchemaId,
+    ) -> ComponentResult<Vec<ComponentId>> {
+        let mut component_ids = vec![];
+        for schema_variant in SchemaVariant::list_for_schema(ctx, schema_id).await? {
+            component_ids
+                .extend(SchemaVariant::list_component_ids(ctx, schema_variant.id()).await?);
+        }
+
+        Ok(component_ids)
+    }
+
+    /// List all [`Components`](Component) using any [`SchemaVariant`] of the provided
+    /// [`SchemaId`](Schema), so that large diagrams can be loaded one schema at a time.
+    pub async fn list_for_schema(
+        ctx: &DalContext,
+        schema_id: SchemaId,
+    ) -> ComponentResult<Vec<Self>> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let mut node_weights = vec![];
+        for component_id in Self::list_ids_for_schema(ctx, schema_id).await? {
+            let node_weight = workspace_snapshot
+                .get_node_weight_by_id(component_id)
+                .await?
+                .get_component_node_weight()?;
+            node_weights.push(node_weight);
+        }
+
+        Self::list_from_node_weights(ctx, node_weights).await
+    }
+
+    /// Count the [`Components`](Component) using any [`SchemaVariant`] of the provided
+    /// [`SchemaId`](Schema) without fetching their contents.
+    pub async fn count_for_schema(ctx: &DalContext, schema_id: SchemaId) -> ComponentResult<usize> {
+        Ok(Self::list_ids_for_schema(ctx, schema_id).await?.len())
+    }
+
+    async fn list_from_node_weights(
+        ctx: &DalContext,
+        node_weights: Vec<ComponentNodeWeight>,
+    ) -> ComponentResult<Vec<Self>> {
+        let hashes: Vec<ContentHash> = node_weights
+            .iter()
+            .map(|node_weight| node_weight.content_hash())
+            .collect();
+
         let contents: HashMap<ContentHash, ComponentContent> = ctx
             .layer_db()
             .cas()
             .try_read_many_as(hashes.as_slice())
             .await?;
 
+        let mut components = Vec::with_capacity(node_weights.len());
         for node_weight in node_weights {
             match contents.get(&node_weight.content_hash()) {
                 Some(content) => {
@@ -1392,6 +1454,33 @@ impl Component {
         Ok(components)
     }
 
+    /// Returns the concurrency limit overrides of the given [`Components`](Component) that have
+    /// one, reading their contents in a single batch.
+    pub async fn concurrency_limits_by_id(
+        ctx: &DalContext,
+        component_ids: impl IntoIterator<Item = ComponentId>,
+    ) -> ComponentResult<HashMap<ComponentId, i32>> {
+        let mut node_weights = vec![];
+        for component_id in component_ids {
+            node_weights.push(
+                ctx.workspace_snapshot()?
+                    .get_node_weight_by_id(component_id)
+                    .await?
+                    .get_component_node_weight()?,
+            );
+        }
+
+        Ok(Self::list_from_node_weights(ctx, node_weights)
+            .await?
+            .into_iter()
+            .filter_map(|component| {
+                component
+                    .concurrency_limit
+                    .map(|limit| (component.id, limit))
+            })
+            .collect())
+    }
+
     pub async fn list_to_be_deleted(ctx: &DalContext) -> ComponentResult<Vec<ComponentId>> {
         let mut to_be_deleted = vec![];
         let components = Self::list(ctx).await?;
@@ -2436,7 +2525,7 @@ impl Component {
         let original_component = self.clone();
         let mut component = self;
 
-        let before = ComponentContentV2::from(component.clone());
+        let before = ComponentContentV3::from(component.clone());
         lambda(&mut component)?;
 
         // The `to_delete` lives on the node itself, not in the content, so we need to be a little
@@ -2458,10 +2547,10 @@ impl Component {
                 .await?;
         }
 
-        let updated = ComponentContentV2::from(component.clone());
+        let updated = ComponentContentV3::from(component.clone());
         if updated != before {
             let (hash, _) = ctx.layer_db().cas().write(
-                Arc::new(ComponentContent::V2(updated.clone()).into()),
+                Arc::new(ComponentContent::V3(updated.clone()).into()),
                 None,
                 ctx.events_tenancy(),
                 ctx.events_actor(),
@@ -2616,6 +2705,20 @@ impl Component {
         }
     }
 
+    /// Overrides the workspace's component concurrency limit for this component. `None`, or a
+    /// limit below 1, removes the override so the workspace limit applies.
+    pub async fn set_concurrency_limit(
+        self,
+        ctx: &DalContext,
+        limit: Option<i32>,
+    ) -> ComponentResult<Self> {
+        self.modify(ctx, |component| {
+            component.concurrency_limit = limit.filter(|limit| *limit > 0);
+            Ok(())
+        })
+        .await
+    }
+
     pub async fn set_to_delete(self, ctx: &DalContext, to_delete: bool) -> ComponentResult<Self> {
         let component_id = self.id;
         let schema_variant_id = Self::schema_variant_id(ctx, component_id).await?;
@@ -3521,13 +3624,20 @@ impl Component {
                             .into_iter()
                             .map(|a| a.into())
                             .collect(),
-                        direction: DiagramSocketDirection::Input,
+                        direction: if socket.kind().is_bidirectional() {
+                            DiagramSocketDirection::Bidirectional
+                        } else {
+                            DiagramSocketDirection::Input
+                        },
                         max_connections: match socket.arity() {
                             SocketArity::Many => None,
                             SocketArity::One => Some(1),
                         },
                         is_required: Some(false),
-                        node_side: DiagramSocketNodeSide::Left,
+                        node_side: match (socket.kind().is_bidirectional(), socket.node_side()) {
+                            (true, Some(node_side)) => node_side.into(),
+                            _ => DiagramSocketNodeSide::Left,
+                        },
                         is_management: Some(false),
                         managed_schemas: None,
                     });
@@ -3546,13 +3656,20 @@ impl Component {
                             .into_iter()
                             .map(|a| a.into())
                             .collect(),
-                        direction: DiagramSocketDirection::Output,
+                        direction: if socket.kind().is_bidirectional() {
+                            DiagramSocketDirection::Bidirectional
+                        } else {
+                            DiagramSocketDirection::Output
+                        },
                         max_connections: match socket.arity() {
                             SocketArity::Many => None,
                             SocketArity::One => Some(1),
                         },
                         is_required: Some(false),
-                        node_side: DiagramSocketNodeSide::Right,
+                        node_side: match (socket.kind().is_bidirectional(), socket.node_side()) {
+                            (true, Some(node_side)) => node_side.into(),
+                            _ => DiagramSocketNodeSide::Right,
+                        },
                         is_management: Some(false),
                         managed_schemas: None,
                     });
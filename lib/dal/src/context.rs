@@ -648,6 +648,12 @@ impl DalContext {
         }
     }
 
+    /// Pin the current workspace snapshot, so that long reads keep using it even if the context
+    /// is later moved to another snapshot (e.g. by [`Self::update_snapshot_to_visibility`]).
+    pub fn pin_snapshot(&self) -> Result<SnapshotPin, WorkspaceSnapshotError> {
+        Ok(SnapshotPin(self.workspace_snapshot()?))
+    }
+
     pub fn blocking(&self) -> bool {
         self.blocking
     }
@@ -964,6 +970,7 @@ impl DalContext {
     pub fn events_actor(&self) -> si_events::Actor {
         match self.history_actor() {
             HistoryActor::User(user_pk) => si_events::Actor::User(*user_pk),
+            HistoryActor::Machine(machine_pk) => si_events::Actor::Machine(*machine_pk),
             HistoryActor::SystemInit => si_events::Actor::System,
         }
     }
@@ -1058,6 +1065,26 @@ impl DalContext {
     }
 }
 
+/// A [`WorkspaceSnapshot`] pinned by [`DalContext::pin_snapshot`]. Mutations made through the
+/// context to the same snapshot are still visible through the pin, but swapping the context's
+/// snapshot is not.
+#[derive(Clone)]
+pub struct SnapshotPin(Arc<WorkspaceSnapshot>);
+
+impl SnapshotPin {
+    pub fn snapshot(&self) -> Arc<WorkspaceSnapshot> {
+        self.0.clone()
+    }
+}
+
+impl std::ops::Deref for SnapshotPin {
+    type Target = WorkspaceSnapshot;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 /// A context which represents a suitable tenancies, visibilities, etc. for consumption by a set
 /// of DAL objects.
 #[derive(Clone, Debug, Eq, PartialEq)]
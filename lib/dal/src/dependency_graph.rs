@@ -1,4 +1,4 @@
-use petgraph::prelude::*;
+use petgraph::{algo::tarjan_scc, prelude::*};
 use std::collections::{hash_map::Entry, HashMap};
 
 #[derive(Debug, Clone)]
@@ -109,4 +109,56 @@ impl<T: Copy + std::cmp::Eq + std::cmp::PartialEq + std::hash::Hash> DependencyG
     pub fn all_ids(&self) -> Vec<T> {
         self.graph.node_weights().copied().collect()
     }
+
+    /// Returns each set of ids that depend on one another in a cycle. Ids that only depend on
+    /// themselves (see [`Self::cycle_on_self`]) are not included.
+    pub fn cycles(&self) -> Vec<Vec<T>> {
+        tarjan_scc(&self.graph)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                component
+                    .into_iter()
+                    .filter_map(|node_idx| self.graph.node_weight(node_idx).copied())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.id_to_index_map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id_to_index_map.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_reports_cross_id_cycles() {
+        let mut graph = DependencyGraph::new();
+        graph.id_depends_on(1, 2);
+        graph.id_depends_on(2, 3);
+        graph.id_depends_on(3, 1);
+        graph.id_depends_on(4, 1);
+
+        let mut cycles = graph.cycles();
+        assert_eq!(1, cycles.len());
+        let mut cycle = cycles.pop().expect("has a cycle");
+        cycle.sort();
+        assert_eq!(vec![1, 2, 3], cycle);
+    }
+
+    #[test]
+    fn cycles_ignores_self_cycles() {
+        let mut graph = DependencyGraph::new();
+        graph.id_depends_on(1, 2);
+        graph.cycle_on_self(2);
+
+        assert!(graph.cycles().is_empty());
+    }
 }
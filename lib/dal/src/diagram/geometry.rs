@@ -449,36 +449,67 @@ impl Geometry {
         ctx: &DalContext,
         new_geometry: RawGeometry,
     ) -> DiagramResult<()> {
+        if let Some(updated) = Self::update_bulk(ctx, vec![(self.id, new_geometry)])
+            .await?
+            .pop()
+        {
+            *self = updated;
+        }
+
+        Ok(())
+    }
+
+    /// Updates many [`Geometries`](Geometry) at once (e.g. when a selection of components is
+    /// dragged), returning them in the order given. Every id is checked before anything is
+    /// changed, so an invalid id leaves all of the geometries untouched.
+    pub async fn update_bulk(
+        ctx: &DalContext,
+        updates: Vec<(GeometryId, RawGeometry)>,
+    ) -> DiagramResult<Vec<Self>> {
+        let mut geometries = Vec::with_capacity(updates.len());
+        for (geometry_id, new_geometry) in updates {
+            geometries.push((Self::get_by_id(ctx, geometry_id).await?, new_geometry));
+        }
+
         let timestamp = Timestamp::now();
-        let geometry = new_geometry.clone();
-
-        let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(
-                GeometryContent::V1(GeometryContentV1 {
-                    timestamp,
-                    x: new_geometry.x.to_string(),
-                    y: new_geometry.y.to_string(),
-                    width: new_geometry.width.map(|w| w.to_string()),
-                    height: new_geometry.height.map(|h| h.to_string()),
-                })
-                .into(),
-            ),
-            None,
-            ctx.events_tenancy(),
-            ctx.events_actor(),
-        )?;
+        let mut content_updates = Vec::with_capacity(geometries.len());
+        let mut updated = Vec::with_capacity(geometries.len());
+        for (mut geometry, new_geometry) in geometries {
+            let (hash, _) = ctx.layer_db().cas().write(
+                Arc::new(
+                    GeometryContent::V1(GeometryContentV1 {
+                        timestamp,
+                        x: new_geometry.x.to_string(),
+                        y: new_geometry.y.to_string(),
+                        width: new_geometry.width.map(|w| w.to_string()),
+                        height: new_geometry.height.map(|h| h.to_string()),
+                    })
+                    .into(),
+                ),
+                None,
+                ctx.events_tenancy(),
+                ctx.events_actor(),
+            )?;
+            content_updates.push((geometry.id, hash));
+
+            geometry.x = new_geometry.x;
+            geometry.y = new_geometry.y;
+            geometry.width = new_geometry.width;
+            geometry.height = new_geometry.height;
+            geometry.timestamp = timestamp;
+            updated.push(geometry);
+        }
 
         ctx.workspace_snapshot()?
-            .update_content(self.id.into(), hash)
+            .with_mutations(|graph| {
+                for (geometry_id, hash) in content_updates {
+                    graph.update_content(geometry_id.into(), hash)?;
+                }
+                Ok(())
+            })
             .await?;
 
-        self.x = geometry.x;
-        self.y = geometry.y;
-        self.width = geometry.width;
-        self.height = geometry.height;
-        self.timestamp = timestamp;
-
-        Ok(())
+        Ok(updated)
     }
 
     /// Removes a [Geometry] from the graph, provided it's not the last geometry for a component
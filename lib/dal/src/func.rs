@@ -380,6 +380,35 @@ impl Func {
         })
     }
 
+    /// Runs a deterministic formatting pass over func code: line endings become `\n`, tabs in
+    /// leading indentation are expanded to two spaces, trailing whitespace is trimmed and the
+    /// code ends with exactly one newline. Whitespace-only code normalizes to an empty string.
+    ///
+    /// Lines are handled without parsing the code, so whitespace inside multi-line string or
+    /// template literals is changed too. Only run this when the caller asked for it.
+    pub fn normalize_code(code: &str) -> String {
+        let mut normalized = String::with_capacity(code.len());
+        for line in code.lines() {
+            let line = line.trim_end();
+            let body = line.trim_start_matches(['\t', ' ']);
+            for indent in line[..line.len() - body.len()].chars() {
+                match indent {
+                    '\t' => normalized.push_str("  "),
+                    _ => normalized.push(' '),
+                }
+            }
+            normalized.push_str(body);
+            normalized.push('\n');
+        }
+
+        let content_len = normalized.trim_end().len();
+        normalized.truncate(content_len);
+        if !normalized.is_empty() {
+            normalized.push('\n');
+        }
+        normalized
+    }
+
     pub fn is_dynamic(&self) -> bool {
         Self::is_dynamic_for_name_string(&self.name)
     }
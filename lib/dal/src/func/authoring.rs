@@ -537,6 +537,34 @@ impl FuncAuthoringClient {
         func_id: FuncId,
         code: String,
     ) -> FuncAuthoringResult<()> {
+        Self::save_code_with_normalization(ctx, func_id, code, false).await?;
+        Ok(())
+    }
+
+    #[instrument(
+        level = "info",
+        name = "func.authoring.save_code_with_normalization",
+        skip(ctx, code)
+    )]
+    /// Save only the code for the given [`FuncId`], running it through [`Func::normalize_code`]
+    /// first if `normalize` is set. Pass `false` to store the code verbatim.
+    ///
+    /// Returns whether normalization changed the code.
+    /// Returns an error if the [`Func`] is currently locked
+    pub async fn save_code_with_normalization(
+        ctx: &DalContext,
+        func_id: FuncId,
+        code: String,
+        normalize: bool,
+    ) -> FuncAuthoringResult<bool> {
+        let (code, normalized) = if normalize {
+            let normalized_code = Func::normalize_code(&code);
+            let touched = normalized_code != code;
+            (normalized_code, touched)
+        } else {
+            (code, false)
+        };
+
         let func = Func::get_by_id_or_error(ctx, func_id).await?;
         func.error_if_locked()?;
         Func::modify_by_id(ctx, func.id, |func| {
@@ -551,7 +579,7 @@ impl FuncAuthoringClient {
         for attribute_prototype_id in attribute_prototypes {
             AttributeBinding::enqueue_dvu_for_impacted_values(ctx, attribute_prototype_id).await?;
         }
-        Ok(())
+        Ok(normalized)
     }
 
     /// Save metadata about the [`FuncId`]
@@ -66,6 +66,7 @@ impl FuncDispatch for FuncBackendJsAction {
                             group: None,
                             message: message.clone(),
                             timestamp: std::cmp::max(Utc::now().timestamp(), 0) as u64,
+                            encoding: Default::default(),
                         })
                         .await
                         .map_err(|_| FuncBackendError::SendError)?;
@@ -84,6 +85,7 @@ impl FuncDispatch for FuncBackendJsAction {
                         group: None,
                         message: failure.error().message.to_owned(),
                         timestamp: std::cmp::max(Utc::now().timestamp(), 0) as u64,
+                        encoding: Default::default(),
                     })
                     .await
                     .map_err(|_| FuncBackendError::SendError)?;
@@ -54,6 +54,10 @@ impl FuncDispatch for FuncBackendJsAttribute {
                 &change_set_id.to_string(),
             )
             .await?;
+        let value = match value {
+            FunctionResult::Success(value) => value.validate(&self.request.response_type),
+            failure => failure,
+        };
         let value = match value {
             FunctionResult::Failure(failure) => match &self.request.response_type {
                 ResolverFunctionResponseType::Action
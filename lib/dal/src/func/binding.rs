@@ -24,7 +24,7 @@ use crate::func::binding::attribute::AttributeBindingMalformedInput;
 use crate::func::FuncKind;
 use crate::management::prototype::ManagementPrototypeError;
 use crate::prop::PropError;
-use crate::schema::variant::leaves::LeafKind;
+use crate::schema::variant::leaves::{LeafInputLocation, LeafKind};
 use crate::socket::output::OutputSocketError;
 use crate::{
     socket::input::InputSocketError, AttributePrototypeId, ComponentError, DalContext, Func,
@@ -116,6 +116,23 @@ pub enum FuncBindingError {
 
 type FuncBindingResult<T> = Result<T, FuncBindingError>;
 
+/// A problem found when validating a [`FuncBinding`] against a [`SchemaVariant`] via
+/// [`FuncBinding::validate`].
+#[remain::sorted]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BindingError {
+    #[error("input socket ({1}) for func argument ({0}) does not exist on schema variant ({2})")]
+    ArgumentInputSocketNotOnSchemaVariant(FuncArgumentId, InputSocketId, SchemaVariantId),
+    #[error("output socket ({1}) for func argument ({0}) does not exist on schema variant ({2})")]
+    ArgumentOutputSocketNotOnSchemaVariant(FuncArgumentId, OutputSocketId, SchemaVariantId),
+    #[error("prop ({1}) for func argument ({0}) does not exist on schema variant ({2})")]
+    ArgumentPropNotOnSchemaVariant(FuncArgumentId, PropId, SchemaVariantId),
+    #[error("leaf input location ({0:?}) does not exist on schema variant ({1})")]
+    LeafInputLocationNotOnSchemaVariant(LeafInputLocation, SchemaVariantId),
+    #[error("output location ({0}) does not exist on schema variant ({1})")]
+    OutputLocationNotOnSchemaVariant(AttributeFuncDestination, SchemaVariantId),
+}
+
 /// Represents the location where the function ultimately writes to
 /// We currently only allow Attribute Funcs to be attached to Props
 /// (or the attribute value in the case of a component) and Output Sockets
@@ -393,6 +410,137 @@ impl FuncBinding {
         }
     }
 
+    /// Checks that every input and output location referenced by the [`FuncBinding`] resolves on
+    /// the given [`SchemaVariant`]. All problems are collected and returned at once so that they
+    /// can be surfaced together. Bindings without input or output locations are always valid.
+    pub async fn validate(
+        &self,
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+    ) -> FuncBindingResult<Result<(), Vec<BindingError>>> {
+        let mut problems = vec![];
+        match self {
+            FuncBinding::Attribute(attribute) => {
+                if !Self::destination_is_on_schema_variant(
+                    ctx,
+                    attribute.output_location,
+                    schema_variant_id,
+                )
+                .await?
+                {
+                    problems.push(BindingError::OutputLocationNotOnSchemaVariant(
+                        attribute.output_location,
+                        schema_variant_id,
+                    ));
+                }
+
+                for argument_binding in &attribute.argument_bindings {
+                    let func_argument_id = argument_binding.func_argument_id;
+                    let source = match &argument_binding.attribute_func_input_location {
+                        AttributeFuncArgumentSource::Prop(prop_id) => {
+                            AttributeFuncDestination::Prop(*prop_id)
+                        }
+                        AttributeFuncArgumentSource::InputSocket(input_socket_id) => {
+                            AttributeFuncDestination::InputSocket(*input_socket_id)
+                        }
+                        AttributeFuncArgumentSource::OutputSocket(output_socket_id) => {
+                            AttributeFuncDestination::OutputSocket(*output_socket_id)
+                        }
+                        AttributeFuncArgumentSource::StaticArgument(_)
+                        | AttributeFuncArgumentSource::Secret(_) => continue,
+                    };
+                    if Self::destination_is_on_schema_variant(ctx, source, schema_variant_id)
+                        .await?
+                    {
+                        continue;
+                    }
+                    problems.push(match source {
+                        AttributeFuncDestination::Prop(prop_id) => {
+                            BindingError::ArgumentPropNotOnSchemaVariant(
+                                func_argument_id,
+                                prop_id,
+                                schema_variant_id,
+                            )
+                        }
+                        AttributeFuncDestination::InputSocket(input_socket_id) => {
+                            BindingError::ArgumentInputSocketNotOnSchemaVariant(
+                                func_argument_id,
+                                input_socket_id,
+                                schema_variant_id,
+                            )
+                        }
+                        AttributeFuncDestination::OutputSocket(output_socket_id) => {
+                            BindingError::ArgumentOutputSocketNotOnSchemaVariant(
+                                func_argument_id,
+                                output_socket_id,
+                                schema_variant_id,
+                            )
+                        }
+                    });
+                }
+            }
+            FuncBinding::CodeGeneration(leaf) | FuncBinding::Qualification(leaf) => {
+                for input in &leaf.inputs {
+                    if Prop::find_prop_id_by_path_opt(ctx, schema_variant_id, &input.prop_path())
+                        .await?
+                        .is_none()
+                    {
+                        problems.push(BindingError::LeafInputLocationNotOnSchemaVariant(
+                            *input,
+                            schema_variant_id,
+                        ));
+                    }
+                }
+            }
+            FuncBinding::Action(_)
+            | FuncBinding::Authentication(_)
+            | FuncBinding::Management(_) => {}
+        }
+
+        Ok(if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        })
+    }
+
+    async fn destination_is_on_schema_variant(
+        ctx: &DalContext,
+        destination: AttributeFuncDestination,
+        schema_variant_id: SchemaVariantId,
+    ) -> FuncBindingResult<bool> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let node_index = match destination {
+            AttributeFuncDestination::Prop(prop_id) => {
+                workspace_snapshot.get_node_index_by_id_opt(prop_id).await
+            }
+            AttributeFuncDestination::OutputSocket(output_socket_id) => {
+                workspace_snapshot
+                    .get_node_index_by_id_opt(output_socket_id)
+                    .await
+            }
+            AttributeFuncDestination::InputSocket(input_socket_id) => {
+                workspace_snapshot
+                    .get_node_index_by_id_opt(input_socket_id)
+                    .await
+            }
+        };
+        if node_index.is_none() {
+            return Ok(false);
+        }
+
+        match destination.find_schema_variant(ctx).await {
+            Ok(found_schema_variant_id) => Ok(found_schema_variant_id == schema_variant_id),
+            Err(FuncBindingError::SchemaVariant(
+                SchemaVariantError::NotFoundForInputSocket(_)
+                | SchemaVariantError::NotFoundForOutputSocket(_)
+                | SchemaVariantError::NotFoundForProp(_)
+                | SchemaVariantError::NotFoundForRootProp(_),
+            )) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     pub async fn for_func_id(
         ctx: &DalContext,
         func_id: FuncId,
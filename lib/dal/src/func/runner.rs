@@ -1314,6 +1314,7 @@ impl FuncRunner {
                         .code_base64
                         .ok_or_else(|| FuncRunnerError::BeforeFuncMissingCode(func.id))?,
                     arg: arg.clone(),
+                    order: 0,
                 })
             }
         }
@@ -1487,7 +1488,7 @@ impl FuncRunner {
             .ok_or(FuncRunnerError::EmptyWidgetOptionsForSecretProp(
                 secret_child_prop_id,
             ))?
-            .value;
+            .value_string();
 
         // Iterate through all default secret defining schema variants and find the output socket that matches the
         // provided secret child prop. This works on two assumptions. First: secret defining schema variants can have
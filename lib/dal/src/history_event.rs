@@ -12,6 +12,7 @@ use crate::{Tenancy, TransactionsError};
 
 const SYSTEMINIT_EMAIL_SUFFIX: &str = "@systeminit.com";
 const TEST_SYSTEMINIT_EMAIL_SUFFIX: &str = "@test.systeminit.com";
+const MACHINE_EMAIL_DOMAIN: &str = "machines.systeminit.com";
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -35,6 +36,7 @@ pub type HistoryEventResult<T> = Result<T, HistoryEventError>;
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, StrumDisplay, Clone, Copy, Hash)]
 pub enum HistoryActor {
+    Machine(MachinePk),
     SystemInit,
     User(UserPk),
 }
@@ -43,6 +45,7 @@ impl HistoryActor {
     pub fn distinct_id(&self) -> String {
         match self {
             HistoryActor::User(pk) => pk.to_string(),
+            HistoryActor::Machine(pk) => pk.to_string(),
             HistoryActor::SystemInit => "unknown-backend".to_string(),
         }
     }
@@ -50,6 +53,9 @@ impl HistoryActor {
     pub async fn email(&self, ctx: &DalContext) -> HistoryEventResult<String> {
         Ok(match self {
             HistoryActor::SystemInit => "sally@systeminit.com".to_string(),
+            // Machines are not people and must never be mistaken for a System Initiative staff
+            // member, so they get a placeholder address on a dedicated subdomain.
+            HistoryActor::Machine(machine_pk) => format!("{machine_pk}@{MACHINE_EMAIL_DOMAIN}"),
             HistoryActor::User(user_pk) => User::get_by_pk_or_error(ctx, *user_pk)
                 .await
                 .map_err(|e| HistoryEventError::User(e.to_string()))?
@@ -71,7 +77,13 @@ impl From<UserPk> for HistoryActor {
     }
 }
 
-pub use si_id::HistoryEventPk;
+impl From<MachinePk> for HistoryActor {
+    fn from(pk: MachinePk) -> Self {
+        HistoryActor::Machine(pk)
+    }
+}
+
+pub use si_id::{HistoryEventPk, MachinePk};
 
 /// HistoryEvents are the audit trail for things in SI. They track
 /// that a specific actor did something, and optionally store data
@@ -74,6 +74,8 @@ pub enum JobConsumerError {
     SerdeJson(#[from] serde_json::Error),
     #[error("standard model error: {0}")]
     StandardModel(#[from] StandardModelError),
+    #[error("execution of job {0} timed out after {1:?}")]
+    Timeout(String, Duration),
     #[error("tokio task error: {0}")]
     TokioTask(#[from] JoinError),
     #[error("transactions error: {0}")]
@@ -113,9 +115,12 @@ pub enum RetryBackoff {
 }
 
 /// Jobs that return a state of `JobCompletionState::Retry` will be retried
-/// with the requested backoff and limit
+/// with the requested backoff and limit. Jobs that return
+/// `JobCompletionState::Cancelled` were stopped before finishing their work
+/// and are not retried.
 pub enum JobCompletionState {
     Retry { limit: u32, backoff: RetryBackoff },
+    Cancelled,
     Done,
 }
 
@@ -153,7 +158,7 @@ pub trait JobConsumer: std::fmt::Debug + Sync + JobConsumerMetadata {
                         tokio::time::sleep(calculate_exponential_sleep_ms(retries, 2)).await;
                     };
                 }
-                JobCompletionState::Done => {
+                JobCompletionState::Cancelled | JobCompletionState::Done => {
                     break;
                 }
             }
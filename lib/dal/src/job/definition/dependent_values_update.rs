@@ -3,6 +3,7 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::TryFrom,
     sync::Arc,
+    time::Duration,
 };
 use telemetry_utils::metric;
 
@@ -15,10 +16,12 @@ use tokio::{
     sync::RwLock,
     task::{JoinError, JoinSet},
 };
+use tokio_util::sync::CancellationToken;
 use ulid::Ulid;
 
 use crate::{
     attribute::value::{dependent_value_graph::DependentValueGraph, AttributeValueError},
+    change_set::ChangeSetId,
     job::{
         consumer::{
             JobCompletionState, JobConsumer, JobConsumerError, JobConsumerMetadata,
@@ -29,8 +32,9 @@ use crate::{
     prop::PropError,
     status::{StatusMessageState, StatusUpdate, StatusUpdateError},
     workspace_snapshot::DependentValueRoot,
-    AccessBuilder, AttributeValue, AttributeValueId, ComponentError, ComponentId, DalContext, Func,
-    TransactionsError, Visibility, WorkspacePk, WorkspaceSnapshotError, WsEvent, WsEventError,
+    AccessBuilder, AttributeValue, AttributeValueId, Component, ComponentError, ComponentId,
+    DalContext, Func, TransactionsError, Visibility, WorkspacePk, WorkspaceSnapshotError, WsEvent,
+    WsEventError, WsEventResult, WsPayload,
 };
 
 #[remain::sorted]
@@ -44,6 +48,8 @@ pub enum DependentValueUpdateError {
     DependentValuesUpdateAuditLog(#[from] DependentValueUpdateAuditLogError),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
+    #[error("function result for attribute value {0} is {1} bytes, over the limit of {2} bytes")]
+    ResultTooLarge(AttributeValueId, usize, usize),
     #[error("status update error: {0}")]
     StatusUpdate(#[from] StatusUpdateError),
     #[error(transparent)]
@@ -58,12 +64,117 @@ pub enum DependentValueUpdateError {
 
 pub type DependentValueUpdateResult<T> = Result<T, DependentValueUpdateError>;
 
+/// The default number of iterations a single [`DependentValuesUpdate`] run may take before it
+/// stops and leaves the remaining values for a subsequent run.
+pub const DEFAULT_DVU_MAX_ITERATIONS: usize = 1_000_000;
+
+/// The default maximum size, in bytes of serialized JSON, of a function result that a
+/// [`DependentValuesUpdate`] will set as an attribute value.
+pub const DEFAULT_DVU_MAX_RESULT_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+/// The minimum time between progress updates sent while a [`DependentValuesUpdate`] runs, so that
+/// large graphs do not flood the websocket.
+const PROGRESS_UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A value that is part of a dependency cycle found by a [`DependentValuesUpdate`].
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentValuesCycleMember {
+    attribute_value_id: AttributeValueId,
+    component_id: Option<ComponentId>,
+    /// What the value is for (e.g. the path of its prop), if it could be resolved
+    description: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentValuesCyclePayload {
+    values: Vec<DependentValuesCycleMember>,
+}
+
+impl WsEvent {
+    pub async fn dependent_values_cycle_detected(
+        ctx: &DalContext,
+        values: Vec<DependentValuesCycleMember>,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::DependentValuesCycleDetected(DependentValuesCyclePayload { values }),
+        )
+        .await
+    }
+}
+
+/// The outcome of a [`DependentValuesUpdate`] run.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentValuesUpdateFinishedPayload {
+    change_set_id: ChangeSetId,
+    /// How many values had their functions executed and their results applied
+    succeeded: usize,
+    /// How many values failed to execute or to have their results applied
+    failed: usize,
+    /// Whether the run got through every value. Runs that were cancelled or stopped at their
+    /// iteration limit leave the remaining values for a later run and report `false`.
+    completed: bool,
+    duration_ms: u64,
+}
+
+impl WsEvent {
+    pub async fn dependent_values_update_finished(
+        ctx: &DalContext,
+        succeeded: usize,
+        failed: usize,
+        completed: bool,
+        duration: Duration,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::DependentValuesUpdateFinished(DependentValuesUpdateFinishedPayload {
+                change_set_id: ctx.change_set_id(),
+                succeeded,
+                failed,
+                completed,
+                duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            }),
+        )
+        .await
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
-struct DependentValuesUpdateArgs;
+#[serde(rename_all = "camelCase")]
+struct DependentValuesUpdateArgs {
+    // Jobs enqueued before this field existed do not carry it.
+    #[serde(default = "default_max_iterations")]
+    max_iterations: usize,
+    #[serde(default = "default_max_result_size_bytes")]
+    max_result_size_bytes: usize,
+}
+
+impl Default for DependentValuesUpdateArgs {
+    fn default() -> Self {
+        Self {
+            max_iterations: DEFAULT_DVU_MAX_ITERATIONS,
+            max_result_size_bytes: DEFAULT_DVU_MAX_RESULT_SIZE_BYTES,
+        }
+    }
+}
+
+fn default_max_iterations() -> usize {
+    DEFAULT_DVU_MAX_ITERATIONS
+}
+
+fn default_max_result_size_bytes() -> usize {
+    DEFAULT_DVU_MAX_RESULT_SIZE_BYTES
+}
 
 impl From<DependentValuesUpdate> for DependentValuesUpdateArgs {
-    fn from(_value: DependentValuesUpdate) -> Self {
-        Self
+    fn from(value: DependentValuesUpdate) -> Self {
+        Self {
+            max_iterations: value.max_iterations,
+            max_result_size_bytes: value.max_result_size_bytes,
+        }
     }
 }
 
@@ -72,19 +183,54 @@ pub struct DependentValuesUpdate {
     access_builder: AccessBuilder,
     visibility: Visibility,
     job: Option<JobInfo>,
+    max_iterations: usize,
+    max_result_size_bytes: usize,
     #[serde(skip)]
     set_value_lock: Arc<RwLock<()>>,
+    #[serde(skip)]
+    cancellation_token: CancellationToken,
 }
 
 impl DependentValuesUpdate {
     pub fn new(access_builder: AccessBuilder, visibility: Visibility) -> Box<Self> {
+        Self::new_with_max_iterations(access_builder, visibility, DEFAULT_DVU_MAX_ITERATIONS)
+    }
+
+    /// Creates a [`DependentValuesUpdate`] that stops after `max_iterations` iterations, guarding
+    /// against runaway dependency graphs. Any values left over are re-added as unfinished
+    /// [`DependentValueRoots`](DependentValueRoot).
+    pub fn new_with_max_iterations(
+        access_builder: AccessBuilder,
+        visibility: Visibility,
+        max_iterations: usize,
+    ) -> Box<Self> {
         Box::new(Self {
             access_builder,
             visibility,
             job: None,
+            max_iterations,
+            max_result_size_bytes: DEFAULT_DVU_MAX_RESULT_SIZE_BYTES,
             set_value_lock: Arc::new(RwLock::new(())),
+            cancellation_token: CancellationToken::new(),
         })
     }
+
+    /// Uses the provided [`CancellationToken`] to stop this job mid-run (e.g. when the change set
+    /// is abandoned). A cancelled run re-adds any values it did not get to as unfinished
+    /// [`DependentValueRoots`](DependentValueRoot) and completes with
+    /// [`JobCompletionState::Cancelled`].
+    pub fn with_cancellation_token(mut self: Box<Self>, token: CancellationToken) -> Box<Self> {
+        self.cancellation_token = token;
+        self
+    }
+
+    /// Rejects function results larger than `max_result_size_bytes` of serialized JSON instead of
+    /// setting them, treating them as execution errors. Defaults to
+    /// [`DEFAULT_DVU_MAX_RESULT_SIZE_BYTES`].
+    pub fn with_max_result_size(mut self: Box<Self>, max_result_size_bytes: usize) -> Box<Self> {
+        self.max_result_size_bytes = max_result_size_bytes;
+        self
+    }
 }
 
 impl JobProducer for DependentValuesUpdate {
@@ -140,6 +286,8 @@ struct StatusUpdateTracker {
     values_by_component: HashMap<ComponentId, HashSet<AttributeValueId>>,
     components_by_value: HashMap<AttributeValueId, ComponentId>,
     active_components: HashSet<ComponentId>,
+    concurrency_limits: HashMap<ComponentId, usize>,
+    in_flight_values: HashMap<ComponentId, usize>,
 }
 
 impl StatusUpdateTracker {
@@ -151,6 +299,8 @@ impl StatusUpdateTracker {
             values_by_component: HashMap::new(),
             components_by_value: HashMap::new(),
             active_components: HashSet::new(),
+            concurrency_limits: HashMap::new(),
+            in_flight_values: HashMap::new(),
         };
 
         for value_id in value_ids {
@@ -165,6 +315,13 @@ impl StatusUpdateTracker {
             tracker.components_by_value.insert(value_id, component_id);
         }
 
+        tracker.concurrency_limits =
+            Component::concurrency_limits_by_id(ctx, tracker.values_by_component.keys().copied())
+                .await?
+                .into_iter()
+                .map(|(component_id, limit)| (component_id, limit as usize))
+                .collect();
+
         Ok(tracker)
     }
 
@@ -178,10 +335,45 @@ impl StatusUpdateTracker {
             .is_some_and(|component_id| !self.active_components.contains(component_id))
     }
 
+    /// Returns whether starting this value would go past the concurrency limit that applies to
+    /// it. A component with its own limit runs at most that many of its values at once. Any other
+    /// component is held back by the workspace limit on how many components have been started.
+    fn would_exceed_concurrency_limit(
+        &self,
+        value_id: AttributeValueId,
+        workspace_limit: usize,
+    ) -> bool {
+        let Some(component_id) = self.components_by_value.get(&value_id) else {
+            return false;
+        };
+
+        match self.concurrency_limits.get(component_id) {
+            Some(component_limit) => {
+                self.in_flight_values
+                    .get(component_id)
+                    .copied()
+                    .unwrap_or_default()
+                    >= *component_limit
+            }
+            None => {
+                self.would_start_component(value_id)
+                    && self.active_components_count() >= workspace_limit
+            }
+        }
+    }
+
+    /// Returns whether the component for this value has its own concurrency limit.
+    fn has_component_concurrency_limit(&self, value_id: AttributeValueId) -> bool {
+        self.components_by_value
+            .get(&value_id)
+            .is_some_and(|component_id| self.concurrency_limits.contains_key(component_id))
+    }
+
     fn start_value(&mut self, value_id: AttributeValueId) -> Option<ComponentId> {
         self.components_by_value
             .get(&value_id)
             .and_then(|component_id| {
+                *self.in_flight_values.entry(*component_id).or_default() += 1;
                 self.active_components
                     .insert(*component_id)
                     .then_some(*component_id)
@@ -189,6 +381,14 @@ impl StatusUpdateTracker {
     }
 
     fn finish_value(&mut self, value_id: AttributeValueId) -> Option<ComponentId> {
+        if let Some(in_flight) = self
+            .components_by_value
+            .get(&value_id)
+            .and_then(|component_id| self.in_flight_values.get_mut(component_id))
+        {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+
         self.components_by_value
             .get(&value_id)
             .and_then(
@@ -204,13 +404,14 @@ impl StatusUpdateTracker {
     }
 
     fn finish_remaining(&self) -> Vec<StatusUpdate> {
-        self.values_by_component
+        let component_ids: Vec<ComponentId> = self
+            .values_by_component
             .iter()
             .filter(|(_, values)| !values.is_empty())
-            .map(|(component_id, _)| {
-                StatusUpdate::new_dvu(StatusMessageState::StatusFinished, *component_id)
-            })
-            .collect()
+            .map(|(component_id, _)| *component_id)
+            .collect();
+
+        StatusUpdate::new_dvu_batch(StatusMessageState::StatusFinished, &component_ids)
     }
 
     fn get_status_update(
@@ -235,7 +436,9 @@ impl DependentValuesUpdate {
         let span = Span::current();
         metric!(counter.dvu_concurrency_count = 1);
 
-        let roots = ctx.workspace_snapshot()?.take_dependent_values().await?;
+        // The roots stay on the graph until the run is over, so that a run that fails part way
+        // through leaves them for the next one.
+        let roots = ctx.workspace_snapshot()?.peek_dependent_values().await?;
 
         let mut unfinished_values: HashSet<Ulid> = HashSet::new();
         let mut finished_values: HashSet<Ulid> = HashSet::new();
@@ -258,7 +461,7 @@ impl DependentValuesUpdate {
 
         let concurrency_limit = ctx.get_workspace().await?.component_concurrency_limit() as usize;
 
-        let mut dependency_graph = DependentValueGraph::new(ctx, roots).await?;
+        let mut dependency_graph = DependentValueGraph::new(ctx, roots.clone()).await?;
 
         debug!(
             "DependentValueGraph calculation took: {:?}",
@@ -274,8 +477,15 @@ impl DependentValuesUpdate {
                 dependency_graph.remove_value(value_id);
             }
         }
+        // Values in a cycle will never become independent, so let the user know which ones they
+        // are rather than leaving them to wonder why they never update.
+        for cycle in dependency_graph.value_cycles() {
+            report_cycle(ctx, cycle).await;
+        }
+
         let all_value_ids = dependency_graph.all_value_ids();
-        metric!(counter.dvu.values_to_run = all_value_ids.len());
+        let total_value_count = all_value_ids.len();
+        metric!(counter.dvu.values_to_run = total_value_count);
 
         let mut tracker = StatusUpdateTracker::new_for_values(ctx, all_value_ids).await?;
 
@@ -285,12 +495,29 @@ impl DependentValuesUpdate {
         let mut independent_value_ids: HashSet<AttributeValueId> =
             dependency_graph.independent_values().into_iter().collect();
         let mut would_start_ids = HashSet::new();
+        let mut iterations = 0;
+        let mut max_iterations_exceeded = false;
+        let mut cancelled = false;
+        let mut last_progress_update = tokio::time::Instant::now();
+        let mut succeeded_count = 0;
+        let mut failed_count = 0;
 
         loop {
             if independent_value_ids.is_empty() && task_id_to_av_id.is_empty() {
                 break;
             }
 
+            if self.cancellation_token.is_cancelled() {
+                cancelled = true;
+                break;
+            }
+
+            if iterations >= self.max_iterations {
+                max_iterations_exceeded = true;
+                break;
+            }
+            iterations += 1;
+
             if independent_value_ids
                 .difference(&would_start_ids)
                 .next()
@@ -308,10 +535,16 @@ impl DependentValuesUpdate {
                     {
                         let id = Ulid::new();
 
-                        if tracker.would_start_component(attribute_value_id)
-                            && tracker.active_components_count() >= concurrency_limit
+                        if tracker
+                            .would_exceed_concurrency_limit(attribute_value_id, concurrency_limit)
                         {
-                            would_start_ids.insert(attribute_value_id);
+                            // A value held back by its component's own limit waits for one of
+                            // that component's in-flight values, which are still running, so it
+                            // is tried again once they finish. Everything else waits for the
+                            // next run.
+                            if !tracker.has_component_concurrency_limit(attribute_value_id) {
+                                would_start_ids.insert(attribute_value_id);
+                            }
                             continue;
                         }
 
@@ -333,6 +566,7 @@ impl DependentValuesUpdate {
                             before_value,
                             self.set_value_lock.clone(),
                             status_update,
+                            self.max_result_size_bytes,
                         ));
                         task_id_to_av_id.insert(id, attribute_value_id);
                         spawned_ids.insert(attribute_value_id);
@@ -340,8 +574,16 @@ impl DependentValuesUpdate {
                 }
             }
 
-            // Wait for a task to finish
-            if let Some(join_result) = update_join_set.join_next().await {
+            // Wait for a task to finish, unless we are cancelled while waiting
+            let maybe_join_result = tokio::select! {
+                biased;
+                _ = self.cancellation_token.cancelled() => {
+                    cancelled = true;
+                    break;
+                }
+                maybe_join_result = update_join_set.join_next() => maybe_join_result,
+            };
+            if let Some(join_result) = maybe_join_result {
                 let (task_id, execution_result, before_value) = join_result?;
 
                 metric!(counter.dvu.values_to_run = -1);
@@ -384,6 +626,7 @@ impl DependentValuesUpdate {
                                         // become independent values (once all other dependencies are removed)
                                         dependency_graph.remove_value(finished_value_id);
                                         drop(write_guard);
+                                        succeeded_count += 1;
 
                                         // Publish the audit log for the updated dependent value.
                                         audit_log::write(
@@ -399,14 +642,17 @@ impl DependentValuesUpdate {
                                         execution_error(ctx, err.to_string(), finished_value_id)
                                             .await;
                                         dependency_graph.cycle_on_self(finished_value_id);
+                                        failed_count += 1;
                                     }
                                 },
                                 Ok(false) => {
                                     dependency_graph.remove_value(finished_value_id);
+                                    succeeded_count += 1;
                                 }
                                 Err(err) => {
                                     execution_error(ctx, err.to_string(), finished_value_id).await;
                                     dependency_graph.cycle_on_self(finished_value_id);
+                                    failed_count += 1;
                                 }
                             }
                         }
@@ -420,6 +666,7 @@ impl DependentValuesUpdate {
                             execution_error(ctx, err.to_string(), finished_value_id).await;
                             drop(read_guard);
                             dependency_graph.cycle_on_self(finished_value_id);
+                            failed_count += 1;
                         }
                     }
 
@@ -430,23 +677,72 @@ impl DependentValuesUpdate {
                             error!(si.error.message = ?err, "status update finished event send failed for AttributeValue {finished_value_id}");
                         }
                     }
+
+                    if last_progress_update.elapsed() >= PROGRESS_UPDATE_INTERVAL {
+                        send_progress_update(ctx, &dependency_graph, total_value_count).await;
+                        last_progress_update = tokio::time::Instant::now();
+                    }
                 }
             }
 
             independent_value_ids = dependency_graph.independent_values().into_iter().collect();
         }
 
+        if total_value_count > 0 {
+            send_progress_update(ctx, &dependency_graph, total_value_count).await;
+        }
+
         let snap = ctx.workspace_snapshot()?;
+        snap.remove_dependent_values(&roots).await?;
         let mut added_unfinished = false;
-        for value_id in &independent_value_ids {
-            if spawned_ids.contains(value_id) {
-                snap.add_dependent_value_root(DependentValueRoot::Finished(value_id.into()))
-                    .await?;
+        if max_iterations_exceeded || cancelled {
+            // Stop any in-flight executions and leave everything we did not get to (including
+            // those executions) for the next run.
+            update_join_set.abort_all();
+            let remaining_value_ids: HashSet<AttributeValueId> = independent_value_ids
+                .iter()
+                .chain(task_id_to_av_id.values())
+                .copied()
+                .collect();
+            let component_ids: HashSet<ComponentId> = remaining_value_ids
+                .iter()
+                .filter_map(|value_id| tracker.components_by_value.get(value_id).copied())
+                .collect();
+            if cancelled {
+                info!(
+                    ?component_ids,
+                    "DependentValuesUpdate cancelled, re-adding remaining values as unfinished roots"
+                );
+                // The aborted executions will never send their "finish" updates.
+                for status_update in tracker.finish_remaining() {
+                    if let Err(err) = send_status_update(ctx, status_update).await {
+                        error!(si.error.message = ?err, "status update finished event send for cancelled component failed");
+                    }
+                }
             } else {
+                error!(
+                    max_iterations = self.max_iterations,
+                    ?component_ids,
+                    "DependentValuesUpdate exceeded max iterations, re-adding remaining values as unfinished roots"
+                );
+            }
+
+            for value_id in remaining_value_ids {
                 added_unfinished = true;
                 snap.add_dependent_value_root(DependentValueRoot::Unfinished(value_id.into()))
                     .await?;
             }
+        } else {
+            for value_id in &independent_value_ids {
+                if spawned_ids.contains(value_id) {
+                    snap.add_dependent_value_root(DependentValueRoot::Finished(value_id.into()))
+                        .await?;
+                } else {
+                    added_unfinished = true;
+                    snap.add_dependent_value_root(DependentValueRoot::Unfinished(value_id.into()))
+                        .await?;
+                }
+            }
         }
 
         // If we encounter a failure when executing the values above, we may not
@@ -467,8 +763,23 @@ impl DependentValuesUpdate {
 
         debug!("DependentValuesUpdate took: {:?}", start.elapsed());
 
+        WsEvent::dependent_values_update_finished(
+            ctx,
+            succeeded_count,
+            failed_count,
+            !(cancelled || max_iterations_exceeded),
+            start.elapsed(),
+        )
+        .await?
+        .publish_on_commit(ctx)
+        .await?;
+
         ctx.commit().await?;
         metric!(counter.dvu_concurrency_count = -1);
+
+        if cancelled {
+            return Ok(JobCompletionState::Cancelled);
+        }
         Ok(JobCompletionState::Done)
     }
 }
@@ -494,10 +805,7 @@ async fn execution_error_detail(
     ctx: &DalContext,
     id: AttributeValueId,
 ) -> DependentValueUpdateResult<String> {
-    let is_for = AttributeValue::is_for(ctx, id)
-        .await?
-        .debug_info(ctx)
-        .await?;
+    let is_for = value_description(ctx, id).await?;
     let prototype_func = AttributeValue::prototype_func(ctx, id).await?.name;
 
     Ok(format!(
@@ -505,6 +813,54 @@ async fn execution_error_detail(
     ))
 }
 
+async fn value_description(
+    ctx: &DalContext,
+    id: AttributeValueId,
+) -> DependentValueUpdateResult<String> {
+    Ok(AttributeValue::is_for(ctx, id)
+        .await?
+        .debug_info(ctx)
+        .await?)
+}
+
+async fn report_cycle(ctx: &DalContext, cycle: Vec<AttributeValueId>) {
+    let mut values = Vec::with_capacity(cycle.len());
+    for attribute_value_id in cycle {
+        values.push(DependentValuesCycleMember {
+            attribute_value_id,
+            component_id: AttributeValue::component_id(ctx, attribute_value_id)
+                .await
+                .ok(),
+            description: value_description(ctx, attribute_value_id).await.ok(),
+        });
+    }
+
+    let descriptions: Vec<String> = values
+        .iter()
+        .map(|value| {
+            value
+                .description
+                .clone()
+                .unwrap_or_else(|| value.attribute_value_id.to_string())
+        })
+        .collect();
+    warn!(
+        name = "dependent_values_cycle",
+        si.error.message = format!("dependency cycle between {}", descriptions.join(", ")),
+    );
+
+    match WsEvent::dependent_values_cycle_detected(ctx, values).await {
+        Ok(event) => {
+            if let Err(err) = event.publish_immediately(ctx).await {
+                error!(si.error.message = ?err, "dependent values cycle event send failed");
+            }
+        }
+        Err(err) => {
+            error!(si.error.message = ?err, "could not create dependent values cycle event");
+        }
+    }
+}
+
 type PrototypeFunctionExecutionResult = (
     Ulid,
     DependentValueUpdateResult<(FuncRunValue, Func, Vec<AttributeValueId>)>,
@@ -522,6 +878,7 @@ type PrototypeFunctionExecutionResult = (
         si.attribute_value.id = %attribute_value_id,
     ),
 )]
+#[allow(clippy::too_many_arguments)]
 async fn values_from_prototype_function_execution(
     task_id: Ulid,
     parent_span: Span,
@@ -530,6 +887,7 @@ async fn values_from_prototype_function_execution(
     before_value: Option<serde_json::Value>,
     set_value_lock: Arc<RwLock<()>>,
     status_update: Option<StatusUpdate>,
+    max_result_size_bytes: usize,
 ) -> PrototypeFunctionExecutionResult {
     metric!(counter.dvu.function_execution = 1);
 
@@ -542,11 +900,54 @@ async fn values_from_prototype_function_execution(
     let result =
         AttributeValue::execute_prototype_function(&ctx, attribute_value_id, set_value_lock)
             .await
-            .map_err(Into::into);
+            .map_err(Into::into)
+            .and_then(|(execution_values, func, input_attribute_value_ids)| {
+                // Oversized results would bloat the snapshot once stored, so they are rejected
+                // like any other execution error. The processed value is either the unprocessed
+                // value itself or an empty container, so only the unprocessed value is measured.
+                let size = json_size(
+                    execution_values
+                        .unprocessed_value()
+                        .or(execution_values.value()),
+                );
+                if size > max_result_size_bytes {
+                    return Err(DependentValueUpdateError::ResultTooLarge(
+                        attribute_value_id,
+                        size,
+                        max_result_size_bytes,
+                    ));
+                }
+
+                Ok((execution_values, func, input_attribute_value_ids))
+            });
 
     (task_id, result, before_value)
 }
 
+/// The length of `value` serialized as JSON, without holding the serialized bytes.
+fn json_size(value: Option<&serde_json::Value>) -> usize {
+    struct ByteCounter(usize);
+
+    impl std::io::Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let Some(value) = value else {
+        return 0;
+    };
+    let mut counter = ByteCounter(0);
+    // Writing to the counter cannot fail, and neither can serializing a `Value`.
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
 async fn send_status_update(
     ctx: &DalContext,
     status_update: StatusUpdate,
@@ -559,15 +960,33 @@ async fn send_status_update(
     Ok(())
 }
 
+async fn send_progress_update(
+    ctx: &DalContext,
+    dependency_graph: &DependentValueGraph,
+    total_value_count: usize,
+) {
+    let completed = total_value_count.saturating_sub(dependency_graph.value_count());
+    let status_update = StatusUpdate::new_dvu_progress(completed, total_value_count);
+    if let Err(err) = send_status_update(ctx, status_update).await {
+        error!(si.error.message = ?err, "status update progress event send failed");
+    }
+}
+
 impl TryFrom<JobInfo> for DependentValuesUpdate {
     type Error = JobConsumerError;
 
     fn try_from(job: JobInfo) -> Result<Self, Self::Error> {
+        // Jobs enqueued before the args had any fields serialized them as `null`.
+        let args = Option::<DependentValuesUpdateArgs>::deserialize(&job.arg)?.unwrap_or_default();
+
         Ok(Self {
             access_builder: job.access_builder,
             visibility: job.visibility,
             job: Some(job),
+            max_iterations: args.max_iterations,
+            max_result_size_bytes: args.max_result_size_bytes,
             set_value_lock: Arc::new(RwLock::new(())),
+            cancellation_token: CancellationToken::new(),
         })
     }
 }
@@ -709,3 +1128,98 @@ pub mod audit_log {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args_enqueued_without_fields_use_defaults() {
+        for arg in [serde_json::Value::Null, serde_json::json!({})] {
+            let args = Option::<DependentValuesUpdateArgs>::deserialize(&arg)
+                .expect("could not deserialize args")
+                .unwrap_or_default();
+            assert_eq!(DEFAULT_DVU_MAX_ITERATIONS, args.max_iterations);
+            assert_eq!(
+                DEFAULT_DVU_MAX_RESULT_SIZE_BYTES,
+                args.max_result_size_bytes
+            );
+        }
+    }
+
+    fn tracker_for_components(
+        components: &[(ComponentId, Option<usize>, &[AttributeValueId])],
+    ) -> StatusUpdateTracker {
+        let mut tracker = StatusUpdateTracker {
+            values_by_component: HashMap::new(),
+            components_by_value: HashMap::new(),
+            active_components: HashSet::new(),
+            concurrency_limits: HashMap::new(),
+            in_flight_values: HashMap::new(),
+        };
+        for (component_id, limit, value_ids) in components {
+            tracker
+                .values_by_component
+                .insert(*component_id, value_ids.iter().copied().collect());
+            for value_id in *value_ids {
+                tracker.components_by_value.insert(*value_id, *component_id);
+            }
+            if let Some(limit) = limit {
+                tracker.concurrency_limits.insert(*component_id, *limit);
+            }
+        }
+        tracker
+    }
+
+    #[test]
+    fn components_with_different_concurrency_limits() {
+        let heavy = ComponentId::generate();
+        let light = ComponentId::generate();
+        let plain = ComponentId::generate();
+        let heavy_values = [AttributeValueId::generate(), AttributeValueId::generate()];
+        let light_values = [
+            AttributeValueId::generate(),
+            AttributeValueId::generate(),
+            AttributeValueId::generate(),
+        ];
+        let plain_values = [AttributeValueId::generate()];
+        let mut tracker = tracker_for_components(&[
+            (heavy, Some(1), &heavy_values[..]),
+            (light, Some(2), &light_values[..]),
+            (plain, None, &plain_values[..]),
+        ]);
+        let workspace_limit = 2;
+
+        // Each component is held to its own number of in-flight values.
+        tracker.start_value(heavy_values[0]);
+        assert!(tracker.would_exceed_concurrency_limit(heavy_values[1], workspace_limit));
+        tracker.start_value(light_values[0]);
+        assert!(!tracker.would_exceed_concurrency_limit(light_values[1], workspace_limit));
+        tracker.start_value(light_values[1]);
+        assert!(tracker.would_exceed_concurrency_limit(light_values[2], workspace_limit));
+
+        // A component without a limit of its own falls back to the workspace limit, which the
+        // two running components already use up.
+        assert!(tracker.would_exceed_concurrency_limit(plain_values[0], workspace_limit));
+        assert!(!tracker.would_exceed_concurrency_limit(plain_values[0], 3));
+
+        // Finishing a value makes room for the next one of the same component only.
+        tracker.finish_value(heavy_values[0]);
+        assert!(!tracker.would_exceed_concurrency_limit(heavy_values[1], workspace_limit));
+        assert!(tracker.would_exceed_concurrency_limit(light_values[2], workspace_limit));
+    }
+
+    #[test]
+    fn args_round_trip() {
+        let arg = serde_json::to_value(DependentValuesUpdateArgs {
+            max_iterations: 7,
+            max_result_size_bytes: 11,
+        })
+        .expect("could not serialize args");
+        let args = Option::<DependentValuesUpdateArgs>::deserialize(&arg)
+            .expect("could not deserialize args")
+            .unwrap_or_default();
+        assert_eq!(7, args.max_iterations);
+        assert_eq!(11, args.max_result_size_bytes);
+    }
+}
@@ -10,11 +10,12 @@ use thiserror::Error;
 use crate::action::prototype::ActionKind;
 use crate::validation::ValidationStatus;
 use crate::{
-    action::ActionCompletionStatus, func::argument::FuncArgumentKind, prop::WidgetOptions,
-    property_editor::schema::WidgetKind, socket::connection_annotation::ConnectionAnnotation,
-    ActionPrototypeId, ComponentId, ComponentType, DalContext, FuncBackendKind,
-    FuncBackendResponseType, FuncId, PropId, PropKind, SchemaId, SchemaVariant, SchemaVariantId,
-    SocketArity, SocketKind, Timestamp, UserPk,
+    action::ActionCompletionStatus, func::argument::FuncArgumentKind, prop::WidgetOption,
+    prop::WidgetOptions, property_editor::schema::WidgetKind,
+    socket::connection_annotation::ConnectionAnnotation, ActionPrototypeId, ComponentId,
+    ComponentType, DalContext, FuncBackendKind, FuncBackendResponseType, FuncId, PropId, PropKind,
+    SchemaId, SchemaVariant, SchemaVariantId, SocketArity, SocketKind, SocketNodeSide, Timestamp,
+    UserPk,
 };
 
 #[remain::sorted]
@@ -222,15 +223,21 @@ pub struct AttributePrototypeContentV1 {
 pub enum ComponentContent {
     V1(ComponentContentV1),
     V2(ComponentContentV2),
+    V3(ComponentContentV3),
 }
 
 impl ComponentContent {
-    pub fn extract(self) -> ComponentContentV2 {
+    pub fn extract(self) -> ComponentContentV3 {
         match self {
-            ComponentContent::V1(v1) => ComponentContentV2 {
+            ComponentContent::V1(v1) => ComponentContentV3 {
                 timestamp: v1.timestamp,
+                concurrency_limit: None,
             },
-            ComponentContent::V2(v2) => v2,
+            ComponentContent::V2(v2) => ComponentContentV3 {
+                timestamp: v2.timestamp,
+                concurrency_limit: None,
+            },
+            ComponentContent::V3(v3) => v3,
         }
     }
 }
@@ -249,6 +256,13 @@ pub struct ComponentContentV2 {
     pub timestamp: Timestamp,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentContentV3 {
+    pub timestamp: Timestamp,
+    /// Overrides the workspace's component concurrency limit for this component.
+    pub concurrency_limit: Option<i32>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum ViewContent {
     V1(ViewContentV1),
@@ -364,6 +378,7 @@ pub struct FuncArgumentContentV1 {
 pub enum InputSocketContent {
     V1(InputSocketContentV1),
     V2(InputSocketContentV2),
+    V3(InputSocketContentV3),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -397,6 +412,39 @@ pub struct InputSocketContentV2 {
     pub connection_annotations: Vec<ConnectionAnnotation>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct InputSocketContentV3 {
+    pub timestamp: Timestamp,
+    /// Name for [`Self`] that can be used for identification.
+    pub name: String,
+    /// Definition of the inbound type (e.g. "JSONSchema" or "Number").
+    pub inbound_type_definition: Option<String>,
+    /// Definition of the outbound type (e.g. "JSONSchema" or "Number").
+    pub outbound_type_definition: Option<String>,
+    pub kind: SocketKind,
+    pub required: bool,
+    pub ui_hidden: bool,
+    pub connection_annotations: Vec<ConnectionAnnotation>,
+    /// Which side of the node a bidirectional socket is drawn on.
+    pub node_side: Option<SocketNodeSide>,
+}
+
+impl From<InputSocketContentV2> for InputSocketContentV3 {
+    fn from(value: InputSocketContentV2) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            name: value.name,
+            inbound_type_definition: value.inbound_type_definition,
+            outbound_type_definition: value.outbound_type_definition,
+            kind: value.kind,
+            required: value.required,
+            ui_hidden: value.ui_hidden,
+            connection_annotations: value.connection_annotations,
+            node_side: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum ModuleContent {
     V1(ModuleContentV1),
@@ -453,6 +501,26 @@ impl From<ModuleContentV1> for ModuleContentV2 {
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum OutputSocketContent {
     V1(OutputSocketContentV1),
+    V2(OutputSocketContentV2),
+}
+
+impl OutputSocketContent {
+    pub fn extract(self) -> OutputSocketContentV2 {
+        match self {
+            OutputSocketContent::V1(v1) => OutputSocketContentV2 {
+                timestamp: v1.timestamp,
+                name: v1.name,
+                type_definition: v1.type_definition,
+                arity: v1.arity,
+                kind: v1.kind,
+                required: v1.required,
+                ui_hidden: v1.ui_hidden,
+                connection_annotations: v1.connection_annotations,
+                node_side: None,
+            },
+            OutputSocketContent::V2(v2) => v2,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -469,9 +537,75 @@ pub struct OutputSocketContentV1 {
     pub connection_annotations: Vec<ConnectionAnnotation>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct OutputSocketContentV2 {
+    pub timestamp: Timestamp,
+    /// Name for [`Self`] that can be used for identification.
+    pub name: String,
+    /// Definition of the data type (e.g. "JSONSchema" or "Number").
+    pub type_definition: Option<String>,
+    pub arity: SocketArity,
+    pub kind: SocketKind,
+    pub required: bool,
+    pub ui_hidden: bool,
+    pub connection_annotations: Vec<ConnectionAnnotation>,
+    /// Which side of the node a bidirectional socket is drawn on.
+    pub node_side: Option<SocketNodeSide>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
 pub enum PropContent {
     V1(PropContentV1),
+    V2(PropContentV2),
+    V3(PropContentV3),
+}
+
+impl PropContent {
+    pub fn extract(self) -> PropContentV3 {
+        match self {
+            PropContent::V1(v1) => PropContent::V2(PropContentV2 {
+                timestamp: v1.timestamp,
+                name: v1.name,
+                kind: v1.kind,
+                widget_kind: v1.widget_kind,
+                widget_options: v1.widget_options.map(|options| {
+                    options
+                        .into_iter()
+                        .map(|option| WidgetOption::new(option.label, option.value))
+                        .collect()
+                }),
+                doc_link: v1.doc_link,
+                documentation: v1.documentation,
+                hidden: v1.hidden,
+                refers_to_prop_id: v1.refers_to_prop_id,
+                diff_func_id: v1.diff_func_id,
+                validation_format: v1.validation_format,
+            })
+            .extract(),
+            PropContent::V2(v2) => PropContentV3 {
+                timestamp: v2.timestamp,
+                name: v2.name,
+                kind: v2.kind,
+                widget_kind: v2.widget_kind,
+                widget_options: v2.widget_options,
+                doc_link: v2.doc_link,
+                documentation: v2.documentation,
+                hidden: v2.hidden,
+                refers_to_prop_id: v2.refers_to_prop_id,
+                diff_func_id: v2.diff_func_id,
+                validation_format: v2.validation_format,
+                required: false,
+            },
+            PropContent::V3(v3) => v3,
+        }
+    }
+}
+
+/// The widget option format stored in [`PropContentV1`], which only supported string values.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WidgetOptionV1 {
+    pub label: String,
+    pub value: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -484,6 +618,58 @@ pub struct PropContentV1 {
     /// The kind of "widget" that should be used for this [`Prop`].
     pub widget_kind: WidgetKind,
     /// The configuration of the "widget".
+    pub widget_options: Option<Vec<WidgetOptionV1>>,
+    /// A link to external documentation for working with this specific [`Prop`].
+    pub doc_link: Option<String>,
+    /// Embedded documentation for working with this specific [`Prop`].
+    pub documentation: Option<String>,
+    /// A toggle for whether or not the [`Prop`] should be visually hidden.
+    pub hidden: bool,
+    /// Props can be connected to eachother to signify that they should contain the same value
+    /// This is useful for diffing the resource with the domain, to suggest actions if the real world changes
+    pub refers_to_prop_id: Option<PropId>,
+    /// Connected props may need a custom diff function
+    pub diff_func_id: Option<FuncId>,
+    /// A serialized validation format JSON object for the prop.
+    pub validation_format: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PropContentV2 {
+    pub timestamp: Timestamp,
+    /// The name of the [`Prop`].
+    pub name: String,
+    /// The kind of the [`Prop`].
+    pub kind: PropKind,
+    /// The kind of "widget" that should be used for this [`Prop`].
+    pub widget_kind: WidgetKind,
+    /// The configuration of the "widget", which may contain non-string values.
+    pub widget_options: Option<WidgetOptions>,
+    /// A link to external documentation for working with this specific [`Prop`].
+    pub doc_link: Option<String>,
+    /// Embedded documentation for working with this specific [`Prop`].
+    pub documentation: Option<String>,
+    /// A toggle for whether or not the [`Prop`] should be visually hidden.
+    pub hidden: bool,
+    /// Props can be connected to eachother to signify that they should contain the same value
+    /// This is useful for diffing the resource with the domain, to suggest actions if the real world changes
+    pub refers_to_prop_id: Option<PropId>,
+    /// Connected props may need a custom diff function
+    pub diff_func_id: Option<FuncId>,
+    /// A serialized validation format JSON object for the prop.
+    pub validation_format: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PropContentV3 {
+    pub timestamp: Timestamp,
+    /// The name of the [`Prop`].
+    pub name: String,
+    /// The kind of the [`Prop`].
+    pub kind: PropKind,
+    /// The kind of "widget" that should be used for this [`Prop`].
+    pub widget_kind: WidgetKind,
+    /// The configuration of the "widget", which may contain non-string values.
     pub widget_options: Option<WidgetOptions>,
     /// A link to external documentation for working with this specific [`Prop`].
     pub doc_link: Option<String>,
@@ -498,6 +684,8 @@ pub struct PropContentV1 {
     pub diff_func_id: Option<FuncId>,
     /// A serialized validation format JSON object for the prop.
     pub validation_format: Option<String>,
+    /// Whether the [`Prop`] must be given a value, e.g. when generating input forms.
+    pub required: bool,
 }
 
 #[derive(Debug, Clone, EnumDiscriminants, Serialize, Deserialize, PartialEq)]
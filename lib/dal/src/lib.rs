@@ -85,13 +85,13 @@ pub use component::ComponentError;
 pub use component::ComponentId;
 pub use context::{
     AccessBuilder, Connections, DalContext, DalContextBuilder, DalLayerDb, RequestContext,
-    ServicesContext, Transactions, TransactionsError,
+    ServicesContext, SnapshotPin, Transactions, TransactionsError,
 };
 pub use func::{
     backend::{FuncBackendKind, FuncBackendResponseType},
     Func, FuncError, FuncId,
 };
-pub use history_event::{HistoryActor, HistoryEvent, HistoryEventError};
+pub use history_event::{HistoryActor, HistoryEvent, HistoryEventError, MachinePk};
 pub use jetstream_streams::{JetstreamStreams, JetstreamStreamsError};
 pub use job::processor::{JobQueueProcessor, NatsProcessor};
 pub use key_pair::{KeyPair, KeyPairError, KeyPairResult, PublicKey};
@@ -120,13 +120,14 @@ pub use socket::input::{InputSocket, InputSocketId};
 pub use socket::output::{OutputSocket, OutputSocketId};
 pub use socket::SocketArity;
 pub use socket::SocketKind;
+pub use socket::SocketNodeSide;
 pub use standard_connection::{HelperError, HelperResult};
 pub use standard_model::{StandardModel, StandardModelError, StandardModelResult};
 pub use tenancy::{Tenancy, TenancyError};
 pub use timestamp::{Timestamp, TimestampError};
 pub use user::{User, UserError, UserPk, UserResult};
 pub use visibility::Visibility;
-pub use workspace::{Workspace, WorkspaceError, WorkspacePk, WorkspaceResult};
+pub use workspace::{Workspace, WorkspaceError, WorkspaceExportExt, WorkspacePk, WorkspaceResult};
 pub use workspace_snapshot::graph::{WorkspaceSnapshotGraph, WorkspaceSnapshotGraphVCurrent};
 pub use workspace_snapshot::{
     edge_weight::{EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants},
@@ -219,6 +220,18 @@ pub async fn migrate(pg: &PgPool) -> ModelResult<()> {
     Ok(())
 }
 
+/// Returns the names of the embedded database migrations that [`migrate`] would apply, without
+/// applying any of them.
+#[instrument(level = "info", skip_all)]
+pub async fn pending_migrations(pg: &PgPool) -> ModelResult<Vec<String>> {
+    Ok(pg
+        .pending_migrations(embedded::migrations::runner())
+        .await?
+        .iter()
+        .map(ToString::to_string)
+        .collect())
+}
+
 pub fn generate_unique_id(length: usize) -> String {
     let mut rng = rand::thread_rng();
     (0..length)
@@ -254,6 +267,7 @@ pub fn generate_name() -> String {
 )]
 #[strum(serialize_all = "camelCase")]
 pub enum MigrationMode {
+    DryRun,
     Run,
     RunAndQuit,
     Skip,
@@ -271,6 +285,10 @@ impl MigrationMode {
         <MigrationMode as strum::VariantNames>::VARIANTS
     }
 
+    pub fn is_dry_run(&self) -> bool {
+        matches!(self, Self::DryRun)
+    }
+
     pub fn is_run(&self) -> bool {
         matches!(self, Self::Run)
     }
@@ -291,6 +309,7 @@ mod tests {
 
         #[test]
         fn display() {
+            assert_eq!("dryRun", MigrationMode::DryRun.to_string());
             assert_eq!("run", MigrationMode::Run.to_string());
             assert_eq!("runAndQuit", MigrationMode::RunAndQuit.to_string());
             assert_eq!("skip", MigrationMode::Skip.to_string());
@@ -298,6 +317,10 @@ mod tests {
 
         #[test]
         fn from_str() {
+            assert_eq!(
+                MigrationMode::DryRun,
+                "dryRun".parse().expect("failed to parse")
+            );
             assert_eq!(MigrationMode::Run, "run".parse().expect("failed to parse"));
             assert_eq!(
                 MigrationMode::RunAndQuit,
@@ -0,0 +1,2 @@
+ALTER TABLE workspaces
+    ADD COLUMN deleted_at timestamp with time zone NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE change_set_pointers
+    ADD COLUMN last_activity_at timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP();
+
+UPDATE change_set_pointers SET last_activity_at = updated_at;
@@ -18,7 +18,7 @@ use crate::change_set::ChangeSetError;
 use crate::func::argument::{FuncArgument, FuncArgumentError};
 use crate::func::intrinsics::IntrinsicFunc;
 use crate::func::FuncError;
-use crate::layer_db_types::{PropContent, PropContentDiscriminants, PropContentV1};
+use crate::layer_db_types::{PropContent, PropContentDiscriminants, PropContentV3};
 use crate::workspace_snapshot::content_address::{ContentAddress, ContentAddressDiscriminants};
 use crate::workspace_snapshot::edge_weight::EdgeWeightKind;
 use crate::workspace_snapshot::edge_weight::EdgeWeightKindDiscriminants;
@@ -32,11 +32,13 @@ use crate::{
 };
 use crate::{AttributeValueId, InputSocketId};
 
-pub const PROP_VERSION: PropContentDiscriminants = PropContentDiscriminants::V1;
+pub const PROP_VERSION: PropContentDiscriminants = PropContentDiscriminants::V3;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum PropError {
+    #[error("child props of {0:?} matching name {1} ignoring case are ambiguous: {2:?}")]
+    AmbiguousChildPropName(NodeIndex, String, Vec<String>),
     #[error("array missing child element: {0}")]
     ArrayMissingChildElement(PropId),
     #[error("attribute prototype error: {0}")]
@@ -55,6 +57,10 @@ pub enum PropError {
     FuncArgument(#[from] FuncArgumentError),
     #[error("helper error: {0}")]
     Helper(#[from] HelperError),
+    #[error("invalid prop name (must not contain the prop path separator): {0:?}")]
+    InvalidPropName(String),
+    #[error("json prop {0} cannot have child props")]
+    JsonPropCannotHaveChildren(PropId),
     #[error("layer db error: {0}")]
     LayerDb(#[from] si_layer_cache::LayerDbError),
     #[error("map or array {0} missing element prop")]
@@ -91,16 +97,119 @@ pub const SECRET_KIND_WIDGET_OPTION_LABEL: &str = "secretKind";
 
 pub use si_id::PropId;
 
-// TODO: currently we only have string values in all widget_options but we should extend this to
-// support other types. However, we cannot use serde_json::Value since postcard will not
-// deserialize into a serde_json::Value.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct WidgetOption {
     label: String,
-    pub value: String,
+    pub value: WidgetOptionValue,
 }
 pub type WidgetOptions = Vec<WidgetOption>;
 
+impl WidgetOption {
+    pub fn new(label: impl Into<String>, value: impl Into<WidgetOptionValue>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the value rendered as a string, which is what all widget option values used to be.
+    pub fn value_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// The value of a [`WidgetOption`]. We cannot use [`serde_json::Value`] since postcard will not
+/// deserialize into a [`serde_json::Value`], so the supported types are enumerated instead.
+///
+/// Human-readable formats (like JSON) see a bare string, integer or boolean, whereas other formats
+/// (like postcard) see the variant tag, since they cannot deserialize untagged enums.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WidgetOptionValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl std::fmt::Display for WidgetOptionValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boolean(value) => value.fmt(f),
+            Self::Integer(value) => value.fmt(f),
+            Self::String(value) => value.fmt(f),
+        }
+    }
+}
+
+impl From<String> for WidgetOptionValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for WidgetOptionValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<i64> for WidgetOptionValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for WidgetOptionValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "WidgetOptionValue")]
+enum TaggedWidgetOptionValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "WidgetOptionValue", untagged)]
+enum UntaggedWidgetOptionValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl Serialize for WidgetOptionValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            UntaggedWidgetOptionValue::serialize(self, serializer)
+        } else {
+            TaggedWidgetOptionValue::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WidgetOptionValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            UntaggedWidgetOptionValue::deserialize(deserializer)
+        } else {
+            TaggedWidgetOptionValue::deserialize(deserializer)
+        }
+    }
+}
+
 /// An individual "field" within the tree of a [`SchemaVariant`](crate::SchemaVariant).
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Prop {
@@ -128,11 +237,13 @@ pub struct Prop {
     pub diff_func_id: Option<FuncId>,
     /// A serialized validation format JSON object for the prop.
     pub validation_format: Option<String>,
+    /// Whether the [`Prop`] must be given a value. This is distinct from having a default value.
+    pub required: bool,
     /// Indicates whether this prop is a valid input for a function
     pub can_be_used_as_prototype_arg: bool,
 }
 
-impl From<Prop> for PropContentV1 {
+impl From<Prop> for PropContentV3 {
     fn from(value: Prop) -> Self {
         Self {
             timestamp: value.timestamp,
@@ -146,12 +257,13 @@ impl From<Prop> for PropContentV1 {
             refers_to_prop_id: value.refers_to_prop_id,
             diff_func_id: value.diff_func_id,
             validation_format: value.validation_format,
+            required: value.required,
         }
     }
 }
 
-/// This is the separator used for the "path" column. It is a vertical tab character, which should
-/// not (we'll see) be able to be provided by our users in [`Prop`] names.
+/// This is the separator used for the "path" column. It is a vertical tab character, which must
+/// not appear in [`Prop`] names (enforced on creation with [`PropError::InvalidPropName`]).
 pub const PROP_PATH_SEPARATOR: &str = "\x0B";
 
 /// This type should be used to manage prop paths instead of a raw string
@@ -262,6 +374,10 @@ pub enum PropKind {
     Array,
     Boolean,
     Integer,
+    /// A leaf that holds an arbitrary JSON value (object, array or scalar) as a single value. It is
+    /// not a container: it never has child props, and whatever it holds is opaque to the graph.
+    /// It may be used as the element of an array or map, in which case each entry is an arbitrary
+    /// JSON value.
     Json,
     Map,
     Object,
@@ -291,6 +407,8 @@ impl PropKind {
         self.is_container()
     }
 
+    /// Returns the value a [`Prop`] of this kind starts with, if any. [`PropKind::Json`] starts as
+    /// an empty object even though it is not a container.
     pub fn empty_value(&self) -> Option<serde_json::Value> {
         match self {
             Self::Array => Some(serde_json::json!([])),
@@ -351,26 +469,43 @@ impl From<PropKind> for FuncBackendResponseType {
 
 impl Prop {
     pub async fn into_frontend_type(self, ctx: &DalContext) -> PropResult<si_frontend_types::Prop> {
-        let path = self.path(ctx).await?.with_replaced_sep_and_prefix("/");
-        Ok(si_frontend_types::Prop {
+        let path = self.path(ctx).await?;
+        Ok(self.into_frontend_type_with_path(path))
+    }
+
+    /// Like [`Self::into_frontend_type`], but uses the provided [`PropPath`] rather than walking
+    /// to the root to compute it. Useful when the caller already knows the path, such as when
+    /// rendering a whole prop tree.
+    pub fn into_frontend_type_with_path(self, path: PropPath) -> si_frontend_types::Prop {
+        let path = path.with_replaced_sep_and_prefix("/");
+        si_frontend_types::Prop {
             id: self.id(),
             kind: self.kind.into(),
             name: self.name.to_owned(),
             path: path.to_owned(),
             hidden: self.hidden,
-            eligible_to_receive_data: {
-                // props can receive data if they're on a certain part of the prop tree
-                // or if they're not a child of an array/map (for now?)
-                let eligible_by_path = path == "/root/resource_value"
-                    || path == "/root/si/color"
-                    || path.starts_with("/root/domain/")
-                    || path.starts_with("/root/resource_value/");
-                eligible_by_path && self.can_be_used_as_prototype_arg
-            },
+            required: self.required,
+            eligible_to_receive_data: Self::is_eligible_to_receive_data(
+                &path,
+                self.can_be_used_as_prototype_arg,
+            ),
             eligible_to_send_data: self.can_be_used_as_prototype_arg,
-        })
+        }
+    }
+
+    /// Determines if a [`Prop`] at the given path (using "/" as the separator, including the
+    /// leading "/") is eligible to receive data.
+    pub fn is_eligible_to_receive_data(path: &str, can_be_used_as_prototype_arg: bool) -> bool {
+        // props can receive data if they're on a certain part of the prop tree
+        // or if they're not a child of an array/map (for now?)
+        let eligible_by_path = path == "/root/resource_value"
+            || path == "/root/si/color"
+            || path.starts_with("/root/domain/")
+            || path.starts_with("/root/resource_value/");
+        eligible_by_path && can_be_used_as_prototype_arg
     }
-    pub fn assemble(prop_node_weight: PropNodeWeight, inner: PropContentV1) -> Self {
+
+    pub fn assemble(prop_node_weight: PropNodeWeight, inner: PropContentV3) -> Self {
         Self {
             id: prop_node_weight.id().into(),
             timestamp: inner.timestamp,
@@ -384,6 +519,7 @@ impl Prop {
             refers_to_prop_id: inner.refers_to_prop_id,
             diff_func_id: inner.diff_func_id,
             validation_format: inner.validation_format,
+            required: inner.required,
             can_be_used_as_prototype_arg: prop_node_weight.can_be_used_as_prototype_arg(),
         }
     }
@@ -424,6 +560,16 @@ impl Prop {
         validation_format: Option<String>,
         parent_prop_id: PropId,
     ) -> PropResult<Self> {
+        let parent_kind = ctx
+            .workspace_snapshot()?
+            .get_node_weight_by_id(parent_prop_id)
+            .await?
+            .get_prop_node_weight()?
+            .kind();
+        if parent_kind == PropKind::Json {
+            return Err(PropError::JsonPropCannotHaveChildren(parent_prop_id));
+        }
+
         let prop = Self::new_inner(
             ctx,
             name,
@@ -492,6 +638,9 @@ impl Prop {
     ) -> PropResult<Self> {
         let ordered = kind.ordered();
         let name = name.into();
+        if name.contains(PROP_PATH_SEPARATOR) {
+            return Err(PropError::InvalidPropName(name));
+        }
 
         let timestamp = Timestamp::now();
         let (widget_kind, widget_options): (WidgetKind, Option<WidgetOptions>) =
@@ -506,7 +655,7 @@ impl Prop {
                 None => (WidgetKind::from(kind), None),
             };
 
-        let content = PropContentV1 {
+        let content = PropContentV3 {
             timestamp,
             name: name.clone(),
             kind,
@@ -518,10 +667,11 @@ impl Prop {
             refers_to_prop_id: None,
             diff_func_id: None,
             validation_format,
+            required: false,
         };
 
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(PropContent::V1(content.clone()).into()),
+            Arc::new(PropContent::V3(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -557,6 +707,17 @@ impl Prop {
             .cloned()
     }
 
+    /// Returns the `(label, value)` pairs from the [`WidgetOptions`] for rendering choices, such as
+    /// those of a [`WidgetKind::Select`]. The reserved secret kind option is excluded.
+    pub fn select_options(&self) -> Vec<(String, String)> {
+        self.widget_options
+            .iter()
+            .flatten()
+            .filter(|opt| opt.label != SECRET_KIND_WIDGET_OPTION_LABEL)
+            .map(|opt| (opt.label.clone(), opt.value_string()))
+            .collect()
+    }
+
     /// Returns `Some` with the parent [`PropId`](Prop) or returns `None` if the parent is a
     /// [`SchemaVariant`].
     pub async fn parent_prop_id_by_id(
@@ -713,25 +874,41 @@ impl Prop {
             .get_prop_node_weight()?;
         let hash = node_weight.content_hash();
 
-        let content: PropContent = ctx
-            .layer_db()
-            .cas()
-            .try_read_as(&hash)
-            .await?
-            .ok_or(WorkspaceSnapshotError::MissingContentFromStore(ulid))?;
-
-        // NOTE(nick,jacob,zack): if we had a v2, then there would be migration logic here.
-        let PropContent::V1(inner) = content;
+        let content: PropContent = ctx.layer_db().cas().try_read_as(&hash).await?.ok_or(
+            WorkspaceSnapshotError::MissingContentFromStoreForHash(
+                ulid,
+                ContentAddressDiscriminants::Prop,
+                hash,
+            ),
+        )?;
 
-        Ok(Self::assemble(node_weight, inner))
+        Ok(Self::assemble(node_weight, content.extract()))
     }
 
+    /// Returns the element [`PropId`](Prop) of an array or map [`Prop`]. The element itself may
+    /// be of any kind, including [`PropKind::Json`].
     pub async fn element_prop_id(ctx: &DalContext, prop_id: PropId) -> PropResult<PropId> {
-        Self::direct_child_prop_ids_unordered(ctx, prop_id)
+        if let Some(element_prop_id) = Self::direct_child_prop_ids_unordered(ctx, prop_id)
             .await?
             .first()
             .copied()
-            .ok_or(PropError::MapOrArrayMissingElementProp(prop_id))
+        {
+            return Ok(element_prop_id);
+        }
+
+        // Only look up the kind when there is no element, to explain why.
+        let kind = ctx
+            .workspace_snapshot()?
+            .get_node_weight_by_id(prop_id)
+            .await?
+            .get_prop_node_weight()?
+            .kind();
+        match kind {
+            PropKind::Array | PropKind::Map => {
+                Err(PropError::MapOrArrayMissingElementProp(prop_id))
+            }
+            kind => Err(PropError::ElementPropNotOnKind(prop_id, kind)),
+        }
     }
 
     pub async fn find_child_prop_index_by_name(
@@ -763,6 +940,52 @@ impl Prop {
         ))
     }
 
+    /// Like [`Self::find_child_prop_index_by_name`], but matches the child name ignoring case.
+    /// Returns an error if more than one child matches.
+    pub async fn find_child_prop_index_by_name_ci(
+        ctx: &DalContext,
+        node_index: NodeIndex,
+        child_name: impl AsRef<str>,
+    ) -> PropResult<NodeIndex> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+        let child_name = child_name.as_ref();
+        let lowercase_child_name = child_name.to_lowercase();
+
+        let mut matches = vec![];
+        for prop_node_index in workspace_snapshot
+            .outgoing_targets_for_edge_weight_kind_by_index(
+                node_index,
+                EdgeWeightKindDiscriminants::Use,
+            )
+            .await?
+        {
+            if let NodeWeight::Prop(prop_inner) =
+                workspace_snapshot.get_node_weight(prop_node_index).await?
+            {
+                if prop_inner.name().to_lowercase() == lowercase_child_name {
+                    matches.push((prop_node_index, prop_inner.name().to_owned()));
+                }
+            }
+        }
+
+        match matches.len() {
+            0 => Err(PropError::ChildPropNotFoundByName(
+                node_index,
+                child_name.to_string(),
+            )),
+            1 => Ok(matches[0].0),
+            _ => {
+                let mut names: Vec<String> = matches.into_iter().map(|(_, name)| name).collect();
+                names.sort();
+                Err(PropError::AmbiguousChildPropName(
+                    node_index,
+                    child_name.to_string(),
+                    names,
+                ))
+            }
+        }
+    }
+
     /// Find the `SchemaVariantId`` for a given prop. If the prop tree is
     /// orphaned, we just return `None`
     pub async fn schema_variant_id(
@@ -848,6 +1071,31 @@ impl Prop {
             .into())
     }
 
+    /// Like [`Self::find_prop_id_by_path`], but matches each path segment ignoring case. This is
+    /// useful for paths from sources with inconsistent casing. Returns an error if a segment
+    /// matches more than one child.
+    pub async fn find_prop_id_by_path_ci(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        path: &PropPath,
+    ) -> PropResult<PropId> {
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let mut current_node_index = workspace_snapshot
+            .get_node_index_by_id(schema_variant_id)
+            .await?;
+        for part in path.as_parts() {
+            current_node_index =
+                Self::find_child_prop_index_by_name_ci(ctx, current_node_index, part).await?;
+        }
+
+        Ok(workspace_snapshot
+            .get_node_weight(current_node_index)
+            .await?
+            .id()
+            .into())
+    }
+
     pub async fn find_prop_by_path(
         ctx: &DalContext,
         schema_variant_id: SchemaVariantId,
@@ -1016,14 +1264,10 @@ impl Prop {
     pub async fn list_content(ctx: &DalContext, prop_ids: Vec<PropId>) -> PropResult<Vec<Self>> {
         let workspace_snapshot = ctx.workspace_snapshot()?;
 
-        let mut node_weights = vec![];
-        let mut content_hashes = vec![];
-        for prop_id in prop_ids {
-            let prop_node_index = workspace_snapshot.get_node_index_by_id(prop_id).await?;
-            let node_weight = workspace_snapshot
-                .get_node_weight(prop_node_index)
-                .await?
-                .get_prop_node_weight()?;
+        let mut node_weights = Vec::with_capacity(prop_ids.len());
+        let mut content_hashes = Vec::with_capacity(prop_ids.len());
+        for node_weight in workspace_snapshot.get_node_weights_by_ids(prop_ids).await? {
+            let node_weight = node_weight.get_prop_node_weight()?;
             content_hashes.push(node_weight.content_hash());
             node_weights.push(node_weight);
         }
@@ -1038,13 +1282,12 @@ impl Prop {
         for node_weight in node_weights {
             match content_map.get(&node_weight.content_hash()) {
                 Some(content) => {
-                    // NOTE(nick,jacob,zack): if we had a v2, then there would be migration logic here.
-                    let PropContent::V1(inner) = content;
-
-                    props.push(Self::assemble(node_weight, inner.to_owned()));
+                    props.push(Self::assemble(node_weight, content.to_owned().extract()));
                 }
-                None => Err(WorkspaceSnapshotError::MissingContentFromStore(
+                None => Err(WorkspaceSnapshotError::MissingContentFromStoreForHash(
                     node_weight.id(),
+                    ContentAddressDiscriminants::Prop,
+                    node_weight.content_hash(),
                 ))?,
             }
         }
@@ -1057,13 +1300,13 @@ impl Prop {
     {
         let mut prop = self;
 
-        let before = PropContentV1::from(prop.clone());
+        let before = PropContentV3::from(prop.clone());
         lambda(&mut prop)?;
-        let updated = PropContentV1::from(prop.clone());
+        let updated = PropContentV3::from(prop.clone());
 
         if updated != before {
             let (hash, _) = ctx.layer_db().cas().write(
-                Arc::new(PropContent::V1(updated.clone()).into()),
+                Arc::new(PropContent::V3(updated.clone()).into()),
                 None,
                 ctx.events_tenancy(),
                 ctx.events_actor(),
@@ -1103,6 +1346,123 @@ impl Prop {
         Ok(ordered_child_props)
     }
 
+    /// Walks the subtree below the given [`Prop`] depth first, following the order of each
+    /// [`Prop`]'s children, and returns every descendant with its full [`PropPath`]. The given
+    /// [`Prop`] itself is not included.
+    pub async fn walk_subtree_ordered(
+        ctx: &DalContext,
+        prop_id: PropId,
+    ) -> PropResult<Vec<(PropPath, PropId)>> {
+        let mut descendants = vec![];
+        let mut work_stack = vec![(Self::path_by_id(ctx, prop_id).await?, prop_id)];
+
+        while let Some((path, id)) = work_stack.pop() {
+            // Push in reverse so that the first child is the next one popped.
+            for child in Self::direct_child_props_ordered(ctx, id)
+                .await?
+                .into_iter()
+                .rev()
+            {
+                work_stack.push((path.join(&PropPath::new([&child.name])), child.id));
+            }
+
+            if id != prop_id {
+                descendants.push((path, id));
+            }
+        }
+
+        Ok(descendants)
+    }
+
+    /// Finds every [`Prop`] of the given [`PropKind`] in the [`SchemaVariant`]'s prop tree
+    /// (including the root [`Prop`]). Results are in depth first order, following the order of
+    /// each [`Prop`]'s children, so they are deterministic.
+    pub async fn find_by_kind(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        kind: PropKind,
+    ) -> PropResult<Vec<PropId>> {
+        let root_prop_id =
+            Self::find_prop_id_by_path(ctx, schema_variant_id, &PropPath::new(["root"])).await?;
+        let workspace_snapshot = ctx.workspace_snapshot()?;
+
+        let mut matching_prop_ids = vec![];
+        for prop_id in std::iter::once(root_prop_id).chain(
+            Self::walk_subtree_ordered(ctx, root_prop_id)
+                .await?
+                .into_iter()
+                .map(|(_, prop_id)| prop_id),
+        ) {
+            let prop_kind = workspace_snapshot
+                .get_node_weight_by_id(prop_id)
+                .await?
+                .get_prop_node_weight()?
+                .kind();
+            if prop_kind == kind {
+                matching_prop_ids.push(prop_id);
+            }
+        }
+
+        Ok(matching_prop_ids)
+    }
+
+    /// Returns the [`Props`](Prop) in the same prop tree whose
+    /// [`refers_to_prop_id`](Self::refers_to_prop_id) is the given [`Prop`] (the reverse of that
+    /// link). The link is only stored in the content of the referring [`Prop`], so this loads
+    /// every [`Prop`] in the tree.
+    pub async fn referrers(ctx: &DalContext, prop_id: PropId) -> PropResult<Vec<PropId>> {
+        let root_prop_id = Self::root_prop_for_prop_id(ctx, prop_id).await?;
+        let mut candidate_ids = vec![root_prop_id];
+        candidate_ids.extend(
+            Self::walk_subtree_ordered(ctx, root_prop_id)
+                .await?
+                .into_iter()
+                .map(|(_, id)| id),
+        );
+
+        Ok(Self::list_content(ctx, candidate_ids)
+            .await?
+            .into_iter()
+            .filter(|prop| prop.refers_to_prop_id == Some(prop_id))
+            .map(|prop| prop.id)
+            .collect())
+    }
+
+    /// Returns the [`doc_link`](Self::doc_link) of this [`Prop`] or, if it has none, that of its
+    /// nearest ancestor with one. The stored field is left untouched.
+    pub async fn effective_doc_link(&self, ctx: &DalContext) -> PropResult<Option<String>> {
+        self.nearest_ancestor_value(ctx, |prop| prop.doc_link.clone())
+            .await
+    }
+
+    /// Returns the [`documentation`](Self::documentation) of this [`Prop`] or, if it has none,
+    /// that of its nearest ancestor with some. The stored field is left untouched.
+    pub async fn effective_documentation(&self, ctx: &DalContext) -> PropResult<Option<String>> {
+        self.nearest_ancestor_value(ctx, |prop| prop.documentation.clone())
+            .await
+    }
+
+    async fn nearest_ancestor_value(
+        &self,
+        ctx: &DalContext,
+        value: impl Fn(&Prop) -> Option<String>,
+    ) -> PropResult<Option<String>> {
+        if let Some(own_value) = value(self) {
+            return Ok(Some(own_value));
+        }
+
+        let mut maybe_parent_id = Self::parent_prop_id_by_id(ctx, self.id).await?;
+        while let Some(parent_id) = maybe_parent_id {
+            let parent = Self::get_by_id(ctx, parent_id).await?;
+            if let Some(parent_value) = value(&parent) {
+                return Ok(Some(parent_value));
+            }
+            maybe_parent_id = Self::parent_prop_id_by_id(ctx, parent_id).await?;
+        }
+
+        Ok(None)
+    }
+
     pub async fn find_equivalent_in_schema_variant(
         ctx: &DalContext,
         prop_id: PropId,
@@ -1129,6 +1489,8 @@ impl Prop {
             PropKind::Boolean => "boolean".to_string(),
             PropKind::Integer => "number".to_string(),
             PropKind::String => "string".to_string(),
+            // Arrays and maps of json props render as "any[]" and "Record<string, any>".
+            PropKind::Json => "any".to_string(),
             PropKind::Array => {
                 let element_prop_id = Self::element_prop_id(ctx, self.id).await?;
                 let element_prop = Self::get_by_id(ctx, element_prop_id).await?;
@@ -1157,7 +1519,194 @@ impl Prop {
 
                 object_type
             }
-            _ => "".to_string(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_db_types::{PropContentV1, WidgetOptionV1};
+
+    #[test]
+    fn eligible_to_receive_data_resource_value() {
+        assert!(Prop::is_eligible_to_receive_data(
+            "/root/resource_value",
+            true
+        ));
+    }
+
+    #[test]
+    fn eligible_to_receive_data_resource_value_child() {
+        assert!(Prop::is_eligible_to_receive_data(
+            "/root/resource_value/status",
+            true
+        ));
+    }
+
+    #[test]
+    fn eligible_to_receive_data_color() {
+        assert!(Prop::is_eligible_to_receive_data("/root/si/color", true));
+    }
+
+    #[test]
+    fn eligible_to_receive_data_domain_child() {
+        assert!(Prop::is_eligible_to_receive_data("/root/domain/name", true));
+    }
+
+    #[test]
+    fn ineligible_to_receive_data_by_path() {
+        assert!(!Prop::is_eligible_to_receive_data("/root/si/name", true));
+        assert!(!Prop::is_eligible_to_receive_data("/root/domain", true));
+        assert!(!Prop::is_eligible_to_receive_data(
+            "/root/domainish/name",
+            true
+        ));
+    }
+
+    #[test]
+    fn ineligible_to_receive_data_when_not_usable_as_prototype_arg() {
+        assert!(!Prop::is_eligible_to_receive_data(
+            "/root/domain/name",
+            false
+        ));
+        assert!(!Prop::is_eligible_to_receive_data("/root/si/color", false));
+    }
+
+    fn prop_with_widget_options(widget_options: Option<WidgetOptions>) -> Prop {
+        Prop {
+            id: PropId::generate(),
+            timestamp: Timestamp::now(),
+            name: "prop".to_string(),
+            kind: PropKind::String,
+            widget_kind: WidgetKind::Select,
+            widget_options,
+            doc_link: None,
+            documentation: None,
+            hidden: false,
+            refers_to_prop_id: None,
+            diff_func_id: None,
+            validation_format: None,
+            required: false,
+            can_be_used_as_prototype_arg: false,
+        }
+    }
+
+    fn widget_option(label: &str, value: &str) -> WidgetOption {
+        WidgetOption::new(label, value)
+    }
+
+    #[test]
+    fn select_options_lists_label_value_pairs() {
+        let prop = prop_with_widget_options(Some(vec![
+            widget_option("Small", "t3.small"),
+            widget_option(SECRET_KIND_WIDGET_OPTION_LABEL, "AWS Credential"),
+            widget_option("Large", "t3.large"),
+        ]));
+
+        assert_eq!(
+            vec![
+                ("Small".to_string(), "t3.small".to_string()),
+                ("Large".to_string(), "t3.large".to_string()),
+            ],
+            prop.select_options()
+        );
+    }
+
+    #[test]
+    fn select_options_excludes_secret_kind() {
+        let prop = prop_with_widget_options(Some(vec![widget_option(
+            SECRET_KIND_WIDGET_OPTION_LABEL,
+            "AWS Credential",
+        )]));
+
+        assert!(prop.select_options().is_empty());
+        assert!(prop_with_widget_options(None).select_options().is_empty());
+    }
+
+    #[test]
+    fn widget_option_value_postcard_round_trip() {
+        for value in [
+            WidgetOptionValue::Boolean(true),
+            WidgetOptionValue::Integer(-42),
+            WidgetOptionValue::String("AWS Credential".to_string()),
+        ] {
+            let option = WidgetOption::new("label", value);
+            let bytes = postcard::to_stdvec(&option).expect("could not serialize");
+            let round_tripped: WidgetOption =
+                postcard::from_bytes(&bytes).expect("could not deserialize");
+            assert_eq!(option, round_tripped);
+        }
+    }
+
+    #[test]
+    fn widget_option_value_json_is_untagged() {
+        let options: WidgetOptions = serde_json::from_value(serde_json::json!([
+            { "label": "enabled", "value": false },
+            { "label": "count", "value": 3 },
+            { "label": SECRET_KIND_WIDGET_OPTION_LABEL, "value": "AWS Credential" },
+        ]))
+        .expect("could not deserialize");
+
+        assert_eq!(
+            vec![
+                WidgetOption::new("enabled", false),
+                WidgetOption::new("count", 3i64),
+                WidgetOption::new(SECRET_KIND_WIDGET_OPTION_LABEL, "AWS Credential"),
+            ],
+            options
+        );
+        assert_eq!(
+            serde_json::json!({ "label": "count", "value": 3 }),
+            serde_json::to_value(&options[1]).expect("could not serialize")
+        );
+        assert_eq!("3", options[1].value_string());
+    }
+
+    #[test]
+    fn prop_content_v1_widget_options_are_migrated() {
+        let v1 = PropContentV1 {
+            timestamp: Timestamp::now(),
+            name: "prop".to_string(),
+            kind: PropKind::String,
+            widget_kind: WidgetKind::Secret,
+            widget_options: Some(vec![WidgetOptionV1 {
+                label: SECRET_KIND_WIDGET_OPTION_LABEL.to_string(),
+                value: "AWS Credential".to_string(),
+            }]),
+            doc_link: None,
+            documentation: None,
+            hidden: false,
+            refers_to_prop_id: None,
+            diff_func_id: None,
+            validation_format: None,
+        };
+
+        assert_eq!(
+            Some(vec![WidgetOption::new(
+                SECRET_KIND_WIDGET_OPTION_LABEL,
+                "AWS Credential"
+            )]),
+            PropContent::V1(v1).extract().widget_options
+        );
+    }
+
+    #[test]
+    fn prop_content_v1_is_not_required() {
+        let v1 = PropContentV1 {
+            timestamp: Timestamp::now(),
+            name: "prop".to_string(),
+            kind: PropKind::String,
+            widget_kind: WidgetKind::Text,
+            widget_options: None,
+            doc_link: None,
+            documentation: None,
+            hidden: false,
+            refers_to_prop_id: None,
+            diff_func_id: None,
+            validation_format: None,
+        };
+
+        assert!(!PropContent::V1(v1).extract().required);
+    }
+}
@@ -2,4 +2,5 @@ SELECT row_to_json(w.*) AS object
 FROM workspaces AS w
 INNER JOIN user_belongs_to_workspaces bt ON bt.workspace_pk = w.pk
 WHERE bt.user_pk = $1
+  AND w.deleted_at IS NULL
 ORDER BY w.created_at ASC
@@ -1727,12 +1727,9 @@ impl SchemaVariant {
                 WorkspaceSnapshotError::MissingContentFromStore(output_socket_id.into()),
             )?;
 
-            // NOTE(nick,jacob,zack): if we had a v2, then there would be migration logic here.
-            let OutputSocketContent::V1(output_socket_content_inner) = output_socket_content;
-
             output_sockets.push(OutputSocket::assemble(
                 output_socket_id,
-                output_socket_content_inner.to_owned(),
+                output_socket_content.to_owned().extract(),
             ));
         }
 
@@ -1759,7 +1756,8 @@ impl SchemaVariant {
                     )
                     .into());
                 }
-                InputSocketContent::V2(content_inner) => content_inner,
+                InputSocketContent::V2(content_inner) => content_inner.to_owned().into(),
+                InputSocketContent::V3(content_inner) => content_inner.to_owned(),
             };
 
             input_sockets.push(InputSocket::assemble(
@@ -1768,7 +1766,7 @@ impl SchemaVariant {
                     &input_socket_weight,
                 )
                 .arity(),
-                input_socket_content_inner.to_owned(),
+                input_socket_content_inner,
             ));
         }
 
@@ -218,7 +218,7 @@ impl Secret {
         algorithm: SecretAlgorithm,
     ) -> SecretResult<Self> {
         let user = match ctx.history_actor() {
-            HistoryActor::SystemInit => None,
+            HistoryActor::Machine(_) | HistoryActor::SystemInit => None,
             HistoryActor::User(user_pk) => Some(*user_pk),
         };
 
@@ -705,7 +705,7 @@ impl Secret {
         lambda(&mut secret)?;
         if before != SecretContentV1::from(secret.clone()) {
             match ctx.history_actor() {
-                HistoryActor::SystemInit => {}
+                HistoryActor::Machine(_) | HistoryActor::SystemInit => {}
                 HistoryActor::User(id) => {
                     secret.updated_by = Some(*id);
                 }
@@ -785,7 +785,7 @@ impl EncryptedSecret {
         algorithm: SecretAlgorithm,
     ) -> SecretResult<()> {
         let user = match ctx.history_actor() {
-            HistoryActor::SystemInit => None,
+            HistoryActor::Machine(_) | HistoryActor::SystemInit => None,
             HistoryActor::User(user_pk) => Some(*user_pk),
         };
 
@@ -1,6 +1,7 @@
 //! Sockets are the mechanisms to pass and transform data between attributes.
 
 use serde::{Deserialize, Serialize};
+use si_frontend_types::DiagramSocketNodeSide;
 use si_pkg::SocketSpecArity;
 use strum::{AsRefStr, Display, EnumIter, EnumString};
 
@@ -9,7 +10,8 @@ pub mod debug;
 pub mod input;
 pub mod output;
 
-#[remain::sorted]
+/// The order of these variants is part of the serialized socket content, so new variants must
+/// be added at the end rather than sorted.
 #[derive(
     AsRefStr,
     Clone,
@@ -28,6 +30,14 @@ pub mod output;
 pub enum SocketKind {
     Frame,
     Standard,
+    /// A socket that can be used as both an input and an output on the diagram
+    Bidirectional,
+}
+
+impl SocketKind {
+    pub fn is_bidirectional(&self) -> bool {
+        matches!(self, Self::Bidirectional)
+    }
 }
 
 #[remain::sorted]
@@ -51,6 +61,37 @@ pub enum SocketArity {
     One,
 }
 
+/// The side of a node that a [`bidirectional`](SocketKind::Bidirectional) socket is drawn on.
+#[remain::sorted]
+#[derive(
+    AsRefStr,
+    Copy,
+    Clone,
+    Debug,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum SocketNodeSide {
+    Left,
+    Right,
+}
+
+impl From<SocketNodeSide> for DiagramSocketNodeSide {
+    fn from(value: SocketNodeSide) -> Self {
+        match value {
+            SocketNodeSide::Left => Self::Left,
+            SocketNodeSide::Right => Self::Right,
+        }
+    }
+}
+
 impl From<&SocketArity> for SocketSpecArity {
     fn from(value: &SocketArity) -> Self {
         match value {
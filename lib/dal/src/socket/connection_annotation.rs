@@ -65,25 +65,7 @@ impl TryFrom<String> for ConnectionAnnotation {
 
 impl ConnectionAnnotation {
     pub fn target_fits_reference(target_ca: &Self, reference_ca: &Self) -> bool {
-        let annotation_src = &target_ca.tokens;
-        let annotation_dest = &reference_ca.tokens;
-
-        if annotation_dest.len() > annotation_src.len() {
-            return false;
-        }
-
-        let annotation_dest_last_index = annotation_dest.len() - 1;
-        let annotation_src_last_index = annotation_src.len() - 1;
-
-        for i in 0..annotation_dest.len() {
-            if annotation_dest[annotation_dest_last_index - i].to_lowercase()
-                != annotation_src[annotation_src_last_index - i].to_lowercase()
-            {
-                return false;
-            }
-        }
-
-        true
+        FeConnectionAnnotation::tokens_fit(&target_ca.tokens, &reference_ca.tokens)
     }
 
     pub fn from_tokens_array(tokens: Vec<String>) -> Self {
@@ -156,3 +138,24 @@ fn connection_annotation_fits() {
         )
     }
 }
+
+#[test]
+fn frontend_connection_annotation_is_compatible_with() {
+    let output: FeConnectionAnnotation =
+        ConnectionAnnotation::try_from("User ARN<arn<string>>".to_string())
+            .expect("parse output annotation")
+            .into();
+
+    for (raw_input, result) in [
+        ("user arn<arn<string>>", true),
+        ("ARN<String>", true),
+        ("string", true),
+        ("arn", false),
+        ("role_arn<arn<string>>", false),
+    ] {
+        let input: FeConnectionAnnotation = ConnectionAnnotation::try_from(raw_input.to_string())
+            .expect("parse input annotation")
+            .into();
+        assert_eq!(output.is_compatible_with(&input), result, "{raw_input}");
+    }
+}
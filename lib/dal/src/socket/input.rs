@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 use si_frontend_types as frontend_types;
 use si_layer_cache::LayerDbError;
@@ -12,12 +14,12 @@ use crate::{
     change_set::ChangeSetError,
     func::FuncError,
     implement_add_edge_to,
-    layer_db_types::InputSocketContentV2,
+    layer_db_types::{InputSocketContent, InputSocketContentV3},
     socket::{
         connection_annotation::{ConnectionAnnotation, ConnectionAnnotationError},
         output::OutputSocketError,
     },
-    socket::{SocketArity, SocketKind},
+    socket::{SocketArity, SocketKind, SocketNodeSide},
     workspace_snapshot::{
         edge_weight::EdgeWeightKindDiscriminants, node_weight::NodeWeightError, InputSocketExt,
         WorkspaceSnapshotError,
@@ -93,6 +95,7 @@ pub struct InputSocket {
     required: bool,
     ui_hidden: bool,
     connection_annotations: Vec<ConnectionAnnotation>,
+    node_side: Option<SocketNodeSide>,
 }
 
 impl InputSocket {
@@ -103,7 +106,7 @@ impl InputSocket {
             .map_err(Into::into)
     }
 
-    pub fn assemble(id: InputSocketId, arity: SocketArity, inner: InputSocketContentV2) -> Self {
+    pub fn assemble(id: InputSocketId, arity: SocketArity, inner: InputSocketContentV3) -> Self {
         Self {
             id,
             timestamp: inner.timestamp,
@@ -115,6 +118,7 @@ impl InputSocket {
             required: inner.required,
             ui_hidden: inner.ui_hidden,
             connection_annotations: inner.connection_annotations,
+            node_side: inner.node_side,
         }
     }
     pub fn id(&self) -> InputSocketId {
@@ -133,6 +137,10 @@ impl InputSocket {
         self.ui_hidden
     }
 
+    pub fn kind(&self) -> SocketKind {
+        self.kind
+    }
+
     pub fn required(&self) -> bool {
         self.required
     }
@@ -141,6 +149,48 @@ impl InputSocket {
         self.connection_annotations.clone()
     }
 
+    /// The side of the node this socket is drawn on, if it has been set. Only used for
+    /// [`bidirectional`](SocketKind::Bidirectional) sockets.
+    pub fn node_side(&self) -> Option<SocketNodeSide> {
+        self.node_side
+    }
+
+    /// Sets the side of the node this socket is drawn on.
+    pub async fn set_node_side(
+        mut self,
+        ctx: &DalContext,
+        node_side: Option<SocketNodeSide>,
+    ) -> InputSocketResult<Self> {
+        if self.node_side == node_side {
+            return Ok(self);
+        }
+        self.node_side = node_side;
+
+        let content = InputSocketContentV3 {
+            timestamp: self.timestamp,
+            name: self.name.clone(),
+            inbound_type_definition: self.inbound_type_definition.clone(),
+            outbound_type_definition: self.outbound_type_definition.clone(),
+            kind: self.kind,
+            required: self.required,
+            ui_hidden: self.ui_hidden,
+            connection_annotations: self.connection_annotations.clone(),
+            node_side: self.node_side,
+        };
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(InputSocketContent::V3(content).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        ctx.workspace_snapshot()?
+            .update_content(self.id.into(), hash)
+            .await?;
+
+        Ok(self)
+    }
+
     implement_add_edge_to!(
         source_id: InputSocketId,
         destination_id: AttributePrototypeId,
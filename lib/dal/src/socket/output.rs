@@ -11,8 +11,8 @@ use crate::attribute::prototype::argument::AttributePrototypeArgumentId;
 use crate::attribute::prototype::AttributePrototypeError;
 use crate::attribute::value::AttributeValueError;
 use crate::change_set::ChangeSetError;
-use crate::layer_db_types::{OutputSocketContent, OutputSocketContentV1};
-use crate::socket::{SocketArity, SocketKind};
+use crate::layer_db_types::{OutputSocketContent, OutputSocketContentV2};
+use crate::socket::{SocketArity, SocketKind, SocketNodeSide};
 use crate::workspace_snapshot::content_address::{ContentAddress, ContentAddressDiscriminants};
 use crate::workspace_snapshot::edge_weight::{EdgeWeightKind, EdgeWeightKindDiscriminants};
 use crate::workspace_snapshot::node_weight::{ContentNodeWeight, NodeWeight, NodeWeightError};
@@ -90,10 +90,11 @@ pub struct OutputSocket {
     required: bool,
     ui_hidden: bool,
     connection_annotations: Vec<ConnectionAnnotation>,
+    node_side: Option<SocketNodeSide>,
 }
 
 impl OutputSocket {
-    pub fn assemble(id: OutputSocketId, inner: OutputSocketContentV1) -> Self {
+    pub fn assemble(id: OutputSocketId, inner: OutputSocketContentV2) -> Self {
         Self {
             id,
             timestamp: inner.timestamp,
@@ -104,6 +105,7 @@ impl OutputSocket {
             ui_hidden: inner.ui_hidden,
             required: inner.required,
             connection_annotations: inner.connection_annotations,
+            node_side: inner.node_side,
         }
     }
 
@@ -123,6 +125,10 @@ impl OutputSocket {
         self.ui_hidden
     }
 
+    pub fn kind(&self) -> SocketKind {
+        self.kind
+    }
+
     pub fn required(&self) -> bool {
         self.required
     }
@@ -131,6 +137,48 @@ impl OutputSocket {
         self.connection_annotations.clone()
     }
 
+    /// The side of the node this socket is drawn on, if it has been set. Only used for
+    /// [`bidirectional`](SocketKind::Bidirectional) sockets.
+    pub fn node_side(&self) -> Option<SocketNodeSide> {
+        self.node_side
+    }
+
+    /// Sets the side of the node this socket is drawn on.
+    pub async fn set_node_side(
+        mut self,
+        ctx: &DalContext,
+        node_side: Option<SocketNodeSide>,
+    ) -> OutputSocketResult<Self> {
+        if self.node_side == node_side {
+            return Ok(self);
+        }
+        self.node_side = node_side;
+
+        let content = OutputSocketContentV2 {
+            timestamp: self.timestamp,
+            name: self.name.clone(),
+            type_definition: self.type_definition.clone(),
+            arity: self.arity,
+            kind: self.kind,
+            required: self.required,
+            ui_hidden: self.ui_hidden,
+            connection_annotations: self.connection_annotations.clone(),
+            node_side: self.node_side,
+        };
+        let (hash, _) = ctx.layer_db().cas().write(
+            Arc::new(OutputSocketContent::V2(content).into()),
+            None,
+            ctx.events_tenancy(),
+            ctx.events_actor(),
+        )?;
+
+        ctx.workspace_snapshot()?
+            .update_content(self.id.into(), hash)
+            .await?;
+
+        Ok(self)
+    }
+
     implement_add_edge_to!(
         source_id: OutputSocketId,
         destination_id: AttributePrototypeId,
@@ -159,7 +207,7 @@ impl OutputSocket {
             vec![ConnectionAnnotation::try_from(name.clone())?]
         };
 
-        let content = OutputSocketContentV1 {
+        let content = OutputSocketContentV2 {
             timestamp: Timestamp::now(),
             name: name.clone(),
             type_definition,
@@ -168,9 +216,10 @@ impl OutputSocket {
             required: false,
             ui_hidden: false,
             connection_annotations,
+            node_side: None,
         };
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(OutputSocketContent::V1(content.clone()).into()),
+            Arc::new(OutputSocketContent::V2(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -219,7 +268,7 @@ impl OutputSocket {
     async fn get_node_weight_and_content(
         ctx: &DalContext,
         output_socket_id: OutputSocketId,
-    ) -> OutputSocketResult<(ContentNodeWeight, OutputSocketContentV1)> {
+    ) -> OutputSocketResult<(ContentNodeWeight, OutputSocketContentV2)> {
         let weight = ctx
             .workspace_snapshot()?
             .get_node_weight_by_id(output_socket_id)
@@ -234,10 +283,7 @@ impl OutputSocket {
                 output_socket_id.into(),
             ))?;
 
-        // Do inner content "upgrading" here when there becomes a need for a V2 storage format.
-        let OutputSocketContent::V1(inner) = content;
-
-        Ok((weight, inner))
+        Ok((weight, content.extract()))
     }
 
     ///
@@ -365,10 +411,10 @@ impl OutputSocket {
         for node_weight in node_weights {
             match content_map.get(&node_weight.content_hash()) {
                 Some(content) => {
-                    // NOTE(nick,jacob,zack): if we had a v2, then there would be migration logic here.
-                    let OutputSocketContent::V1(inner) = content;
-
-                    output_sockets.push(Self::assemble(node_weight.id().into(), inner.to_owned()));
+                    output_sockets.push(Self::assemble(
+                        node_weight.id().into(),
+                        content.to_owned().extract(),
+                    ));
                 }
                 None => Err(WorkspaceSnapshotError::MissingContentFromStore(
                     node_weight.id(),
@@ -1,14 +1,20 @@
 //! Status system that can send real time updates for activity to external
 //! consumers, such as the web frontend.
 
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    attribute::{prototype::AttributePrototypeError, value::AttributeValueError},
+    attribute::{
+        prototype::AttributePrototypeError,
+        value::{AttributeValueError, DependentValueGraph},
+    },
     prop::PropError,
-    ComponentId, DalContext, WsEvent, WsEventResult, WsPayload,
+    AttributeValue, ComponentId, DalContext, WorkspaceSnapshotError, WsEvent, WsEventError,
+    WsEventResult, WsPayload,
 };
 
 #[remain::sorted]
@@ -20,6 +26,10 @@ pub enum StatusUpdateError {
     AttributeValue(#[from] AttributeValueError),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
+    #[error("workspace snapshot error: {0}")]
+    WorkspaceSnapshot(#[from] WorkspaceSnapshotError),
+    #[error("ws event error: {0}")]
+    WsEvent(#[from] WsEventError),
 }
 
 pub type StatusUpdateResult<T> = Result<T, StatusUpdateError>;
@@ -46,6 +56,13 @@ pub enum StatusUpdate {
         component_id: ComponentId,
         timestamp: DateTime<Utc>,
     },
+    /// Aggregate progress sent by the dependent values update job
+    #[serde(rename_all = "camelCase")]
+    DependentValueUpdateProgress {
+        completed: usize,
+        total: usize,
+        timestamp: DateTime<Utc>,
+    },
     /// Updates sent by the rebaser
     #[serde(rename_all = "camelCase")]
     Rebase {
@@ -65,6 +82,59 @@ impl StatusUpdate {
         }
     }
 
+    /// Create a progress message for a dependent values update, where `completed` of `total`
+    /// values have been processed
+    pub fn new_dvu_progress(completed: usize, total: usize) -> Self {
+        Self::DependentValueUpdateProgress {
+            completed,
+            total,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Create a status update message for a dependent values update for each of the provided
+    /// [`ComponentIds`](ComponentId), preserving their order
+    pub fn new_dvu_batch(state: StatusMessageState, component_ids: &[ComponentId]) -> Vec<Self> {
+        component_ids
+            .iter()
+            .map(|component_id| Self::new_dvu(state.clone(), *component_id))
+            .collect()
+    }
+
+    /// Publish a finished dependent values update message for every [`Component`](crate::Component)
+    /// in the current change set that may have been left in progress, returning how many were
+    /// published.
+    ///
+    /// A dependent values update only removes its roots from the snapshot once it has finished,
+    /// so the components it would start for the roots still on the snapshot are the ones that
+    /// may have been left started by a run that never sent its finished messages (e.g. because
+    /// the job crashed). Idle components are left alone.
+    pub async fn clear_stuck_for_change_set(ctx: &DalContext) -> StatusUpdateResult<usize> {
+        let roots = ctx.workspace_snapshot()?.peek_dependent_values().await?;
+        if roots.is_empty() {
+            return Ok(0);
+        }
+
+        let mut component_ids = Vec::new();
+        let mut seen_component_ids = HashSet::new();
+        for value_id in DependentValueGraph::new(ctx, roots).await?.all_value_ids() {
+            let component_id = AttributeValue::component_id(ctx, value_id).await?;
+            if seen_component_ids.insert(component_id) {
+                component_ids.push(component_id);
+            }
+        }
+
+        for status_update in Self::new_dvu_batch(StatusMessageState::StatusFinished, &component_ids)
+        {
+            WsEvent::status_update(ctx, status_update)
+                .await?
+                .publish_immediately(ctx)
+                .await?;
+        }
+
+        Ok(component_ids.len())
+    }
+
     /// Create a status update message for a rebase operation
     pub fn new_rebase(status: StatusMessageState) -> Self {
         Self::Rebase {
@@ -85,3 +155,50 @@ impl WsEvent {
         WsEvent::new(ctx, WsPayload::StatusUpdate(status)).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_dvu_batch_produces_one_update_per_component() {
+        let component_ids = vec![
+            ComponentId::generate(),
+            ComponentId::generate(),
+            ComponentId::generate(),
+        ];
+
+        let updates =
+            StatusUpdate::new_dvu_batch(StatusMessageState::StatusFinished, &component_ids);
+
+        assert_eq!(component_ids.len(), updates.len());
+        for (component_id, update) in component_ids.iter().zip(updates) {
+            match update {
+                StatusUpdate::DependentValueUpdate {
+                    status,
+                    component_id: update_component_id,
+                    ..
+                } => {
+                    assert_eq!(StatusMessageState::StatusFinished, status);
+                    assert_eq!(*component_id, update_component_id);
+                }
+                other => panic!("unexpected status update: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn new_dvu_batch_empty() {
+        assert!(StatusUpdate::new_dvu_batch(StatusMessageState::StatusStarted, &[]).is_empty());
+    }
+
+    #[test]
+    fn new_dvu_progress_serializes_counts() {
+        let value = serde_json::to_value(StatusUpdate::new_dvu_progress(3, 10))
+            .expect("could not serialize progress update");
+
+        assert_eq!("dependentValueUpdateProgress", value["kind"]);
+        assert_eq!(3, value["completed"]);
+        assert_eq!(10, value["total"]);
+    }
+}
@@ -7,14 +7,15 @@ use si_events::{ContentHash, WorkspaceSnapshotAddress};
 use si_layer_cache::db::serialize;
 use si_layer_cache::LayerDbError;
 use si_pkg::{
-    WorkspaceExport, WorkspaceExportChangeSetV0, WorkspaceExportContentV0,
+    WorkspaceExport, WorkspaceExportChangeSetV0, WorkspaceExportContentV0, WorkspaceExportFrameV0,
     WorkspaceExportMetadataV0,
 };
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 use std::sync::Arc;
 use telemetry::prelude::*;
 use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use ulid::Ulid;
 
 use crate::builtins::func::migrate_intrinsics_no_commit;
@@ -23,11 +24,13 @@ use crate::feature_flags::FeatureFlag;
 use crate::layer_db_types::ContentTypes;
 use crate::workspace_integrations::{WorkspaceIntegration, WorkspaceIntegrationsError};
 use crate::workspace_snapshot::graph::WorkspaceSnapshotGraphDiscriminants;
+use crate::workspace_snapshot::migrator::SnapshotGraphMigrator;
 use crate::workspace_snapshot::WorkspaceSnapshotError;
 use crate::{
-    standard_model, standard_model_accessor_ro, BuiltinsError, DalContext, HistoryActor,
-    HistoryEvent, HistoryEventError, KeyPairError, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, User, UserError, UserPk, WorkspaceSnapshot, WorkspaceSnapshotGraph,
+    standard_model, standard_model_accessor_ro, BuiltinsError, ChangeSetStatus, DalContext,
+    HistoryActor, HistoryEvent, HistoryEventError, KeyPairError, StandardModelError, Tenancy,
+    Timestamp, TransactionsError, User, UserError, UserPk, Visibility, WorkspaceSnapshot,
+    WorkspaceSnapshotGraph,
 };
 
 pub use si_id::WorkspaceId;
@@ -55,16 +58,28 @@ pub enum WorkspaceError {
     BuiltinWorkspaceNotFound,
     #[error("change set error: {0}")]
     ChangeSet(#[from] ChangeSetError),
+    #[error("cannot import change set {0}: its base change set is not in the workspace export")]
+    ChangeSetBaseNotInExport(ChangeSetId),
     #[error("change set not found by id: {0}")]
     ChangeSetNotFound(ChangeSetId),
+    #[error("change set not found in workspace export: {0}")]
+    ChangeSetNotInExport(ChangeSetId),
+    #[error("workspace export stream has no metadata frame")]
+    ExportMissingMetadata,
+    #[error("cannot export change set {0}: its base change set {1} is not active and would not be included in the export")]
+    ExportingChangeSetWithMissingBase(ChangeSetId, ChangeSetId),
     #[error("Trying to export from system actor. This can only be done by a user actor")]
     ExportingFromSystemActor,
     #[error(transparent)]
     HistoryEvent(#[from] HistoryEventError),
     #[error("Trying to import a changeset that does not have a valid base: {0}")]
     ImportingOrphanChangeset(ChangeSetId),
+    #[error("change set {0} cannot be the default change set for workspace {1}")]
+    InvalidDefaultChangeSet(ChangeSetId, WorkspacePk),
     #[error("invalid user {0}")]
     InvalidUser(UserPk),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
     #[error(transparent)]
     KeyPair(#[from] KeyPairError),
     #[error("LayerDb error: {0}")]
@@ -106,6 +121,7 @@ pub struct Workspace {
     token: Option<String>,
     snapshot_version: WorkspaceSnapshotGraphDiscriminants,
     component_concurrency_limit: Option<i32>,
+    deleted_at: Option<DateTime<Utc>>,
 }
 
 impl TryFrom<PgRow> for Workspace {
@@ -124,6 +140,7 @@ impl TryFrom<PgRow> for Workspace {
             token: row.try_get("token")?,
             snapshot_version: WorkspaceSnapshotGraphDiscriminants::from_str(&snapshot_version)?,
             component_concurrency_limit: row.try_get("component_concurrency_limit")?,
+            deleted_at: row.try_get("deleted_at")?,
         })
     }
 }
@@ -163,10 +180,40 @@ impl Workspace {
         Ok(())
     }
 
+    /// Points the [`Workspace`] at a new default [`ChangeSet`] ("HEAD"). The [`ChangeSet`] must
+    /// belong to the [`Workspace`] and must not have been abandoned, applied or have failed,
+    /// otherwise [`WorkspaceError::InvalidDefaultChangeSet`] is returned.
     pub async fn update_default_change_set_id(
         &mut self,
         ctx: &DalContext,
         change_set_id: ChangeSetId,
+    ) -> WorkspaceResult<()> {
+        let invalid = WorkspaceError::InvalidDefaultChangeSet(change_set_id, self.pk);
+        if ctx.tenancy().workspace_pk_opt() != Some(self.pk)
+            || !Self::has_change_set(ctx, change_set_id).await?
+        {
+            return Err(invalid);
+        }
+        let change_set = ChangeSet::find(ctx, change_set_id)
+            .await?
+            .ok_or(WorkspaceError::ChangeSetNotFound(change_set_id))?;
+        if matches!(
+            change_set.status,
+            ChangeSetStatus::Abandoned | ChangeSetStatus::Applied | ChangeSetStatus::Failed
+        ) {
+            return Err(invalid);
+        }
+
+        self.update_default_change_set_id_unchecked(ctx, change_set_id)
+            .await
+    }
+
+    /// Like [`Self::update_default_change_set_id`], but without validating the [`ChangeSet`].
+    /// Only for code (like builtin setup and migrations) that genuinely needs to bypass the check.
+    pub async fn update_default_change_set_id_unchecked(
+        &mut self,
+        ctx: &DalContext,
+        change_set_id: ChangeSetId,
     ) -> WorkspaceResult<()> {
         ctx.txns()
             .await?
@@ -193,7 +240,7 @@ impl Workspace {
             let new_change_set =
                 ChangeSet::new(ctx, DEFAULT_CHANGE_SET_NAME, None, new_snap_address).await?;
             found_builtin
-                .update_default_change_set_id(ctx, new_change_set.id)
+                .update_default_change_set_id_unchecked(ctx, new_change_set.id)
                 .await?;
 
             ctx.update_tenancy(Tenancy::new(*found_builtin.pk()));
@@ -321,7 +368,7 @@ impl Workspace {
 
     pub async fn find_first_user_workspace(ctx: &DalContext) -> WorkspaceResult<Option<Self>> {
         let maybe_row = ctx.txns().await?.pg().query_opt(
-            "SELECT row_to_json(w.*) AS object FROM workspaces AS w WHERE pk != $1 ORDER BY created_at ASC LIMIT 1", &[&WorkspacePk::NONE],
+            "SELECT row_to_json(w.*) AS object FROM workspaces AS w WHERE pk != $1 AND deleted_at IS NULL ORDER BY created_at ASC LIMIT 1", &[&WorkspacePk::NONE],
         ).await?;
         let maybe_workspace = match maybe_row {
             Some(found) => Some(Self::try_from(found)?),
@@ -485,55 +532,188 @@ impl Workspace {
     ) -> WorkspaceResult<WorkspaceExport> {
         let mut content_hashes = vec![];
         let mut change_sets: HashMap<Ulid, Vec<WorkspaceExportChangeSetV0>> = HashMap::new();
-        let mut default_change_set_base = Ulid::nil();
-        for change_set in ChangeSet::list_active(ctx).await? {
-            let snap = WorkspaceSnapshot::find_for_change_set(ctx, change_set.id).await?;
-
-            // From root, get every value from every node, store with hash
-            let mut queue = VecDeque::from([snap.root().await?]);
-
-            while let Some(this_node_idx) = queue.pop_front() {
-                // Queue contents
-                content_hashes.extend(
-                    snap.get_node_weight(this_node_idx)
-                        .await?
-                        .content_store_hashes(),
-                );
-
-                let children = snap
-                    .edges_directed_by_index(this_node_idx, Direction::Outgoing)
-                    .await?
-                    .into_iter()
-                    .map(|(_, _, target)| target)
-                    .collect::<VecDeque<_>>();
+        for change_set in self.list_change_sets_for_export(ctx).await? {
+            let (change_set_export, change_set_content_hashes) =
+                Self::export_change_set(ctx, &change_set).await?;
+            content_hashes.extend(change_set_content_hashes);
 
-                queue.extend(children)
-            }
+            change_sets
+                .entry(change_set_export.base_change_set_id.unwrap_or(Ulid::nil()))
+                .or_default()
+                .push(change_set_export)
+        }
+
+        let content_store_values = Self::export_content_store_values(ctx, &content_hashes).await?;
+        let metadata = self.export_metadata(ctx, workspace_version).await?;
+
+        Ok(WorkspaceExport::new(WorkspaceExportContentV0 {
+            change_sets,
+            content_store_values,
+            metadata,
+        }))
+    }
+
+    /// Streams the export of this [`Workspace`] to the provided writer, one
+    /// [`frame`](WorkspaceExportFrameV0) at a time. Unlike
+    /// [`generate_export_data`](Self::generate_export_data), only a single [`ChangeSet`] and its
+    /// content store values are held in memory at once.
+    ///
+    /// Each frame is compressed and prefixed with its length as a big-endian `u64`. The metadata
+    /// frame is written first, followed by the content store values frame and the change set
+    /// frame for every active [`ChangeSet`]. Each content store value is only written once, in the
+    /// frame before the first change set that references it. Use
+    /// [`import_from_reader`](Self::import_from_reader) to import the result.
+    pub async fn export_to_writer(
+        &self,
+        ctx: &DalContext,
+        workspace_version: &str,
+        mut writer: impl AsyncWrite + Unpin,
+    ) -> WorkspaceResult<()> {
+        let metadata = self.export_metadata(ctx, workspace_version).await?;
+        Self::write_export_frame(&mut writer, &WorkspaceExportFrameV0::Metadata(metadata)).await?;
+
+        // Change sets share most of their content, so skip the values an earlier frame has
+        // already carried.
+        let mut exported_content_hashes = HashSet::new();
+        for change_set in self.list_change_sets_for_export(ctx).await? {
+            let (change_set_export, content_hashes) =
+                Self::export_change_set(ctx, &change_set).await?;
+            let content_hashes: Vec<ContentHash> = content_hashes
+                .into_iter()
+                .filter(|content_hash| exported_content_hashes.insert(*content_hash))
+                .collect();
+            let content_store_values =
+                Self::export_content_store_values(ctx, &content_hashes).await?;
+
+            Self::write_export_frame(
+                &mut writer,
+                &WorkspaceExportFrameV0::ContentStoreValues(content_store_values),
+            )
+            .await?;
+            Self::write_export_frame(
+                &mut writer,
+                &WorkspaceExportFrameV0::ChangeSet(change_set_export),
+            )
+            .await?;
+        }
 
-            let base_changeset = change_set
-                .base_change_set_id
-                .map(|id| id.into_inner())
-                .unwrap_or(Ulid::nil());
+        writer.flush().await?;
+
+        Ok(())
+    }
+
+    /// Lists the active [`ChangeSets`](ChangeSet) to export, ensuring that the base of every
+    /// non-default [`ChangeSet`] is exported too. Import rebuilds change sets outward from the
+    /// default [`ChangeSet`], so one whose base is missing from the export could never be imported.
+    async fn list_change_sets_for_export(
+        &self,
+        ctx: &DalContext,
+    ) -> WorkspaceResult<Vec<ChangeSet>> {
+        let change_sets = ChangeSet::list_active(ctx).await?;
+        let exported_ids: HashSet<ChangeSetId> =
+            change_sets.iter().map(|change_set| change_set.id).collect();
 
+        for change_set in &change_sets {
             if change_set.id == self.default_change_set_id() {
-                default_change_set_base = base_changeset
+                continue;
+            }
+            if let Some(base_change_set_id) = change_set.base_change_set_id {
+                if !exported_ids.contains(&base_change_set_id) {
+                    return Err(WorkspaceError::ExportingChangeSetWithMissingBase(
+                        change_set.id,
+                        base_change_set_id,
+                    ));
+                }
             }
+        }
 
-            change_sets
-                .entry(base_changeset)
-                .or_default()
-                .push(WorkspaceExportChangeSetV0 {
-                    id: change_set.id.into_inner(),
-                    name: change_set.name.clone(),
-                    base_change_set_id: change_set.base_change_set_id.map(|id| id.into_inner()),
-                    workspace_snapshot_serialized_data: snap.serialized().await?,
-                })
+        Ok(change_sets)
+    }
+
+    async fn write_export_frame(
+        writer: &mut (impl AsyncWrite + Unpin),
+        frame: &WorkspaceExportFrameV0,
+    ) -> WorkspaceResult<()> {
+        let (bytes, _) = serialize::to_vec(frame)?;
+        writer.write_u64(bytes.len() as u64).await?;
+        writer.write_all(&bytes).await?;
+
+        Ok(())
+    }
+
+    /// Reads the next [`frame`](WorkspaceExportFrameV0) written by
+    /// [`export_to_writer`](Self::export_to_writer), or `None` once the reader is exhausted.
+    pub async fn read_export_frame(
+        reader: &mut (impl AsyncRead + Unpin),
+    ) -> WorkspaceResult<Option<WorkspaceExportFrameV0>> {
+        // Running out of input between frames is the end of the export, anywhere else the export
+        // is truncated.
+        let mut length_bytes = [0; 8];
+        if reader.read(&mut length_bytes[..1]).await? == 0 {
+            return Ok(None);
+        }
+        reader.read_exact(&mut length_bytes[1..]).await?;
+
+        // The length comes from the stream, so read through `take` and let the buffer grow with
+        // the data that actually arrives instead of allocating whatever length is claimed.
+        let length = u64::from_be_bytes(length_bytes);
+        let mut bytes = Vec::new();
+        (&mut *reader).take(length).read_to_end(&mut bytes).await?;
+        if (bytes.len() as u64) < length {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(Some(serialize::from_bytes(&bytes)?))
+    }
+
+    /// Serializes the snapshot for a [`ChangeSet`] and collects the content hashes of every node
+    /// reachable from its root.
+    async fn export_change_set(
+        ctx: &DalContext,
+        change_set: &ChangeSet,
+    ) -> WorkspaceResult<(WorkspaceExportChangeSetV0, Vec<ContentHash>)> {
+        let mut content_hashes = vec![];
+        let snap = WorkspaceSnapshot::find_for_change_set(ctx, change_set.id).await?;
+
+        // From root, get every value from every node, store with hash
+        let mut queue = VecDeque::from([snap.root().await?]);
+
+        while let Some(this_node_idx) = queue.pop_front() {
+            // Queue contents
+            content_hashes.extend(
+                snap.get_node_weight(this_node_idx)
+                    .await?
+                    .content_store_hashes(),
+            );
+
+            let children = snap
+                .edges_directed_by_index(this_node_idx, Direction::Outgoing)
+                .await?
+                .into_iter()
+                .map(|(_, _, target)| target)
+                .collect::<VecDeque<_>>();
+
+            queue.extend(children)
         }
 
+        let change_set_export = WorkspaceExportChangeSetV0 {
+            id: change_set.id.into_inner(),
+            name: change_set.name.clone(),
+            base_change_set_id: change_set.base_change_set_id.map(|id| id.into_inner()),
+            workspace_snapshot_serialized_data: snap.serialized().await?,
+        };
+
+        Ok((change_set_export, content_hashes))
+    }
+
+    async fn export_content_store_values(
+        ctx: &DalContext,
+        content_hashes: &[ContentHash],
+    ) -> WorkspaceResult<Vec<u8>> {
         let store_values_map = ctx
             .layer_db()
             .cas()
-            .read_many(content_hashes.as_ref())
+            .read_many(content_hashes)
             .await?
             .into_iter()
             .map(|(hash, content)| (hash, (content, "postcard".to_string())))
@@ -541,6 +721,24 @@ impl Workspace {
 
         let (content_store_values, _) = serialize::to_vec(&store_values_map)?;
 
+        Ok(content_store_values)
+    }
+
+    async fn export_metadata(
+        &self,
+        ctx: &DalContext,
+        workspace_version: &str,
+    ) -> WorkspaceResult<WorkspaceExportMetadataV0> {
+        let default_change_set = ChangeSet::find(ctx, self.default_change_set_id())
+            .await?
+            .ok_or(WorkspaceError::ChangeSetNotFound(
+                self.default_change_set_id(),
+            ))?;
+        let default_change_set_base = default_change_set
+            .base_change_set_id
+            .map(|id| id.into_inner())
+            .unwrap_or(Ulid::nil());
+
         let created_by = if let HistoryActor::User(user_pk) = ctx.history_actor() {
             let user = User::get_by_pk(ctx, *user_pk)
                 .await?
@@ -551,7 +749,7 @@ impl Workspace {
             "SystemInit".to_string()
         };
 
-        let metadata = WorkspaceExportMetadataV0 {
+        Ok(WorkspaceExportMetadataV0 {
             name: self.name().clone(),
             version: workspace_version.to_string(),
             description: "Workspace Backup".to_string(), // TODO Get this from the user
@@ -561,13 +759,8 @@ impl Workspace {
             default_change_set_base,
             workspace_pk: self.pk().into_inner(),
             workspace_name: self.name().clone(),
-        };
-
-        Ok(WorkspaceExport::new(WorkspaceExportContentV0 {
-            change_sets,
-            content_store_values,
-            metadata,
-        }))
+            component_concurrency_limit: self.raw_component_concurrency_limit(),
+        })
     }
 
     pub async fn import(
@@ -581,6 +774,46 @@ impl Workspace {
             metadata,
         } = workspace_data.into_latest();
 
+        self.import_change_sets(ctx, change_sets, metadata).await?;
+        Self::import_content_store_values(ctx, &content_store_values)?;
+
+        Ok(())
+    }
+
+    /// Imports an export streamed by [`export_to_writer`](Self::export_to_writer), replacing the
+    /// change sets of this [`Workspace`] like [`import`](Self::import) does. Content store values
+    /// are written as their frames are read, so only the change sets are held in memory. No change
+    /// set is touched until the whole stream has been read.
+    pub async fn import_from_reader(
+        &mut self,
+        ctx: &DalContext,
+        mut reader: impl AsyncRead + Unpin,
+    ) -> WorkspaceResult<()> {
+        let mut metadata = None;
+        let mut change_sets: HashMap<Ulid, Vec<WorkspaceExportChangeSetV0>> = HashMap::new();
+        while let Some(frame) = Self::read_export_frame(&mut reader).await? {
+            match frame {
+                WorkspaceExportFrameV0::Metadata(frame_metadata) => metadata = Some(frame_metadata),
+                WorkspaceExportFrameV0::ContentStoreValues(content_store_values) => {
+                    Self::import_content_store_values(ctx, &content_store_values)?
+                }
+                WorkspaceExportFrameV0::ChangeSet(change_set) => change_sets
+                    .entry(change_set.base_change_set_id.unwrap_or(Ulid::nil()))
+                    .or_default()
+                    .push(change_set),
+            }
+        }
+        let metadata = metadata.ok_or(WorkspaceError::ExportMissingMetadata)?;
+
+        self.import_change_sets(ctx, change_sets, metadata).await
+    }
+
+    async fn import_change_sets(
+        &mut self,
+        ctx: &DalContext,
+        change_sets: HashMap<Ulid, Vec<WorkspaceExportChangeSetV0>>,
+        metadata: WorkspaceExportMetadataV0,
+    ) -> WorkspaceResult<()> {
         // ABANDON PREVIOUS CHANGESETS
         for mut change_set in ChangeSet::list_active(ctx).await? {
             change_set.abandon(ctx).await?;
@@ -644,8 +877,22 @@ impl Workspace {
             }
         }
 
+        // Only carry the limit over when the export set one, so that importing an export without
+        // it does not reset a limit tuned for this workspace.
+        if metadata.component_concurrency_limit.is_some() {
+            self.set_component_concurrency_limit(ctx, metadata.component_concurrency_limit)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    fn import_content_store_values(
+        ctx: &DalContext,
+        content_store_values: &[u8],
+    ) -> WorkspaceResult<()> {
         let cas_values: HashMap<ContentHash, (Arc<ContentTypes>, String)> =
-            serialize::from_bytes(&content_store_values)?;
+            serialize::from_bytes(content_store_values)?;
 
         let layer_db = ctx.layer_db();
 
@@ -659,6 +906,91 @@ impl Workspace {
         Ok(())
     }
 
+    /// Imports a single change set from a [`WorkspaceExport`] as a new [`ChangeSet`] based on
+    /// `base_change_set_id`. Unlike [`Self::import`], no other change sets are touched, and only
+    /// the content store values referenced by the imported snapshot are written.
+    ///
+    /// The changes the exported change set made against its own base in the export are rebased
+    /// onto `base_change_set_id`, so anything that only exists in that base is kept. Change sets
+    /// whose base is not part of the export are rejected, since there is nothing to compute their
+    /// changes against.
+    pub async fn import_change_set(
+        ctx: &DalContext,
+        workspace_data: &WorkspaceExport,
+        change_set_id: ChangeSetId,
+        base_change_set_id: ChangeSetId,
+    ) -> WorkspaceResult<ChangeSetId> {
+        let WorkspaceExport::V0(export) = workspace_data;
+
+        let change_set_data = export
+            .change_sets
+            .values()
+            .flatten()
+            .find(|change_set| change_set.id == change_set_id.into_inner())
+            .ok_or(WorkspaceError::ChangeSetNotInExport(change_set_id))?;
+        let base_change_set = ChangeSet::find(ctx, base_change_set_id)
+            .await?
+            .ok_or(WorkspaceError::ChangeSetNotFound(base_change_set_id))?;
+
+        let export_base_change_set_data = change_set_data
+            .base_change_set_id
+            .and_then(|export_base_id| {
+                export
+                    .change_sets
+                    .values()
+                    .flatten()
+                    .find(|change_set| change_set.id == export_base_id)
+            })
+            .ok_or(WorkspaceError::ChangeSetBaseNotInExport(change_set_id))?;
+
+        let export_base_snapshot = Arc::new(WorkspaceSnapshot::from_bytes(
+            &export_base_change_set_data.workspace_snapshot_serialized_data,
+        )?);
+        let imported_snapshot = Arc::new(WorkspaceSnapshot::from_bytes(
+            &change_set_data.workspace_snapshot_serialized_data,
+        )?);
+        let content_hashes: HashSet<ContentHash> = imported_snapshot
+            .nodes()
+            .await?
+            .into_iter()
+            .flat_map(|(node_weight, _)| node_weight.content_store_hashes())
+            .collect();
+
+        let cas_values: HashMap<ContentHash, (Arc<ContentTypes>, String)> =
+            serialize::from_bytes(&export.content_store_values)?;
+        let layer_db = ctx.layer_db();
+        for (hash, (content, _serialization_format)) in cas_values {
+            if content_hashes.contains(&hash) {
+                layer_db
+                    .cas()
+                    .write(content, None, ctx.events_tenancy(), ctx.events_actor())?;
+            }
+        }
+
+        let rebased_snapshot =
+            WorkspaceSnapshot::find(ctx, base_change_set.workspace_snapshot_address).await?;
+        if let Some(rebase_batch) =
+            WorkspaceSnapshot::calculate_rebase_batch(export_base_snapshot, imported_snapshot)
+                .await?
+        {
+            let updates = rebased_snapshot
+                .correct_transforms(rebase_batch.updates().to_vec(), true)
+                .await?;
+            rebased_snapshot.perform_updates(&updates).await?;
+        }
+
+        let new_snap_address = rebased_snapshot.write(ctx).await?;
+        let new_change_set = ChangeSet::new(
+            ctx,
+            change_set_data.name.clone(),
+            Some(base_change_set.id),
+            new_snap_address,
+        )
+        .await?;
+
+        Ok(new_change_set.id)
+    }
+
     standard_model_accessor_ro!(name, String);
 
     pub async fn has_change_set(
@@ -700,6 +1032,72 @@ impl Workspace {
         Ok(())
     }
 
+    /// Lists the [`Workspaces`](Workspace) whose snapshot version is not the current
+    /// [`WorkspaceSnapshotGraph`] version, i.e. those that have not been migrated.
+    pub async fn list_pks_with_outdated_snapshot_version(
+        ctx: &DalContext,
+    ) -> WorkspaceResult<Vec<WorkspacePk>> {
+        let version_string = WorkspaceSnapshotGraph::current_discriminant().to_string();
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                "SELECT pk FROM workspaces WHERE snapshot_version != $1",
+                &[&version_string],
+            )
+            .await?;
+
+        let mut pks = Vec::with_capacity(rows.len());
+        for row in rows {
+            pks.push(row.try_get("pk")?);
+        }
+
+        Ok(pks)
+    }
+
+    /// For each open [`ChangeSet`] across all workspaces, reports whether its snapshot can be
+    /// migrated to the current [`WorkspaceSnapshotGraph`] version. Use this to validate that a
+    /// migration is safe before running it and marking workspaces with
+    /// [`Self::set_snapshot_version_for_all_workspaces`].
+    ///
+    /// No snapshots or change set pointers are written. Individual migration steps may still
+    /// populate the content store, which is content addressed and therefore safe to repeat.
+    pub async fn check_migratable(ctx: &DalContext) -> WorkspaceResult<Vec<(ChangeSetId, bool)>> {
+        let mut results = vec![];
+        for change_set in ChangeSet::list_open_for_all_workspaces(ctx).await? {
+            let change_set = ChangeSet::find(ctx, change_set.id)
+                .await?
+                .ok_or(ChangeSetError::ChangeSetNotFound(change_set.id))?;
+            // Change sets without a workspace, or that a previous migration marked as failed, are
+            // skipped by the migrator, so they are skipped here as well.
+            if change_set.workspace_id.is_none() || change_set.status == ChangeSetStatus::Failed {
+                continue;
+            }
+
+            let change_set_id = change_set.id;
+            let snapshot_address = change_set.workspace_snapshot_address;
+            let mut change_set_ctx = ctx.clone_with_new_visibility(Visibility::from(change_set_id));
+            change_set_ctx.set_change_set(change_set)?;
+
+            let migratable = match SnapshotGraphMigrator::new()
+                .migrate_snapshot(&change_set_ctx, snapshot_address)
+                .await
+            {
+                Ok(_) => true,
+                Err(err) => {
+                    warn!(si.error.message = ?err, %change_set_id, "snapshot for change set is not migratable");
+                    false
+                }
+            };
+
+            results.push((change_set_id, migratable));
+        }
+
+        Ok(results)
+    }
+
     pub fn component_concurrency_limit(&self) -> i32 {
         self.component_concurrency_limit
             .unwrap_or(DEFAULT_COMPONENT_CONCURRENCY_LIMIT)
@@ -709,6 +1107,8 @@ impl Workspace {
         self.component_concurrency_limit
     }
 
+    /// Sets how many components a dependent values update processes at once for this workspace.
+    /// `None`, or a limit below 1, clears it so the default applies.
     pub async fn set_component_concurrency_limit(
         &mut self,
         ctx: &DalContext,
@@ -736,4 +1136,117 @@ impl Workspace {
     pub fn timestamp(&self) -> &Timestamp {
         &self.timestamp
     }
+
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        self.deleted_at
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Soft-deletes this [`Workspace`]. Its data is left untouched, but it is hidden from
+    /// [`Self::list_for_user`] and [`Self::find_first_user_workspace`] until it is restored with
+    /// [`Self::restore`].
+    pub async fn mark_deleted(&mut self, ctx: &DalContext) -> WorkspaceResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "UPDATE workspaces SET deleted_at = CLOCK_TIMESTAMP() WHERE pk = $1 RETURNING deleted_at",
+                &[&self.pk],
+            )
+            .await?;
+
+        self.deleted_at = row.try_get("deleted_at")?;
+
+        Ok(())
+    }
+
+    /// Undoes a soft-delete performed by [`Self::mark_deleted`].
+    pub async fn restore(&mut self, ctx: &DalContext) -> WorkspaceResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .query_none(
+                "UPDATE workspaces SET deleted_at = NULL WHERE pk = $1",
+                &[&self.pk],
+            )
+            .await?;
+
+        self.deleted_at = None;
+
+        Ok(())
+    }
+}
+
+/// Comparisons for [`WorkspaceExports`](WorkspaceExport) that require decoding their contents.
+pub trait WorkspaceExportExt {
+    /// Returns whether both exports hold the same content store values and the same tree of
+    /// change sets, ignoring change set ids (which [`Workspace::import`] remaps).
+    ///
+    /// Change sets are compared by name and by the merkle tree hash of their snapshot root,
+    /// starting from the default change set. Change sets that import would skip because they are
+    /// not reachable from the default change set are ignored.
+    fn content_equivalent(&self, other: &Self) -> WorkspaceResult<bool>;
+}
+
+impl WorkspaceExportExt for WorkspaceExport {
+    fn content_equivalent(&self, other: &Self) -> WorkspaceResult<bool> {
+        let (WorkspaceExport::V0(this), WorkspaceExport::V0(other)) = (self, other);
+
+        if exported_content_hashes(this)? != exported_content_hashes(other)? {
+            return Ok(false);
+        }
+
+        let this_change_sets =
+            ExportedChangeSetShape::children_of(this, this.metadata.default_change_set_base)?;
+        let other_change_sets =
+            ExportedChangeSetShape::children_of(other, other.metadata.default_change_set_base)?;
+
+        Ok(this_change_sets == other_change_sets)
+    }
+}
+
+fn exported_content_hashes(
+    export: &WorkspaceExportContentV0,
+) -> WorkspaceResult<HashSet<ContentHash>> {
+    let cas_values: HashMap<ContentHash, (Arc<ContentTypes>, String)> =
+        serialize::from_bytes(&export.content_store_values)?;
+
+    // Content is addressed by its hash, so equal hashes mean equal values.
+    Ok(cas_values.into_keys().collect())
+}
+
+/// The id independent shape of an exported [`ChangeSet`] and the change sets based on it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ExportedChangeSetShape {
+    name: String,
+    root_merkle_tree_hash: String,
+    children: Vec<ExportedChangeSetShape>,
+}
+
+impl ExportedChangeSetShape {
+    fn children_of(export: &WorkspaceExportContentV0, base_id: Ulid) -> WorkspaceResult<Vec<Self>> {
+        let mut children = vec![];
+        for change_set in export.change_sets.get(&base_id).into_iter().flatten() {
+            let graph: WorkspaceSnapshotGraph =
+                serialize::from_bytes(&change_set.workspace_snapshot_serialized_data)?;
+            let root_merkle_tree_hash = graph
+                .get_node_weight(graph.root())
+                .map_err(WorkspaceSnapshotError::from)?
+                .merkle_tree_hash()
+                .to_string();
+
+            children.push(Self {
+                name: change_set.name.clone(),
+                root_merkle_tree_hash,
+                children: Self::children_of(export, change_set.id)?,
+            });
+        }
+        children.sort();
+
+        Ok(children)
+    }
 }
@@ -38,7 +38,7 @@ pub use traits::{schema::variant::SchemaVariantExt, socket::input::InputSocketEx
 
 use graph::correct_transforms::correct_transforms;
 use graph::detect_updates::Update;
-use graph::{RebaseBatch, WorkspaceSnapshotGraph};
+use graph::{RebaseBatch, WorkspaceSnapshotGraph, WorkspaceSnapshotGraphResult};
 use node_weight::traits::CorrectTransformsError;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
@@ -78,7 +78,7 @@ use crate::{
 };
 use crate::{
     AttributeValueId, Component, ComponentError, ComponentId, InputSocketId, OutputSocketId,
-    SchemaId, SchemaVariantId, TenancyError, Workspace, WorkspaceError,
+    PropId, PropKind, SchemaId, SchemaVariantId, TenancyError, Workspace, WorkspaceError,
 };
 
 use self::node_weight::{NodeWeightDiscriminants, OrderingNodeWeight};
@@ -100,6 +100,32 @@ impl From<&NodeWeight> for NodeInformation {
     }
 }
 
+/// A structural problem found by [`WorkspaceSnapshot::verify_integrity`].
+#[remain::sorted]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityViolation {
+    /// An edge connects a node that is no longer the indexed node for its id.
+    DanglingEdge {
+        source_id: Ulid,
+        target_id: Ulid,
+        kind: EdgeWeightKindDiscriminants,
+    },
+    /// An ordered container (a container [`Prop`](crate::Prop) or an attribute value for one)
+    /// has no ordering node.
+    MissingOrderingNode(Ulid),
+    /// An ordering node lists an id that is not in the graph.
+    OrderingReferencesMissingNode {
+        ordering_node_id: Ulid,
+        missing_id: Ulid,
+    },
+    /// A [`Prop`](crate::Prop) has no parent.
+    PropIsOrphan(PropId),
+    /// A [`Prop`](crate::Prop) has a parent that is neither a prop nor a schema variant.
+    PropParentInvalid(PropId),
+    /// A node has more than one ordering node.
+    TooManyOrderingNodes(Ulid),
+}
+
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum WorkspaceSnapshotError {
@@ -131,10 +157,18 @@ pub enum WorkspaceSnapshotError {
     LayerDb(#[from] si_layer_cache::LayerDbError),
     #[error("missing content from store for id: {0}")]
     MissingContentFromStore(Ulid),
+    #[error("missing {1} content from store for id {0} (content hash: {2})")]
+    MissingContentFromStoreForHash(Ulid, ContentAddressDiscriminants, ContentHash),
     #[error("could not find a max vector clock for change set id {0}")]
     MissingVectorClockForChangeSet(ChangeSetId),
     #[error("monotonic error: {0}")]
     Monotonic(#[from] ulid::MonotonicError),
+    #[error("node not found for id: {0}")]
+    NodeNotFound(Ulid),
+    #[error(
+        "node {0} cannot be added as an ordered node: prop kind {1:?} does not support ordering"
+    )]
+    NodeNotOrderable(Ulid, PropKind),
     #[error("NodeWeight error: {0}")]
     NodeWeight(#[from] NodeWeightError),
     #[error("ordering not found for node with ordered children: {0}")]
@@ -183,7 +217,8 @@ impl WorkspaceSnapshotError {
     pub fn is_node_with_id_not_found(&self) -> bool {
         matches!(
             self,
-            Self::WorkspaceSnapshotGraph(WorkspaceSnapshotGraphError::NodeWithIdNotFound(_,),)
+            Self::NodeNotFound(_)
+                | Self::WorkspaceSnapshotGraph(WorkspaceSnapshotGraphError::NodeWithIdNotFound(_,),)
         )
     }
 }
@@ -228,6 +263,25 @@ pub struct WorkspaceSnapshot {
 
     /// A cached version of the inferred connection graph for this snapshot
     inferred_connection_graph: Arc<RwLock<Option<InferredConnectionGraph>>>,
+
+    /// The nodes and edges modified through this snapshot since it was last written
+    dirty: Arc<Mutex<DirtySet>>,
+}
+
+/// What has been modified through a snapshot since it was last written.
+#[derive(Debug, Default)]
+struct DirtySet {
+    entries: HashSet<DirtyEntry>,
+    /// Set when the graph was mutated directly (see [`WorkspaceSnapshot::with_mutations`]), so
+    /// the individual changes are unknown and everything has to be treated as modified.
+    fully_dirty: bool,
+}
+
+/// A node or edge that has been modified since the snapshot was last written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum DirtyEntry {
+    Edge(Ulid, Ulid, EdgeWeightKindDiscriminants),
+    Node(Ulid),
 }
 
 /// A pretty dumb attempt to make enabling the cycle check more ergonomic. This
@@ -385,6 +439,7 @@ impl WorkspaceSnapshot {
             cycle_check: Arc::new(AtomicBool::new(false)),
             dvu_roots: Arc::new(Mutex::new(HashSet::new())),
             inferred_connection_graph: Arc::new(RwLock::new(None)),
+            dirty: Arc::new(Mutex::new(DirtySet::default())),
         };
 
         initial.write(ctx).await?;
@@ -526,6 +581,20 @@ impl WorkspaceSnapshot {
             new_address
         };
 
+        let (changed, fully_dirty) = {
+            let mut dirty = self.dirty.lock().await;
+            let changed = dirty.entries.len();
+            let fully_dirty = dirty.fully_dirty;
+            *dirty = DirtySet::default();
+            (changed, fully_dirty)
+        };
+        debug!(
+            %new_address,
+            changed,
+            fully_dirty,
+            "wrote workspace snapshot"
+        );
+
         // Note, we continue to use the working copy after this, even for reads, since otherwise
         // we'd have to replace the read_only_graph, which would require another thread-safe
         // interior mutability type to store the read only graph in.
@@ -586,6 +655,78 @@ impl WorkspaceSnapshot {
         }
     }
 
+    /// Acquire the write lock on the working copy once and run `f` against the graph, allowing
+    /// many mutations (e.g. while importing or building a schema variant) without re-acquiring
+    /// the lock for each one. Note that cycle checks are not performed for edges added through
+    /// the closure. The individual changes are not tracked, so the whole snapshot is treated as
+    /// modified until it is next written (see [`Self::changed_since_last_write`]).
+    pub async fn with_mutations<R>(
+        &self,
+        f: impl FnOnce(&mut WorkspaceSnapshotGraphVCurrent) -> WorkspaceSnapshotGraphResult<R>,
+    ) -> WorkspaceSnapshotResult<R> {
+        // Mark before running the closure, since it may mutate the graph and then fail
+        self.dirty.lock().await.fully_dirty = true;
+        let mut working_copy = self.working_copy_mut().await;
+        Ok(f(&mut working_copy)?)
+    }
+
+    /// Returns `true` if the read-only graph has been copied into a mutable working copy, which
+    /// happens the first time the snapshot is modified.
+    pub async fn has_working_copy(&self) -> bool {
+        self.working_copy.read().await.is_some()
+    }
+
+    /// Returns a point-in-time view of the graph that can be traversed without holding any lock on
+    /// the working copy, so long read-only traversals (like a full export) do not block writers.
+    /// If the snapshot has not been modified this is the read-only graph itself, otherwise the
+    /// working copy is cloned once. Mutations made after the view is taken are not reflected in it.
+    pub async fn read_only_view(&self) -> Arc<WorkspaceSnapshotGraph> {
+        match self.working_copy.read().await.as_ref() {
+            Some(working_copy) => Arc::new(WorkspaceSnapshotGraph::V4(working_copy.clone())),
+            None => self.read_only_graph.clone(),
+        }
+    }
+
+    /// Returns the number of distinct nodes and edges modified through this snapshot since it was
+    /// last written. Changes made via [`Self::with_mutations`] are not tracked individually, so
+    /// after one every node and edge in the graph counts as modified. A count of zero means the
+    /// snapshot is unmodified.
+    pub async fn changed_since_last_write(&self) -> WorkspaceSnapshotResult<usize> {
+        let (changed, fully_dirty) = {
+            let dirty = self.dirty.lock().await;
+            (dirty.entries.len(), dirty.fully_dirty)
+        };
+        if fully_dirty {
+            let working_copy = self.working_copy().await;
+            return Ok(working_copy.node_count() + working_copy.graph().edge_count());
+        }
+
+        Ok(changed)
+    }
+
+    async fn mark_dirty(&self, entries: impl IntoIterator<Item = DirtyEntry>) {
+        self.dirty.lock().await.entries.extend(entries);
+    }
+
+    async fn mark_edge_dirty(
+        &self,
+        source_node_index: NodeIndex,
+        target_node_index: NodeIndex,
+        edge_kind: EdgeWeightKindDiscriminants,
+    ) -> WorkspaceSnapshotResult<()> {
+        let (source_id, target_id) = {
+            let working_copy = self.working_copy().await;
+            (
+                working_copy.get_node_weight(source_node_index)?.id(),
+                working_copy.get_node_weight(target_node_index)?.id(),
+            )
+        };
+        self.mark_dirty([DirtyEntry::Edge(source_id, target_id, edge_kind)])
+            .await;
+
+        Ok(())
+    }
+
     /// Discard all changes in the working copy and return the graph to the
     /// version fetched from the layer db
     pub async fn revert(&self) {
@@ -593,6 +734,7 @@ impl WorkspaceSnapshot {
         if working_copy.is_some() {
             *working_copy = None;
         }
+        *self.dirty.lock().await = DirtySet::default();
     }
 
     pub async fn serialized(&self) -> WorkspaceSnapshotResult<Vec<u8>> {
@@ -610,6 +752,7 @@ impl WorkspaceSnapshot {
             cycle_check: Arc::new(AtomicBool::new(false)),
             dvu_roots: Arc::new(Mutex::new(HashSet::new())),
             inferred_connection_graph: Arc::new(RwLock::new(None)),
+            dirty: Arc::new(Mutex::new(DirtySet::default())),
         })
     }
 
@@ -625,24 +768,55 @@ impl WorkspaceSnapshot {
         &self,
         node: NodeWeight,
     ) -> WorkspaceSnapshotResult<NodeIndex> {
+        let id = node.id();
         let new_node_index = self.working_copy_mut().await.add_or_replace_node(node)?;
+        self.mark_dirty([DirtyEntry::Node(id)]).await;
         Ok(new_node_index)
     }
 
+    /// Adds this node to the graph along with an ordering node for its children. Only [`Prop`]
+    /// nodes are validated here: they must be of a container [`PropKind`], since scalar props
+    /// have no children to order. Other nodes are not checked; attribute values, for example, are
+    /// not yet connected to their prop when added, so [`AttributeValue::new`] decides from the
+    /// prop's kind whether to call this.
+    ///
+    /// [`Prop`]: crate::Prop
+    /// [`AttributeValue::new`]: crate::AttributeValue::new
     pub async fn add_ordered_node(&self, node: NodeWeight) -> WorkspaceSnapshotResult<NodeIndex> {
+        if let NodeWeight::Prop(prop_node_weight) = &node {
+            let kind = prop_node_weight.kind();
+            if !kind.ordered() {
+                return Err(WorkspaceSnapshotError::NodeNotOrderable(
+                    prop_node_weight.id(),
+                    kind,
+                ));
+            }
+        }
+
+        let id = node.id();
         let new_node_index = self.working_copy_mut().await.add_ordered_node(node)?;
+        self.mark_dirty([DirtyEntry::Node(id)]).await;
         Ok(new_node_index)
     }
 
+    /// Replaces the content hash of the node with the given id. Returns
+    /// [`WorkspaceSnapshotError::NodeNotFound`] if there is no such node.
     pub async fn update_content(
         &self,
         id: Ulid,
         new_content_hash: ContentHash,
     ) -> WorkspaceSnapshotResult<()> {
-        Ok(self
-            .working_copy_mut()
-            .await
-            .update_content(id, new_content_hash)?)
+        {
+            let mut working_copy = self.working_copy_mut().await;
+            if working_copy.get_node_index_by_id_opt(id).is_none() {
+                return Err(WorkspaceSnapshotError::NodeNotFound(id));
+            }
+
+            working_copy.update_content(id, new_content_hash)?;
+        }
+        self.mark_dirty([DirtyEntry::Node(id)]).await;
+
+        Ok(())
     }
 
     #[instrument(
@@ -657,6 +831,9 @@ impl WorkspaceSnapshot {
         edge_weight: EdgeWeight,
         to_node_id: impl Into<Ulid>,
     ) -> WorkspaceSnapshotResult<()> {
+        let from_node_id = from_node_id.into();
+        let to_node_id = to_node_id.into();
+        let dirty_entry = DirtyEntry::Edge(from_node_id, to_node_id, edge_weight.kind().into());
         let from_node_index = self
             .working_copy()
             .await
@@ -674,6 +851,7 @@ impl WorkspaceSnapshot {
                 .await
                 .add_edge(from_node_index, edge_weight, to_node_index)?
         }
+        self.mark_dirty([dirty_entry]).await;
 
         Ok(())
     }
@@ -686,9 +864,12 @@ impl WorkspaceSnapshot {
         edge_weight: EdgeWeight,
         to_node_index: NodeIndex,
     ) -> WorkspaceSnapshotResult<()> {
+        let edge_kind = edge_weight.kind().into();
         self.working_copy_mut()
             .await
             .add_edge(from_node_index, edge_weight, to_node_index)?;
+        self.mark_edge_dirty(from_node_index, to_node_index, edge_kind)
+            .await?;
 
         Ok(())
     }
@@ -699,6 +880,9 @@ impl WorkspaceSnapshot {
         edge_weight: EdgeWeight,
         to_node_id: impl Into<Ulid>,
     ) -> WorkspaceSnapshotResult<()> {
+        let from_node_id = from_node_id.into();
+        let to_node_id = to_node_id.into();
+        let dirty_entry = DirtyEntry::Edge(from_node_id, to_node_id, edge_weight.kind().into());
         let from_node_index = self
             .working_copy()
             .await
@@ -709,10 +893,14 @@ impl WorkspaceSnapshot {
             edge_weight,
             to_node_index,
         )?;
+        self.mark_dirty([dirty_entry]).await;
 
         Ok(())
     }
 
+    /// Finds the updates needed to bring `self` in line with `onto_workspace_snapshot`. Both sides
+    /// are only read, so a snapshot that has not been modified is compared using its read-only
+    /// graph rather than being copied into a working copy.
     #[instrument(
         name = "workspace_snapshot.detect_updates",
         level = "debug",
@@ -754,10 +942,13 @@ impl WorkspaceSnapshot {
         component_id: ComponentId,
     ) -> WorkspaceSnapshotResult<()> {
         let component_node_index = other.read_only_graph.get_node_index_by_id(component_id)?;
-        Ok(self
-            .working_copy_mut()
+        self.working_copy_mut()
             .await
-            .import_component_subgraph(&other.read_only_graph, component_node_index)?)
+            .import_component_subgraph(&other.read_only_graph, component_node_index)?;
+        self.mark_dirty([DirtyEntry::Node(component_id.into())])
+            .await;
+
+        Ok(())
     }
 
     pub async fn get_node_weight_by_id(
@@ -772,6 +963,21 @@ impl WorkspaceSnapshot {
             .to_owned())
     }
 
+    /// Fetches the [`NodeWeights`](NodeWeight) for all of the provided ids, in order, while
+    /// holding the working copy guard only once.
+    pub async fn get_node_weights_by_ids(
+        &self,
+        ids: impl IntoIterator<Item = impl Into<Ulid>>,
+    ) -> WorkspaceSnapshotResult<Vec<NodeWeight>> {
+        let working_copy = self.working_copy().await;
+        let mut node_weights = Vec::new();
+        for id in ids {
+            let node_idx = working_copy.get_node_index_by_id(id)?;
+            node_weights.push(working_copy.get_node_weight(node_idx)?.to_owned());
+        }
+        Ok(node_weights)
+    }
+
     pub async fn get_node_weight(
         &self,
         node_index: NodeIndex,
@@ -838,6 +1044,27 @@ impl WorkspaceSnapshot {
             .collect())
     }
 
+    /// Returns the ids of every node whose weight references the given [`ContentHash`] in the
+    /// content store. Useful for tracking down content addressing issues.
+    #[instrument(
+        name = "workspace_snapshot.find_nodes_with_content_hash",
+        level = "debug",
+        skip_all,
+        fields()
+    )]
+    pub async fn find_nodes_with_content_hash(
+        &self,
+        hash: ContentHash,
+    ) -> WorkspaceSnapshotResult<Vec<Ulid>> {
+        Ok(self
+            .working_copy()
+            .await
+            .nodes()
+            .filter(|(weight, _)| weight.content_store_hashes().contains(&hash))
+            .map(|(weight, _)| weight.id())
+            .collect())
+    }
+
     #[instrument(name = "workspace_snapshot.edges", level = "debug", skip_all, fields())]
     pub async fn edges(&self) -> WorkspaceSnapshotResult<Vec<(EdgeWeight, NodeIndex, NodeIndex)>> {
         Ok(self
@@ -892,6 +1119,31 @@ impl WorkspaceSnapshot {
         self.working_copy().await.get_node_index_by_id_opt(id)
     }
 
+    /// Resolves the latest [`NodeIndex`] for the node at the provided [`NodeIndex`]. The provided
+    /// index may be stale if the node has been replaced since it was obtained.
+    pub async fn get_latest_node_index(
+        &self,
+        node_index: NodeIndex,
+    ) -> WorkspaceSnapshotResult<NodeIndex> {
+        Ok(self.working_copy().await.get_latest_node_idx(node_index)?)
+    }
+
+    /// Resolves the latest [`NodeIndex`] for each of the provided [`NodeIndices`](NodeIndex)
+    /// under a single read guard, preserving the input order. See
+    /// [`Self::get_latest_node_index`].
+    pub async fn get_latest_node_indices(
+        &self,
+        node_indices: &[NodeIndex],
+    ) -> WorkspaceSnapshotResult<Vec<NodeIndex>> {
+        let working_copy = self.working_copy().await;
+        let latest_node_indices = node_indices
+            .iter()
+            .map(|node_index| working_copy.get_latest_node_idx(*node_index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(latest_node_indices)
+    }
+
     #[instrument(name = "workspace_snapshot.find", level = "debug", skip_all, fields())]
     pub async fn find(
         ctx: &DalContext,
@@ -923,6 +1175,7 @@ impl WorkspaceSnapshot {
             cycle_check: Arc::new(AtomicBool::new(false)),
             dvu_roots: Arc::new(Mutex::new(HashSet::new())),
             inferred_connection_graph: Arc::new(RwLock::new(None)),
+            dirty: Arc::new(Mutex::new(DirtySet::default())),
         })
     }
 
@@ -997,6 +1250,130 @@ impl WorkspaceSnapshot {
             .map(|(category_node_id, _)| category_node_id))
     }
 
+    /// Returns the id of the root-level category node of the given kind, creating it (and its
+    /// edge from the root node) first if the snapshot does not have one yet. This makes adding a
+    /// new [`CategoryNodeKind`] to existing snapshots idempotent.
+    pub async fn get_or_create_category_node(
+        &self,
+        kind: CategoryNodeKind,
+    ) -> WorkspaceSnapshotResult<Ulid> {
+        // Hold the write guard throughout so that two callers cannot both create the node.
+        let mut working_copy = self.working_copy_mut().await;
+        if let Some((category_node_id, _)) = working_copy.get_category_node(None, kind)? {
+            return Ok(category_node_id);
+        }
+
+        let id = working_copy.generate_ulid()?;
+        let lineage_id = working_copy.generate_ulid()?;
+        let category_node_index = working_copy.add_category_node(id, lineage_id, kind)?;
+        let root_node_index = working_copy.root();
+        working_copy.add_edge(
+            root_node_index,
+            EdgeWeight::new(EdgeWeightKind::new_use()),
+            category_node_index,
+        )?;
+        let root_id = working_copy.get_node_weight(root_node_index)?.id();
+        drop(working_copy);
+
+        self.mark_dirty([
+            DirtyEntry::Node(id),
+            DirtyEntry::Edge(root_id, id, EdgeWeightKindDiscriminants::Use),
+        ])
+        .await;
+
+        Ok(id)
+    }
+
+    /// Checks that the graph is well-formed and returns every violation found rather than
+    /// failing on the first one:
+    ///
+    /// - every [`Prop`](crate::Prop) has a prop or schema variant parent, so each prop has a
+    ///   parent chain to its [`SchemaVariant`](crate::SchemaVariant)
+    /// - every ordered container has exactly one ordering node
+    /// - every ordering node only references nodes in the graph
+    /// - every edge connects nodes that are indexed by their ids
+    pub async fn verify_integrity(&self) -> WorkspaceSnapshotResult<Vec<IntegrityViolation>> {
+        let working_copy = self.working_copy().await;
+        let mut violations = Vec::new();
+
+        for (source_weight, source_index, target_index) in working_copy.edges() {
+            let (Some(source), Some(target)) = (
+                working_copy.get_node_weight_opt(source_index),
+                working_copy.get_node_weight_opt(target_index),
+            ) else {
+                continue;
+            };
+            if working_copy.get_node_index_by_id_opt(source.id()) != Some(source_index)
+                || working_copy.get_node_index_by_id_opt(target.id()) != Some(target_index)
+            {
+                violations.push(IntegrityViolation::DanglingEdge {
+                    source_id: source.id(),
+                    target_id: target.id(),
+                    kind: source_weight.kind().into(),
+                });
+            }
+        }
+
+        for (node_weight, node_index) in working_copy.nodes() {
+            let ordered = match node_weight {
+                NodeWeight::Prop(prop_inner) => {
+                    let prop_id = PropId::from(prop_inner.id());
+                    let parent = working_copy
+                        .edges_directed(node_index, Direction::Incoming)
+                        .find(|edge_ref| {
+                            EdgeWeightKindDiscriminants::Use == edge_ref.weight().kind().into()
+                        })
+                        .and_then(|edge_ref| working_copy.get_node_weight_opt(edge_ref.source()));
+                    match parent {
+                        Some(NodeWeight::Prop(_) | NodeWeight::SchemaVariant(_)) => {}
+                        Some(NodeWeight::Content(content_inner))
+                            if ContentAddressDiscriminants::from(
+                                content_inner.content_address(),
+                            ) == ContentAddressDiscriminants::SchemaVariant => {}
+                        Some(_) => violations.push(IntegrityViolation::PropParentInvalid(prop_id)),
+                        None => violations.push(IntegrityViolation::PropIsOrphan(prop_id)),
+                    }
+                    prop_inner.kind().ordered()
+                }
+                NodeWeight::AttributeValue(_) => {
+                    match working_copy.prop_node_index_for_node_index(node_index)? {
+                        Some(prop_index) => matches!(
+                            working_copy.get_node_weight_opt(prop_index),
+                            Some(NodeWeight::Prop(prop_inner)) if prop_inner.kind().ordered()
+                        ),
+                        None => false,
+                    }
+                }
+                NodeWeight::Ordering(ordering_inner) => {
+                    for ordered_id in ordering_inner.order() {
+                        if working_copy.get_node_index_by_id_opt(*ordered_id).is_none() {
+                            violations.push(IntegrityViolation::OrderingReferencesMissingNode {
+                                ordering_node_id: ordering_inner.id(),
+                                missing_id: *ordered_id,
+                            });
+                        }
+                    }
+                    false
+                }
+                _ => false,
+            };
+
+            match working_copy.ordering_node_index_for_container(node_index) {
+                Ok(Some(_)) => {}
+                Ok(None) if ordered => {
+                    violations.push(IntegrityViolation::MissingOrderingNode(node_weight.id()))
+                }
+                Ok(None) => {}
+                Err(WorkspaceSnapshotGraphError::TooManyOrderingForNode(_)) => {
+                    violations.push(IntegrityViolation::TooManyOrderingNodes(node_weight.id()))
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(violations)
+    }
+
     pub async fn edges_directed(
         &self,
         id: impl Into<Ulid>,
@@ -1215,6 +1592,7 @@ impl WorkspaceSnapshot {
         self.remove_all_edges(id).await?;
         self.working_copy_mut().await.remove_node(node_idx);
         self.working_copy_mut().await.remove_node_id(id);
+        self.mark_dirty([DirtyEntry::Node(id)]).await;
 
         Ok(())
     }
@@ -1225,6 +1603,8 @@ impl WorkspaceSnapshot {
         target_node_index: NodeIndex,
         edge_kind: EdgeWeightKindDiscriminants,
     ) -> WorkspaceSnapshotResult<()> {
+        self.mark_edge_dirty(source_node_index, target_node_index, edge_kind)
+            .await?;
         self.working_copy_mut().await.remove_edge(
             source_node_index,
             target_node_index,
@@ -1278,6 +1658,27 @@ impl WorkspaceSnapshot {
         fields()
     )]
     pub async fn perform_updates(&self, updates: &[Update]) -> WorkspaceSnapshotResult<()> {
+        self.mark_dirty(updates.iter().map(|update| match update {
+            Update::NewEdge {
+                source,
+                destination,
+                edge_weight,
+            } => DirtyEntry::Edge(
+                source.id.into(),
+                destination.id.into(),
+                edge_weight.kind().into(),
+            ),
+            Update::RemoveEdge {
+                source,
+                destination,
+                edge_kind,
+            } => DirtyEntry::Edge(source.id.into(), destination.id.into(), *edge_kind),
+            Update::ReplaceNode { node_weight } | Update::NewNode { node_weight } => {
+                DirtyEntry::Node(node_weight.id())
+            }
+        }))
+        .await;
+
         let self_clone = self.clone();
         let updates = updates.to_vec();
         Ok(slow_rt::spawn(async move {
@@ -1296,15 +1697,17 @@ impl WorkspaceSnapshot {
         &self,
         node_index: NodeIndex,
     ) -> WorkspaceSnapshotResult<()> {
-        self.working_copy_mut()
-            .await
-            .update_node_weight(node_index, |node_weight| match node_weight {
-                NodeWeight::Prop(prop_inner) => {
-                    prop_inner.set_can_be_used_as_prototype_arg(true);
-                    Ok(())
-                }
-                _ => Err(WorkspaceSnapshotGraphError::IncompatibleNodeTypes)?,
-            })?;
+        let mut working_copy = self.working_copy_mut().await;
+        working_copy.update_node_weight(node_index, |node_weight| match node_weight {
+            NodeWeight::Prop(prop_inner) => {
+                prop_inner.set_can_be_used_as_prototype_arg(true);
+                Ok(())
+            }
+            _ => Err(WorkspaceSnapshotGraphError::IncompatibleNodeTypes)?,
+        })?;
+        let id = working_copy.get_node_weight(node_index)?.id();
+        drop(working_copy);
+        self.mark_dirty([DirtyEntry::Node(id)]).await;
 
         Ok(())
     }
@@ -1323,10 +1726,14 @@ impl WorkspaceSnapshot {
         new_id: impl Into<Ulid>,
         new_lineage_id: LineageId,
     ) -> WorkspaceSnapshotResult<()> {
+        let current_id = current_id.into();
+        let new_id = new_id.into();
         let idx = self.get_node_index_by_id(current_id).await?;
         self.working_copy_mut()
             .await
             .update_node_id(idx, new_id, new_lineage_id)?;
+        self.mark_dirty([DirtyEntry::Node(current_id), DirtyEntry::Node(new_id)])
+            .await;
 
         Ok(())
     }
@@ -1487,25 +1894,79 @@ impl WorkspaceSnapshot {
     }
 
     pub async fn has_dependent_value_roots(&self) -> WorkspaceSnapshotResult<bool> {
+        Ok(self.dependent_value_roots_count().await? > 0)
+    }
+
+    /// Returns how many dependent value roots are waiting to be processed by a dependent values
+    /// update.
+    pub async fn dependent_value_roots_count(&self) -> WorkspaceSnapshotResult<usize> {
         Ok(
             match self
                 .get_category_node(None, CategoryNodeKind::DependentValueRoots)
                 .await?
             {
-                Some(dv_category_id) => !self
+                Some(dv_category_id) => self
                     .outgoing_targets_for_edge_weight_kind(
                         dv_category_id,
                         EdgeWeightKindDiscriminants::Use,
                     )
                     .await?
-                    .is_empty(),
-                None => false,
+                    .len(),
+                None => 0,
             },
         )
     }
 
     /// Removes all the dependent value nodes from the category and returns the value_ids
     pub async fn take_dependent_values(&self) -> WorkspaceSnapshotResult<Vec<DependentValueRoot>> {
+        let mut value_ids = vec![];
+        for (root, node_weight_id) in self.dependent_value_root_nodes().await? {
+            value_ids.push(root);
+            self.remove_node_by_id(node_weight_id).await?;
+        }
+
+        Ok(value_ids)
+    }
+
+    /// Returns the dependent value roots without removing them, so that they survive if the
+    /// caller fails before it is done with them. Pair with [`Self::remove_dependent_values`].
+    pub async fn peek_dependent_values(&self) -> WorkspaceSnapshotResult<Vec<DependentValueRoot>> {
+        Ok(self
+            .dependent_value_root_nodes()
+            .await?
+            .into_iter()
+            .map(|(root, _)| root)
+            .collect())
+    }
+
+    /// Removes one dependent value root node for each of the provided roots (typically those
+    /// returned by [`Self::peek_dependent_values`]). Roots added since, even for the same value,
+    /// are left alone.
+    pub async fn remove_dependent_values(
+        &self,
+        roots: &[DependentValueRoot],
+    ) -> WorkspaceSnapshotResult<()> {
+        // Count how many of each root to remove, since the same root can appear more than once.
+        let mut to_remove: HashMap<DependentValueRoot, usize> = HashMap::new();
+        for root in roots {
+            *to_remove.entry(*root).or_default() += 1;
+        }
+
+        for (root, node_weight_id) in self.dependent_value_root_nodes().await? {
+            if let Some(remaining) = to_remove.get_mut(&root) {
+                if *remaining > 0 {
+                    *remaining -= 1;
+                    self.remove_node_by_id(node_weight_id).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn dependent_value_root_nodes(
+        &self,
+    ) -> WorkspaceSnapshotResult<Vec<(DependentValueRoot, Ulid)>> {
         let dv_category_id = match self
             .get_category_node(None, CategoryNodeKind::DependentValueRoots)
             .await?
@@ -1516,36 +1977,25 @@ impl WorkspaceSnapshot {
             }
         };
 
-        let mut value_ids = vec![];
-        let mut pending_removes = vec![];
-
+        let mut roots = vec![];
         for dv_node_idx in self
             .outgoing_targets_for_edge_weight_kind(dv_category_id, EdgeWeightKindDiscriminants::Use)
             .await?
         {
-            let root = match self.get_node_weight(dv_node_idx).await? {
-                NodeWeight::DependentValueRoot(unfinished) => Some((
+            match self.get_node_weight(dv_node_idx).await? {
+                NodeWeight::DependentValueRoot(unfinished) => roots.push((
                     DependentValueRoot::Unfinished(unfinished.value_id()),
                     unfinished.id(),
                 )),
-                NodeWeight::FinishedDependentValueRoot(finished) => Some((
+                NodeWeight::FinishedDependentValueRoot(finished) => roots.push((
                     DependentValueRoot::Finished(finished.value_id()),
                     finished.id(),
                 )),
-                _ => None,
-            };
-
-            if let Some((root, node_weight_id)) = root {
-                value_ids.push(root);
-                pending_removes.push(node_weight_id);
+                _ => {}
             }
         }
 
-        for to_remove_id in pending_removes {
-            self.remove_node_by_id(to_remove_id).await?;
-        }
-
-        Ok(value_ids)
+        Ok(roots)
     }
 
     /// List all the `value_ids` from the dependent value nodes in the category.
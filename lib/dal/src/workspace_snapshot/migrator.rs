@@ -11,8 +11,8 @@ use crate::workspace_snapshot::migrator::v4::migrate_v3_to_v4;
 use crate::workspace_snapshot::node_weight::NodeWeightError;
 use crate::{
     workspace_snapshot::migrator::{v2::migrate_v1_to_v2, v3::migrate_v2_to_v3},
-    ChangeSet, ChangeSetError, ChangeSetStatus, DalContext, TransactionsError, Visibility,
-    Workspace, WorkspaceError, WorkspaceSnapshot, WorkspaceSnapshotError,
+    ChangeSet, ChangeSetError, ChangeSetId, ChangeSetStatus, DalContext, TransactionsError,
+    Visibility, Workspace, WorkspaceError, WorkspaceSnapshot, WorkspaceSnapshotError,
 };
 use si_events::WorkspaceSnapshotAddress;
 use si_layer_cache::LayerDbError;
@@ -75,11 +75,21 @@ impl SnapshotGraphMigrator {
         )
     }
 
+    /// Migrates the snapshots of every open change set to the current graph version.
+    ///
+    /// Change sets whose snapshots cannot be read are marked as
+    /// [`Failed`](ChangeSetStatus::Failed) rather than aborting the migration, and their ids are
+    /// returned so that the caller can decide whether a partial migration is acceptable.
     #[instrument(skip(self, ctx))]
-    pub async fn migrate_all(&mut self, ctx: &DalContext) -> SnapshotGraphMigratorResult<()> {
+    pub async fn migrate_all(
+        &mut self,
+        ctx: &DalContext,
+    ) -> SnapshotGraphMigratorResult<Vec<ChangeSetId>> {
+        let mut failed_change_set_ids = Vec::new();
+
         if !self.should_migrate(ctx).await? {
             debug!("Builtin workspace has been migrated. Not migrating snapshots to the latest");
-            return Ok(());
+            return Ok(failed_change_set_ids);
         }
 
         let open_change_sets = ChangeSet::list_open_for_all_workspaces(ctx).await?;
@@ -124,6 +134,7 @@ impl SnapshotGraphMigrator {
                         change_set
                             .update_status(ctx, ChangeSetStatus::Failed)
                             .await?;
+                        failed_change_set_ids.push(change_set.id);
                         continue;
                     } else {
                         return Err(err)?;
@@ -156,7 +167,7 @@ impl SnapshotGraphMigrator {
         )
         .await?;
 
-        Ok(())
+        Ok(failed_change_set_ids)
     }
 
     #[instrument(skip(self, ctx))]
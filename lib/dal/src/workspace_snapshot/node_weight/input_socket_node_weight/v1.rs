@@ -95,9 +95,10 @@ impl InputSocketNodeWeightV1 {
 
                 (v2_content, old_content.arity)
             }
-            // InputSocketContent::V2 was never stored inside a NodeWeight::Content, and doesn't
-            // have all the required information on its own to generate an InputSocketNodeWeight.
-            InputSocketContent::V2(_) => {
+            // InputSocketContent::V2 and V3 were never stored inside a NodeWeight::Content, and
+            // don't have all the required information on their own to generate an
+            // InputSocketNodeWeight.
+            InputSocketContent::V2(_) | InputSocketContent::V3(_) => {
                 return Err(InputSocketNodeWeightError::InvalidContentForNodeWeight(
                     content_node_weight.id(),
                 ));
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use telemetry::prelude::*;
 
 use crate::{
-    layer_db_types::{InputSocketContent, InputSocketContentV2},
+    layer_db_types::{InputSocketContent, InputSocketContentV2, InputSocketContentV3},
     socket::{connection_annotation::ConnectionAnnotation, input::InputSocketResult},
     workspace_snapshot::{
         graph::{InputSocketExt as InputSocketExtGraph, LineageId},
@@ -102,7 +102,7 @@ impl InputSocketExt for WorkspaceSnapshot {
             vec![ConnectionAnnotation::try_from(name.clone()).map_err(Box::new)?]
         };
 
-        let content = InputSocketContentV2 {
+        let content = InputSocketContentV3 {
             timestamp: Timestamp::now(),
             name: name.clone(),
             inbound_type_definition: None,
@@ -111,9 +111,10 @@ impl InputSocketExt for WorkspaceSnapshot {
             required: false,
             ui_hidden: false,
             connection_annotations,
+            node_side: None,
         };
         let (hash, _) = ctx.layer_db().cas().write(
-            Arc::new(InputSocketContent::V2(content.clone()).into()),
+            Arc::new(InputSocketContent::V3(content.clone()).into()),
             None,
             ctx.events_tenancy(),
             ctx.events_actor(),
@@ -141,7 +142,7 @@ impl InputSocketExt for WorkspaceSnapshot {
 
         let input_socket = input_socket_from_node_weight_and_content(
             &input_socket_node_weight,
-            InputSocketContent::V2(content),
+            InputSocketContent::V3(content),
         )
         .map_err(Box::new)?;
 
@@ -275,13 +276,16 @@ fn input_socket_from_node_weight_and_content(
                 connection_annotations: v1_inner.connection_annotations.clone(),
             };
 
-            InputSocket::assemble(node_weight.id().into(), v1_inner.arity, v2_inner)
+            InputSocket::assemble(node_weight.id().into(), v1_inner.arity, v2_inner.into())
         }
         InputSocketContent::V2(inner) => InputSocket::assemble(
             node_weight.id().into(),
             node_weight.inner().arity(),
-            inner.clone(),
+            inner.into(),
         ),
+        InputSocketContent::V3(inner) => {
+            InputSocket::assemble(node_weight.id().into(), node_weight.inner().arity(), inner)
+        }
     };
 
     Ok(input_socket)
@@ -25,6 +25,9 @@ use crate::func::runner::FuncRunLogUpdatedPayload;
 use crate::func::{
     FuncWsEventCodeSaved, FuncWsEventFuncSummary, FuncWsEventGenerating, FuncWsEventPayload,
 };
+use crate::job::definition::dependent_values_update::{
+    DependentValuesCyclePayload, DependentValuesUpdateFinishedPayload,
+};
 use crate::management::prototype::{
     ManagementFuncExecutedPayload, ManagementOperationsCompletePayload,
 };
@@ -103,6 +106,8 @@ pub enum WsPayload {
     ConnectionDeleted(ConnectionDeletedPayload),
     ConnectionUpserted(ConnectionUpsertedPayload),
     Cursor(CursorPayload),
+    DependentValuesCycleDetected(DependentValuesCyclePayload),
+    DependentValuesUpdateFinished(DependentValuesUpdateFinishedPayload),
     FuncArgumentsSaved(FuncWsEventPayload),
     FuncCodeSaved(FuncWsEventCodeSaved),
     FuncCreated(FuncWsEventFuncSummary),
@@ -28,3 +28,40 @@ async fn arguments_for_prototype_function_execution(ctx: &mut DalContext) -> Res
     );
     Ok(())
 }
+
+#[test]
+async fn dependents(ctx: &mut DalContext) -> Result<()> {
+    // The test exclusive schema sets "/root/domain/name" from "/root/si/name" via the identity
+    // function, so the former depends on the latter.
+    let component = ExpectComponent::create_named(ctx, "swifty", "dependents").await;
+    let si_name_av_id = component
+        .prop(ctx, ["root", "si", "name"])
+        .await
+        .attribute_value(ctx)
+        .await
+        .id();
+    let domain_name_av_id = component
+        .prop(ctx, ["root", "domain", "name"])
+        .await
+        .attribute_value(ctx)
+        .await
+        .id();
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx).await?;
+
+    let roots_before = ctx
+        .workspace_snapshot()?
+        .dependent_value_roots_count()
+        .await?;
+    let dependents = AttributeValue::dependents(ctx, si_name_av_id).await?;
+    assert!(dependents.contains(&domain_name_av_id));
+    assert!(!dependents.contains(&si_name_av_id));
+
+    // Previewing dependents must not enqueue anything.
+    assert_eq!(
+        roots_before, // expected
+        ctx.workspace_snapshot()?
+            .dependent_value_roots_count()
+            .await?  // actual
+    );
+    Ok(())
+}
@@ -1,6 +1,6 @@
-use audit_database::AuditDatabaseContext;
+use audit_database::{AuditDatabaseContext, AuditLogRow};
 use audit_logs_stream::AuditLogsStream;
-use dal::{prop::PropPath, AttributeValue, DalContext, Prop, Schema, SchemaVariant};
+use dal::{audit_logging, prop::PropPath, AttributeValue, DalContext, Prop, Schema, SchemaVariant};
 use dal_test::helpers::{
     confirm_jetstream_stream_has_no_messages,
     create_named_component_for_schema_variant_on_default_view,
@@ -10,6 +10,7 @@ use dal_test::{helpers::ChangeSetTestHelpers, test};
 use pending_events::PendingEventsStream;
 use pretty_assertions_sorted::assert_eq;
 use si_events::audit_log::AuditLogKind;
+use si_events::{Actor, MachinePk};
 
 const DATABASE_RETRY_TIMEOUT_SECONDS: u64 = 2;
 const DATABASE_RETRY_INTERVAL_MILLISECONDS: u64 = 100;
@@ -240,3 +241,92 @@ async fn round_trip(ctx: &mut DalContext, audit_database_context: AuditDatabaseC
         .expect("could not list audit logs");
     }
 }
+
+#[test]
+async fn list_paginated(ctx: &DalContext, audit_database_context: AuditDatabaseContext) {
+    let context = audit_database_context;
+    let workspace_id = ctx.workspace_pk().expect("could not get workspace pk");
+
+    let entity_names: Vec<String> = (0..5).map(|index| format!("page entity {index}")).collect();
+    for entity_name in &entity_names {
+        AuditLogRow::insert(
+            &context,
+            workspace_id,
+            AuditLogKind::ApplyChangeSet,
+            chrono::Utc::now().to_rfc3339(),
+            Some(ctx.change_set_id()),
+            Actor::System,
+            Some(entity_name.to_owned()),
+        )
+        .await
+        .expect("could not insert audit log");
+    }
+
+    // A zero limit must not return an empty page, which would look like the end of the history.
+    let (page, next_before) = audit_logging::list_paginated(ctx, &context, None, 0)
+        .await
+        .expect("could not list audit logs");
+    assert_eq!(
+        1,          // expected
+        page.len()  // actual
+    );
+    assert!(next_before.is_some());
+
+    // Walk every page, which must come back newest first and never exceed the limit.
+    let mut listed = Vec::new();
+    let mut before = None;
+    loop {
+        let (page, next_before) = audit_logging::list_paginated(ctx, &context, before, 2)
+            .await
+            .expect("could not list audit logs");
+        assert!(page.len() <= 2);
+        listed.extend(page);
+        match next_before {
+            Some(next_before) => before = Some(next_before),
+            None => break,
+        }
+    }
+    assert!(listed.windows(2).all(|pair| pair[0].pk > pair[1].pk));
+
+    let listed_entity_names: Vec<String> = listed
+        .into_iter()
+        .filter_map(|row| row.entity_name)
+        .filter(|entity_name| entity_names.contains(entity_name))
+        .collect();
+    assert_eq!(
+        entity_names.into_iter().rev().collect::<Vec<_>>(), // expected
+        listed_entity_names                                 // actual
+    );
+}
+
+#[test]
+async fn machine_actor_round_trip(ctx: &DalContext, audit_database_context: AuditDatabaseContext) {
+    let context = audit_database_context;
+    let workspace_id = ctx.workspace_pk().expect("could not get workspace pk");
+
+    let machine_id = MachinePk::new();
+    AuditLogRow::insert(
+        &context,
+        workspace_id,
+        AuditLogKind::ApplyChangeSet,
+        chrono::Utc::now().to_rfc3339(),
+        Some(ctx.change_set_id()),
+        Actor::Machine(machine_id),
+        Some("machine entity".to_string()),
+    )
+    .await
+    .expect("could not insert audit log");
+
+    let (page, _) = audit_logging::list_paginated(ctx, &context, None, 1)
+        .await
+        .expect("could not list audit logs");
+    let row = page.first().expect("no audit log found");
+    assert_eq!(
+        (None, Some(machine_id)),      // expected
+        (row.user_id, row.machine_id)  // actual
+    );
+    assert_eq!(
+        Actor::Machine(machine_id), // expected
+        row.actor()                 // actual
+    );
+}
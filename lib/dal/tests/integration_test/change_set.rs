@@ -1,16 +1,22 @@
+use dal::action::Action;
 use dal::change_set::view::OpenChangeSetsView;
 use dal::{
     context::TransactionsErrorDiscriminants, DalContext, DalContextBuilder, HistoryActor,
     RequestContext, Workspace, WorkspacePk,
 };
-use dal::{ChangeSet, ChangeSetStatus, Component};
+use dal::{
+    ChangeSet, ChangeSetApplyError, ChangeSetError, ChangeSetStatus, Component,
+    WorkspaceSnapshotAddress,
+};
 use dal_test::helpers::{
     create_component_for_default_schema_name_in_default_view, create_user, ChangeSetTestHelpers,
 };
 use dal_test::test;
+use futures::StreamExt;
 use itertools::Itertools;
 use pretty_assertions_sorted::assert_eq;
 use std::collections::HashSet;
+use std::time::Duration;
 
 #[test]
 async fn open_change_sets(ctx: &mut DalContext) {
@@ -474,3 +480,462 @@ async fn change_set_approval_flow(ctx: &mut DalContext) {
         .collect_vec();
     assert_eq!(components.len(), 2);
 }
+
+#[test]
+async fn duplicate_change_set(ctx: &mut DalContext) {
+    let source_change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "small")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update");
+
+    // Duplicate the change set and make sure it shares the base, but not the row.
+    let duplicate_change_set = ChangeSet::duplicate(ctx, "duplicate")
+        .await
+        .expect("could not duplicate change set");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update");
+    assert_ne!(source_change_set.id, duplicate_change_set.id);
+    assert_eq!(
+        source_change_set.base_change_set_id,    // expected
+        duplicate_change_set.base_change_set_id  // actual
+    );
+    assert_eq!(ChangeSetStatus::Open, duplicate_change_set.status);
+
+    // Ensure the duplicate sees the component created in the source.
+    ctx.update_visibility_and_snapshot_to_visibility(duplicate_change_set.id)
+        .await
+        .expect("could not update visibility");
+    let components = Component::list(ctx)
+        .await
+        .expect("could not list components");
+    assert_eq!(
+        vec![component.id()],                            // expected
+        components.iter().map(|c| c.id()).collect_vec()  // actual
+    );
+
+    // Apply the duplicate and ensure the source is left intact.
+    ChangeSetTestHelpers::apply_change_set_to_base(ctx)
+        .await
+        .expect("could not apply change set");
+    let source_change_set = ChangeSet::find(ctx, source_change_set.id)
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+    assert_eq!(ChangeSetStatus::Open, source_change_set.status);
+}
+
+#[test]
+async fn fork_from_non_head_change_set(ctx: &mut DalContext) {
+    let base_change_set_id = ctx.change_set_id();
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "small odd lego", "small")
+            .await
+            .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update");
+
+    let forked_change_set = ChangeSet::fork_from(ctx, base_change_set_id, "forked")
+        .await
+        .expect("could not fork change set");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update");
+    assert_eq!(
+        Some(base_change_set_id),             // expected
+        forked_change_set.base_change_set_id  // actual
+    );
+    assert_eq!(ChangeSetStatus::Open, forked_change_set.status);
+
+    // The fork starts from the base's snapshot, so it sees the component created in the base.
+    ctx.update_visibility_and_snapshot_to_visibility(forked_change_set.id)
+        .await
+        .expect("could not update visibility");
+    let components = Component::list(ctx)
+        .await
+        .expect("could not list components");
+    assert_eq!(
+        vec![component.id()],                            // expected
+        components.iter().map(|c| c.id()).collect_vec()  // actual
+    );
+}
+
+#[test]
+async fn status_transition_publishes_status_changed_event(ctx: &mut DalContext) {
+    let mut change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+    assert_eq!(ChangeSetStatus::Open, change_set.status);
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk_opt()
+        .expect("could not get workspace pk");
+    let mut subscriber = ctx
+        .nats_conn()
+        .subscribe(format!("si.workspace_pk.{workspace_pk}.event"))
+        .await
+        .expect("could not subscribe");
+
+    change_set
+        .begin_approval_flow(ctx)
+        .await
+        .expect("could not begin approval flow");
+    ctx.commit().await.expect("could not commit");
+
+    let status_changed = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(message) = subscriber.next().await {
+            let event: serde_json::Value =
+                serde_json::from_slice(message.payload()).expect("could not deserialize event");
+            if event["payload"]["kind"] == "ChangeSetStatusChanged" {
+                return Some(event["payload"]["data"].clone());
+            }
+        }
+        None
+    })
+    .await
+    .expect("timed out waiting for status changed event")
+    .expect("subscription closed before status changed event");
+
+    assert_eq!(
+        serde_json::json!("Open"),    // expected
+        status_changed["fromStatus"]  // actual
+    );
+    assert_eq!(
+        serde_json::json!(change_set.id.to_string()), // expected
+        status_changed["changeSet"]["id"]             // actual
+    );
+    assert_eq!(
+        serde_json::json!("NeedsApproval"),    // expected
+        status_changed["changeSet"]["status"]  // actual
+    );
+}
+
+#[test]
+async fn each_status_transition_publishes_one_status_changed_event(ctx: &mut DalContext) {
+    let mut change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk_opt()
+        .expect("could not get workspace pk");
+    let mut subscriber = ctx
+        .nats_conn()
+        .subscribe(format!("si.workspace_pk.{workspace_pk}.event"))
+        .await
+        .expect("could not subscribe");
+
+    change_set
+        .begin_approval_flow(ctx)
+        .await
+        .expect("could not begin approval flow");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .cancel_approval_flow(ctx)
+        .await
+        .expect("could not cancel approval flow");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .begin_abandon_approval_flow(ctx)
+        .await
+        .expect("could not begin abandon approval flow");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .cancel_abandon_approval_flow(ctx)
+        .await
+        .expect("could not cancel abandon approval flow");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .request_change_set_approval(ctx)
+        .await
+        .expect("could not request approval");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .reject_change_set_for_apply(ctx)
+        .await
+        .expect("could not reject change set");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .reopen_change_set(ctx)
+        .await
+        .expect("could not reopen change set");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .request_change_set_approval(ctx)
+        .await
+        .expect("could not request approval");
+    ctx.commit().await.expect("could not commit");
+    change_set
+        .approve_change_set_for_apply(ctx)
+        .await
+        .expect("could not approve change set");
+    ctx.commit().await.expect("could not commit");
+
+    // Collect events until the subject has been quiet for a while.
+    let mut transitions = Vec::new();
+    while let Ok(Some(message)) =
+        tokio::time::timeout(Duration::from_secs(1), subscriber.next()).await
+    {
+        let event: serde_json::Value =
+            serde_json::from_slice(message.payload()).expect("could not deserialize event");
+        if event["payload"]["kind"] == "ChangeSetStatusChanged" {
+            let data = &event["payload"]["data"];
+            transitions.push((
+                data["fromStatus"].clone(),
+                data["changeSet"]["status"].clone(),
+            ));
+        }
+    }
+
+    assert_eq!(
+        vec![
+            (
+                serde_json::json!("Open"),
+                serde_json::json!("NeedsApproval")
+            ),
+            (
+                serde_json::json!("NeedsApproval"),
+                serde_json::json!("Open")
+            ),
+            (
+                serde_json::json!("Open"),
+                serde_json::json!("NeedsAbandonApproval")
+            ),
+            (
+                serde_json::json!("NeedsAbandonApproval"),
+                serde_json::json!("Open")
+            ),
+            (
+                serde_json::json!("Open"),
+                serde_json::json!("NeedsApproval")
+            ),
+            (
+                serde_json::json!("NeedsApproval"),
+                serde_json::json!("Rejected")
+            ),
+            (serde_json::json!("Rejected"), serde_json::json!("Open")),
+            (
+                serde_json::json!("Open"),
+                serde_json::json!("NeedsApproval")
+            ),
+            (
+                serde_json::json!("NeedsApproval"),
+                serde_json::json!("Approved")
+            ),
+        ], // expected
+        transitions // actual
+    );
+}
+
+#[test]
+async fn create_editing_change_set(ctx: &mut DalContext) {
+    let base_change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+
+    let editing_change_set = base_change_set
+        .create_editing(ctx)
+        .await
+        .expect("could not create editing change set");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update");
+
+    let found_change_set = ChangeSet::find(ctx, editing_change_set.id)
+        .await
+        .expect("could not find change set")
+        .expect("editing change set is some");
+    assert_eq!(
+        Some(base_change_set.id),            // expected
+        found_change_set.base_change_set_id  // actual
+    );
+    assert_eq!(
+        base_change_set.workspace_snapshot_address,  // expected
+        found_change_set.workspace_snapshot_address  // actual
+    );
+    assert_eq!(ChangeSetStatus::Open, found_change_set.status);
+}
+
+#[test]
+async fn new_rejects_nil_snapshot_address(ctx: &mut DalContext) {
+    let change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+
+    let result = ChangeSet::new(
+        ctx,
+        "nil",
+        Some(change_set.id),
+        WorkspaceSnapshotAddress::nil(),
+    )
+    .await;
+    assert!(matches!(result, Err(ChangeSetError::NilSnapshotAddress)));
+
+    ChangeSet::new(
+        ctx,
+        "real",
+        Some(change_set.id),
+        change_set.workspace_snapshot_address,
+    )
+    .await
+    .expect("could not create change set with a real snapshot address");
+}
+
+#[test]
+async fn apply_onto_abandoned_base_is_rejected(ctx: &mut DalContext) {
+    let mut base_change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+    let child_change_set = base_change_set
+        .create_editing(ctx)
+        .await
+        .expect("could not create child change set");
+    base_change_set
+        .update_status(ctx, ChangeSetStatus::Abandoned)
+        .await
+        .expect("could not abandon base change set");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update");
+
+    ctx.update_visibility_and_snapshot_to_visibility(child_change_set.id)
+        .await
+        .expect("could not update visibility");
+    let result = ChangeSet::apply_to_base_change_set(ctx).await;
+    assert!(matches!(
+        result,
+        Err(ChangeSetApplyError::BaseNotOpen(change_set_id)) if change_set_id == base_change_set.id
+    ));
+}
+
+#[test]
+async fn status_update_records_last_activity(ctx: &mut DalContext) {
+    let mut change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+    let before = change_set.last_activity_at();
+
+    change_set
+        .update_status(ctx, ChangeSetStatus::NeedsApproval)
+        .await
+        .expect("could not update status");
+    assert!(change_set.last_activity_at() > before);
+
+    let found_change_set = ChangeSet::find(ctx, change_set.id)
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+    assert_eq!(
+        change_set.last_activity_at(),       // expected
+        found_change_set.last_activity_at()  // actual
+    );
+}
+
+#[test]
+async fn list_stale_change_sets(ctx: &mut DalContext) {
+    let change_set_id = ctx.change_set_id();
+    let head_change_set_id = ctx
+        .get_workspace_default_change_set_id()
+        .await
+        .expect("could not get default change set id");
+
+    let stale_ids: HashSet<_> = ChangeSet::list_stale(ctx, Duration::ZERO)
+        .await
+        .expect("could not list stale change sets")
+        .into_iter()
+        .map(|change_set| change_set.id)
+        .collect();
+    assert!(stale_ids.contains(&change_set_id));
+    assert!(
+        !stale_ids.contains(&head_change_set_id),
+        "the default change set is never stale"
+    );
+
+    let stale_ids: HashSet<_> = ChangeSet::list_stale(ctx, Duration::from_secs(60 * 60))
+        .await
+        .expect("could not list stale change sets")
+        .into_iter()
+        .map(|change_set| change_set.id)
+        .collect();
+    assert!(!stale_ids.contains(&change_set_id));
+}
+
+#[test]
+async fn apply_returns_enqueued_action_ids(ctx: &mut DalContext) {
+    create_component_for_default_schema_name_in_default_view(ctx, "swifty", "jack antonoff")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let mut expected_action_ids = Action::list_topologically(ctx)
+        .await
+        .expect("could not list actions");
+    assert!(!expected_action_ids.is_empty());
+
+    let (applied_change_set, mut enqueued_action_ids) = ChangeSet::apply_to_base_change_set(ctx)
+        .await
+        .expect("could not apply change set");
+    assert_eq!(
+        ctx.get_workspace_default_change_set_id()
+            .await
+            .expect("could not get default change set id"), // expected
+        applied_change_set
+            .base_change_set_id
+            .expect("no base change set") // actual
+    );
+
+    expected_action_ids.sort();
+    enqueued_action_ids.sort();
+    assert_eq!(
+        expected_action_ids, // expected
+        enqueued_action_ids  // actual
+    );
+}
+
+#[test]
+async fn ancestry_walks_to_head(ctx: &mut DalContext) {
+    let head_change_set_id = ctx
+        .get_workspace_default_change_set_id()
+        .await
+        .expect("could not get default change set id");
+    let change_set = ChangeSet::find(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find change set")
+        .expect("change set is some");
+    let child_change_set = change_set
+        .create_editing(ctx)
+        .await
+        .expect("could not create child change set");
+
+    assert_eq!(
+        vec![child_change_set.id, change_set.id, head_change_set_id], // expected
+        ChangeSet::ancestry(ctx, child_change_set.id)
+            .await
+            .expect("could not get ancestry")  // actual
+    );
+    assert_eq!(
+        vec![head_change_set_id], // expected
+        ChangeSet::ancestry(ctx, head_change_set_id)
+            .await
+            .expect("could not get ancestry")  // actual
+    );
+}
@@ -844,3 +844,42 @@ async fn paste_component_with_dependent_value(ctx: &mut DalContext) {
         downstream_copy.view(ctx).await,
     );
 }
+
+#[test]
+async fn list_components_for_schema(ctx: &mut DalContext) {
+    let first = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "first")
+        .await
+        .expect("could not create component");
+    let second = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "second")
+        .await
+        .expect("could not create component");
+    let other = create_component_for_default_schema_name_in_default_view(ctx, "starfield", "other")
+        .await
+        .expect("could not create component");
+
+    let swifty_schema_id = Schema::find_by_name(ctx, "swifty")
+        .await
+        .expect("could not find schema")
+        .expect("schema not found")
+        .id();
+
+    let mut component_ids: Vec<_> = Component::list_for_schema(ctx, swifty_schema_id)
+        .await
+        .expect("could not list components")
+        .into_iter()
+        .map(|component| component.id())
+        .collect();
+    component_ids.sort();
+    let mut expected_ids = vec![first.id(), second.id()];
+    expected_ids.sort();
+    assert_eq!(
+        expected_ids,  // expected
+        component_ids  // actual
+    );
+    assert!(!component_ids.contains(&other.id()));
+
+    let count = Component::count_for_schema(ctx, swifty_schema_id)
+        .await
+        .expect("could not count components");
+    assert_eq!(2, count);
+}
@@ -1,13 +1,21 @@
+use std::time::Duration;
+
+use dal::attribute::value::DependentValueGraph;
 use dal::component::resource::ResourceData;
+use dal::job::consumer::{JobCompletionState, JobConsumer};
+use dal::job::definition::DependentValuesUpdate;
 use dal::{
     AttributeValue, Component, DalContext, InputSocket, OutputSocket, Schema, SchemaVariant,
 };
-use dal_test::expected::{self, ExpectComponent};
+use dal_test::expected::{self, ExpectComponent, ExpectComponentProp};
 use dal_test::helpers::{
     create_named_component_for_schema_variant_on_default_view, ChangeSetTestHelpers,
 };
 use dal_test::test;
+use futures::StreamExt;
 use serde_json::json;
+use si_data_nats::Subscriber;
+use tokio_util::sync::CancellationToken;
 use veritech_client::ResourceStatus;
 
 #[test]
@@ -519,3 +527,597 @@ async fn component_concurrency_limit(ctx: &mut DalContext) {
         )
     }
 }
+
+#[test]
+async fn max_iterations_guard_leaves_unfinished_roots(ctx: &mut DalContext) {
+    // The wormholes make for a long chain of dependent values from the etoile to the morningstar.
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    let rigid_designator = etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    let job =
+        DependentValuesUpdate::new_with_max_iterations(ctx.access_builder(), *ctx.visibility(), 1);
+    let completion_state = job
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+    assert!(matches!(completion_state, JobCompletionState::Done));
+    assert!(
+        ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("has dependent value roots"),
+        "the guard should leave the remaining values as roots"
+    );
+
+    // A regular run picks up where the guarded run left off.
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+    assert!(
+        !ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("has dependent value roots"),
+        "all dvu roots should be processed and removed"
+    );
+    let stars = morningstar.prop(ctx, ["root", "domain", "stars"]).await;
+    assert_eq!(json!("phosphorus"), stars.get(ctx).await);
+}
+
+#[test]
+async fn dependent_value_roots_count(ctx: &mut DalContext) {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+    assert_eq!(
+        0, // expected
+        ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .dependent_value_roots_count()
+            .await
+            .expect("count dependent value roots")  // actual
+    );
+
+    etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await
+        .set(ctx, "hesperus")
+        .await;
+    let one_change = ctx
+        .workspace_snapshot()
+        .expect("workspace_snapshot")
+        .dependent_value_roots_count()
+        .await
+        .expect("count dependent value roots");
+    assert!(one_change > 0, "setting a value should add a root");
+
+    morningstar
+        .prop(ctx, ["root", "domain", "stars"])
+        .await
+        .set(ctx, "venus")
+        .await;
+    assert_eq!(
+        one_change + 1, // expected
+        ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .dependent_value_roots_count()
+            .await
+            .expect("count dependent value roots")  // actual
+    );
+
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+    assert_eq!(
+        0, // expected
+        ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .dependent_value_roots_count()
+            .await
+            .expect("count dependent value roots")  // actual
+    );
+}
+
+#[test]
+async fn peek_dependent_values_leaves_roots(ctx: &mut DalContext) {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await
+        .set(ctx, "hesperus")
+        .await;
+
+    let snap = ctx.workspace_snapshot().expect("workspace_snapshot");
+    let peeked = snap
+        .peek_dependent_values()
+        .await
+        .expect("peek dependent values");
+    assert!(!peeked.is_empty(), "setting a value should add a root");
+    assert_eq!(
+        peeked.len(), // expected
+        snap.dependent_value_roots_count()
+            .await
+            .expect("count dependent value roots")  // actual
+    );
+
+    // Roots added after peeking are not removed with the peeked ones.
+    morningstar
+        .prop(ctx, ["root", "domain", "stars"])
+        .await
+        .set(ctx, "venus")
+        .await;
+    snap.remove_dependent_values(&peeked)
+        .await
+        .expect("remove dependent values");
+    assert_eq!(
+        1, // expected
+        snap.dependent_value_roots_count()
+            .await
+            .expect("count dependent value roots")  // actual
+    );
+}
+
+#[test]
+async fn component_concurrency_limit_override(ctx: &mut DalContext) {
+    // With a workspace limit of one, a run cannot start a second component.
+    let mut workspace = ctx.get_workspace().await.expect("get workspace");
+    workspace
+        .set_component_concurrency_limit(ctx, Some(1))
+        .await
+        .expect("set concurrency limit");
+    ctx.commit_no_rebase().await.expect("commit");
+
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    // Components with limits of their own are only held to those, so they are not held back
+    // behind the workspace limit.
+    for (component, limit) in [(etoiles, 1), (morningstar, 5)] {
+        component
+            .component(ctx)
+            .await
+            .set_concurrency_limit(ctx, Some(limit))
+            .await
+            .expect("set component concurrency limit");
+    }
+    assert_eq!(
+        Some(1),                                          // expected
+        etoiles.component(ctx).await.concurrency_limit()  // actual
+    );
+    assert_eq!(
+        Some(5),                                              // expected
+        morningstar.component(ctx).await.concurrency_limit()  // actual
+    );
+
+    let rigid_designator = etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    let job = DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility());
+    let completion_state = job
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+    assert!(matches!(completion_state, JobCompletionState::Done));
+    assert!(
+        !ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("has dependent value roots"),
+        "a single run should get through both components"
+    );
+    assert_eq!(
+        json!("phosphorus"),
+        morningstar
+            .prop(ctx, ["root", "domain", "stars"])
+            .await
+            .get(ctx)
+            .await
+    );
+
+    // Clearing an override puts the component back under the workspace limit.
+    let morningstar_component = morningstar
+        .component(ctx)
+        .await
+        .set_concurrency_limit(ctx, None)
+        .await
+        .expect("clear component concurrency limit");
+    assert_eq!(None, morningstar_component.concurrency_limit());
+}
+
+#[test]
+async fn cancelled_run_leaves_unfinished_roots(ctx: &mut DalContext) {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    let rigid_designator = etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let job = DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility())
+        .with_cancellation_token(token);
+    let completion_state = job
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+    assert!(matches!(completion_state, JobCompletionState::Cancelled));
+    assert!(
+        ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("has dependent value roots"),
+        "the cancelled run should leave the remaining values as roots"
+    );
+
+    // A regular run picks up where the cancelled run left off.
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+    assert!(
+        !ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("has dependent value roots"),
+        "all dvu roots should be processed and removed"
+    );
+    let stars = morningstar.prop(ctx, ["root", "domain", "stars"]).await;
+    assert_eq!(json!("phosphorus"), stars.get(ctx).await);
+}
+
+#[test]
+async fn cancelling_mid_run_stops_waiting_on_executions(ctx: &mut DalContext) {
+    let rigid_designator = create_kripke_chain(ctx).await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    // Cancel as soon as the first function execution has started, while the job is waiting on it.
+    let token = CancellationToken::new();
+    let mut subscriber = subscribe_to_workspace_events(ctx).await;
+    let canceller = tokio::spawn({
+        let token = token.clone();
+        async move {
+            while let Some(message) = subscriber.next().await {
+                let event: serde_json::Value =
+                    serde_json::from_slice(message.payload()).expect("could not deserialize event");
+                if event["payload"]["kind"] == "StatusUpdate"
+                    && event["payload"]["data"]["status"] == "statusStarted"
+                {
+                    token.cancel();
+                    return;
+                }
+            }
+        }
+    });
+
+    let job = DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility())
+        .with_cancellation_token(token);
+    let completion_state = tokio::time::timeout(Duration::from_secs(30), job.run(ctx))
+        .await
+        .expect("timed out waiting for the cancelled run")
+        .expect("could not run dependent values update");
+    canceller.await.expect("could not join canceller");
+    assert!(matches!(completion_state, JobCompletionState::Cancelled));
+    assert!(
+        ctx.workspace_snapshot()
+            .expect("workspace_snapshot")
+            .has_dependent_value_roots()
+            .await
+            .expect("has dependent value roots"),
+        "the cancelled run should leave the remaining values as roots"
+    );
+}
+
+#[test]
+async fn oversized_results_are_rejected(ctx: &mut DalContext) {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    let rigid_designator = etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    let stars = morningstar.prop(ctx, ["root", "domain", "stars"]).await;
+    let stars_before = stars.get(ctx).await;
+
+    // Every function result on the way to "phosphorus" is bigger than four bytes once
+    // serialized, so the run is rejected and the value is left untouched.
+    let job =
+        DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility()).with_max_result_size(4);
+    let completion_state = job
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+    assert!(matches!(completion_state, JobCompletionState::Done));
+    assert_eq!(
+        stars_before,         // expected
+        stars.get(ctx).await  // actual
+    );
+}
+
+#[test]
+async fn results_within_the_size_limit_are_accepted(ctx: &mut DalContext) {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    let rigid_designator = etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    // The results in this chain are all well under four kilobytes.
+    let job = DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility())
+        .with_max_result_size(4096);
+    let completion_state = job
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+    assert!(matches!(completion_state, JobCompletionState::Done));
+
+    let stars = morningstar.prop(ctx, ["root", "domain", "stars"]).await;
+    assert_eq!(
+        json!("phosphorus"),  // expected
+        stars.get(ctx).await  // actual
+    );
+}
+
+#[test]
+async fn finished_event_counts_failures(ctx: &mut DalContext) {
+    let rigid_designator = create_kripke_chain(ctx).await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    let mut subscriber = subscribe_to_workspace_events(ctx).await;
+
+    // "hesperus" fits in eleven bytes once serialized but "phosphorus" does not, so only the
+    // function turning one into the other fails.
+    let job = DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility())
+        .with_max_result_size(11);
+    job.run(ctx)
+        .await
+        .expect("could not run dependent values update");
+
+    let data = next_finished_event(&mut subscriber).await;
+    assert_eq!(json!(ctx.change_set_id()), data["changeSetId"]);
+    assert_eq!(json!(1), data["failed"]);
+    assert_eq!(json!(true), data["completed"]);
+    assert!(data["durationMs"].is_u64());
+}
+
+#[test]
+async fn finished_event_counts_every_value(ctx: &mut DalContext) {
+    let rigid_designator = create_kripke_chain(ctx).await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    // Every value left in the graph once the already computed roots are dropped runs exactly
+    // once, the same way the job itself prunes the graph.
+    let roots = ctx
+        .workspace_snapshot()
+        .expect("workspace_snapshot")
+        .peek_dependent_values()
+        .await
+        .expect("peek dependent values");
+    let mut graph = DependentValueGraph::new(ctx, roots)
+        .await
+        .expect("could not build dependent value graph");
+    for value_id in graph.independent_values() {
+        if !graph.values_needs_to_execute_from_prototype_function(value_id) {
+            graph.remove_value(value_id);
+        }
+    }
+    let expected_succeeded = graph.value_count();
+    assert!(
+        expected_succeeded > 0,
+        "the chain should have values to run"
+    );
+
+    let mut subscriber = subscribe_to_workspace_events(ctx).await;
+    DependentValuesUpdate::new(ctx.access_builder(), *ctx.visibility())
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+
+    let data = next_finished_event(&mut subscriber).await;
+    assert_eq!(json!(expected_succeeded), data["succeeded"]);
+    assert_eq!(json!(0), data["failed"]);
+    assert_eq!(json!(true), data["completed"]);
+}
+
+#[test]
+async fn finished_event_reports_partial_runs(ctx: &mut DalContext) {
+    let rigid_designator = create_kripke_chain(ctx).await;
+    rigid_designator.set(ctx, "hesperus").await;
+
+    let mut subscriber = subscribe_to_workspace_events(ctx).await;
+    DependentValuesUpdate::new_with_max_iterations(ctx.access_builder(), *ctx.visibility(), 1)
+        .run(ctx)
+        .await
+        .expect("could not run dependent values update");
+
+    let data = next_finished_event(&mut subscriber).await;
+    assert_eq!(json!(false), data["completed"]);
+}
+
+/// Creates a connected etoiles and morningstar, commits, and returns the etoiles prop at the start
+/// of the long chain of dependent values between them.
+async fn create_kripke_chain(ctx: &mut DalContext) -> ExpectComponentProp {
+    let etoiles = ExpectComponent::create(ctx, "etoiles").await;
+    let morningstar = ExpectComponent::create(ctx, "morningstar").await;
+    etoiles
+        .connect(
+            ctx,
+            "naming_and_necessity",
+            morningstar,
+            "naming_and_necessity",
+        )
+        .await;
+    expected::commit_and_update_snapshot_to_visibility(ctx).await;
+
+    etoiles
+        .prop(
+            ctx,
+            [
+                "root",
+                "domain",
+                "possible_world_a",
+                "wormhole_1",
+                "wormhole_2",
+                "wormhole_3",
+                "rigid_designator",
+            ],
+        )
+        .await
+}
+
+async fn subscribe_to_workspace_events(ctx: &DalContext) -> Subscriber {
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk_opt()
+        .expect("could not get workspace pk");
+    ctx.nats_conn()
+        .subscribe(format!("si.workspace_pk.{workspace_pk}.event"))
+        .await
+        .expect("could not subscribe")
+}
+
+async fn next_finished_event(subscriber: &mut Subscriber) -> serde_json::Value {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(message) = subscriber.next().await {
+            let event: serde_json::Value =
+                serde_json::from_slice(message.payload()).expect("could not deserialize event");
+            if event["payload"]["kind"] == "DependentValuesUpdateFinished" {
+                return event["payload"]["data"].clone();
+            }
+        }
+        panic!("subscriber closed before the finished event arrived");
+    })
+    .await
+    .expect("timed out waiting for the finished event")
+}
@@ -1,8 +1,12 @@
-use dal::{diagram::Diagram, Component, DalContext};
+use dal::{
+    diagram::Diagram, func::intrinsics::IntrinsicFunc, Component, DalContext, Func, InputSocket,
+    SocketArity, SocketKind, SocketNodeSide,
+};
 use dal_test::{
     helpers::{create_component_for_default_schema_name_in_default_view, ChangeSetTestHelpers},
     test,
 };
+use si_frontend_types::{DiagramSocketDirection, DiagramSocketNodeSide};
 
 #[test]
 async fn components_removed_from_snapshot_have_virtual_diagram_entries(ctx: &mut DalContext) {
@@ -59,3 +63,86 @@ async fn components_removed_from_snapshot_have_virtual_diagram_entries(ctx: &mut
         removed_component_summary.change_status
     );
 }
+
+#[test]
+async fn bidirectional_sockets_are_listed_as_bidirectional(ctx: &mut DalContext) {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "both ways")
+            .await
+            .expect("Unable to create component.");
+    let schema_variant_id = Component::schema_variant_id(ctx, component.id())
+        .await
+        .expect("Unable to get schema variant id.");
+    let identity_func_id = Func::find_intrinsic(ctx, IntrinsicFunc::Identity)
+        .await
+        .expect("Unable to find identity func.");
+    let input_socket = InputSocket::new(
+        ctx,
+        schema_variant_id,
+        "Both Ways",
+        identity_func_id,
+        SocketArity::One,
+        SocketKind::Bidirectional,
+        None,
+    )
+    .await
+    .expect("Unable to create input socket.");
+
+    let diagram = Diagram::assemble_for_default_view(ctx)
+        .await
+        .expect("Unable to assemble diagram.");
+    let diagram_component = diagram
+        .components
+        .iter()
+        .find(|diagram_component| diagram_component.component_id == component.id())
+        .expect("Component not on diagram.");
+    let socket = diagram_component
+        .sockets
+        .iter()
+        .find(|socket| socket.label == "Both Ways")
+        .expect("Socket not on diagram.");
+
+    assert_eq!(DiagramSocketDirection::Bidirectional, socket.direction);
+    assert_eq!(DiagramSocketNodeSide::Left, socket.node_side);
+    assert_eq!(Some(1), socket.max_connections);
+    assert_eq!(Some(false), socket.is_required);
+
+    // Standard sockets are unaffected.
+    assert!(diagram_component
+        .sockets
+        .iter()
+        .filter(|socket| socket.label != "Both Ways" && socket.is_management != Some(true))
+        .all(|socket| socket.direction != DiagramSocketDirection::Bidirectional));
+
+    // The node side comes from the socket once it has been set.
+    let input_socket = input_socket
+        .set_node_side(ctx, Some(SocketNodeSide::Right))
+        .await
+        .expect("Unable to set node side.");
+    assert_eq!(Some(SocketNodeSide::Right), input_socket.node_side());
+    assert_eq!(
+        Some(SocketNodeSide::Right),
+        InputSocket::get_by_id(ctx, input_socket.id())
+            .await
+            .expect("Unable to get input socket.")
+            .node_side()
+    );
+
+    let diagram = Diagram::assemble_for_default_view(ctx)
+        .await
+        .expect("Unable to assemble diagram.");
+    let socket = diagram
+        .components
+        .iter()
+        .find(|diagram_component| diagram_component.component_id == component.id())
+        .expect("Component not on diagram.")
+        .sockets
+        .iter()
+        .find(|socket| socket.label == "Both Ways")
+        .expect("Socket not on diagram.");
+
+    assert_eq!(DiagramSocketDirection::Bidirectional, socket.direction);
+    assert_eq!(DiagramSocketNodeSide::Right, socket.node_side);
+    assert_eq!(Some(1), socket.max_connections);
+    assert_eq!(Some(false), socket.is_required);
+}
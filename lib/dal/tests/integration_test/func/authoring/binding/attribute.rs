@@ -4,7 +4,7 @@ use dal::attribute::prototype::argument::AttributePrototypeArgument;
 use dal::func::argument::{FuncArgument, FuncArgumentKind};
 use dal::func::binding::attribute::AttributeBinding;
 use dal::func::binding::{
-    AttributeArgumentBinding, AttributeFuncArgumentSource, AttributeFuncDestination,
+    AttributeArgumentBinding, AttributeFuncArgumentSource, AttributeFuncDestination, BindingError,
     EventualParent, FuncBinding,
 };
 use dal::func::intrinsics::IntrinsicFunc;
@@ -879,3 +879,68 @@ async fn detach_attribute_func(ctx: &mut DalContext) {
     );
     assert!(!funcs.iter().any(|summary| summary.id == func_id));
 }
+
+#[test]
+async fn validate_attribute_binding(ctx: &mut DalContext) {
+    let func_id = Func::find_id_by_name(ctx, "test:falloutEntriesToGalaxies")
+        .await
+        .expect("could not perform find func by name")
+        .expect("no func found");
+    let binding = FuncBinding::for_func_id(ctx, func_id)
+        .await
+        .expect("could not get bindings")
+        .pop()
+        .expect("no bindings found");
+    let schema_variant_id = binding
+        .get_schema_variant()
+        .expect("binding is not for a schema variant");
+
+    // The binding resolves on its own schema variant.
+    assert_eq!(
+        Ok(()), // expected
+        binding
+            .validate(ctx, schema_variant_id)
+            .await
+            .expect("could not validate binding")  // actual
+    );
+
+    // Against another schema variant, every location is reported at once.
+    let other_schema = Schema::find_by_name(ctx, "swifty")
+        .await
+        .expect("could not execute find schema")
+        .expect("schema not found");
+    let other_schema_variant_id = SchemaVariant::get_default_id_for_schema(ctx, other_schema.id())
+        .await
+        .expect("could not get default schema variant id");
+    let FuncBinding::Attribute(attribute) = &binding else {
+        panic!("expected an attribute binding");
+    };
+    let input_location_count = attribute
+        .argument_bindings
+        .iter()
+        .filter(|argument_binding| {
+            !matches!(
+                argument_binding.attribute_func_input_location,
+                AttributeFuncArgumentSource::StaticArgument(_)
+                    | AttributeFuncArgumentSource::Secret(_)
+            )
+        })
+        .count();
+
+    let problems = binding
+        .validate(ctx, other_schema_variant_id)
+        .await
+        .expect("could not validate binding")
+        .expect_err("binding should not resolve on another schema variant");
+    assert_eq!(
+        input_location_count + 1, // expected
+        problems.len()            // actual
+    );
+    assert_eq!(
+        BindingError::OutputLocationNotOnSchemaVariant(
+            attribute.output_location,
+            other_schema_variant_id
+        ), // expected
+        problems[0] // actual
+    );
+}
@@ -29,6 +29,60 @@ async fn qualification(ctx: &mut DalContext) {
         save_func_setup(ctx, "test:qualificationDummySecretStringIsTodd").await;
 }
 
+#[test]
+async fn save_code_with_normalization(ctx: &mut DalContext) {
+    let (func_id, _saved_func) = save_func_setup(ctx, "test:createActionStarfield").await;
+    let messy_code = "async function main() {\r\n\treturn { status: \"ok\" };   \r\n}\n\n\n";
+    let expected_code = "async function main() {\n  return { status: \"ok\" };\n}\n";
+
+    // Normalizing messy code rewrites it and reports that it did so.
+    let normalized = FuncAuthoringClient::save_code_with_normalization(
+        ctx,
+        func_id,
+        messy_code.to_string(),
+        true,
+    )
+    .await
+    .expect("could not save code");
+    assert!(normalized);
+    let func = Func::get_by_id_or_error(ctx, func_id)
+        .await
+        .expect("could not get func by id");
+    assert_eq!(
+        Some(expected_code.to_string()),                       // expected
+        func.code_plaintext().expect("could not decode code")  // actual
+    );
+
+    // Normalizing already normalized code leaves it untouched.
+    let normalized = FuncAuthoringClient::save_code_with_normalization(
+        ctx,
+        func_id,
+        expected_code.to_string(),
+        true,
+    )
+    .await
+    .expect("could not save code");
+    assert!(!normalized);
+
+    // Opting out stores the code verbatim.
+    let normalized = FuncAuthoringClient::save_code_with_normalization(
+        ctx,
+        func_id,
+        messy_code.to_string(),
+        false,
+    )
+    .await
+    .expect("could not save code");
+    assert!(!normalized);
+    let func = Func::get_by_id_or_error(ctx, func_id)
+        .await
+        .expect("could not get func by id");
+    assert_eq!(
+        Some(messy_code.to_string()),                          // expected
+        func.code_plaintext().expect("could not decode code")  // actual
+    );
+}
+
 // Sets up the tests within the module. Find the func to be saved by name and then save it
 // immediately when found. This is the basic "does it work in place" check.
 pub async fn save_func_setup(
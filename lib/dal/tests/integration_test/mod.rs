@@ -24,6 +24,8 @@ mod rebaser;
 mod resource_metadata;
 mod schema;
 mod secret;
+mod status;
 mod validations;
 mod view;
 mod workspace;
+mod workspace_snapshot;
@@ -1,4 +1,7 @@
-use dal::{prop::PropPath, DalContext, Prop, Schema, SchemaVariant};
+use dal::prop::{PropError, PropPath, PROP_PATH_SEPARATOR};
+use dal::workspace_snapshot::content_address::ContentAddressDiscriminants;
+use dal::workspace_snapshot::WorkspaceSnapshotError;
+use dal::{ContentHash, DalContext, Prop, PropId, PropKind, Schema, SchemaVariant};
 use dal_test::test;
 use pretty_assertions_sorted::assert_eq;
 
@@ -29,6 +32,123 @@ async fn prop_path(ctx: &DalContext) {
     assert_eq!(name_path, fetched_name_path);
 }
 
+#[test]
+async fn new_rejects_name_with_path_separator(ctx: &DalContext) {
+    let starfield_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "starfield")
+        .expect("starfield does not exist")
+        .to_owned();
+
+    let variant = SchemaVariant::list_for_schema(ctx, starfield_schema.id())
+        .await
+        .expect("get schema variants")
+        .pop()
+        .expect("get default variant");
+
+    let domain_id =
+        Prop::find_prop_id_by_path(ctx, variant.id(), &PropPath::new(["root", "domain"]))
+            .await
+            .expect("get domain prop id");
+
+    let name = format!("sneaky{PROP_PATH_SEPARATOR}name");
+    let result = Prop::new_without_ui_optionals(ctx, &name, PropKind::String, domain_id).await;
+    assert!(matches!(
+        result,
+        Err(PropError::InvalidPropName(invalid_name)) if invalid_name == name
+    ));
+}
+
+#[test]
+async fn into_frontend_type_with_path_matches(ctx: &DalContext) {
+    let starfield_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "starfield")
+        .expect("starfield does not exist")
+        .to_owned();
+
+    let variant = SchemaVariant::list_for_schema(ctx, starfield_schema.id())
+        .await
+        .expect("get schema variants")
+        .pop()
+        .expect("get default variant");
+
+    for prop in SchemaVariant::all_props(ctx, variant.id())
+        .await
+        .expect("could not list props")
+    {
+        let path = Prop::path_by_id(ctx, prop.id())
+            .await
+            .expect("get prop path by id");
+        let expected = prop
+            .clone()
+            .into_frontend_type(ctx)
+            .await
+            .expect("could not convert prop");
+        let actual = prop.into_frontend_type_with_path(path);
+
+        assert_eq!(
+            expected, // expected
+            actual    // actual
+        );
+    }
+}
+
+#[test]
+async fn walk_subtree_ordered(ctx: &DalContext) {
+    let pirate_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "pirate")
+        .expect("pirate does not exist")
+        .to_owned();
+    let pirate_default_variant_id = pirate_schema
+        .get_default_schema_variant_id(ctx)
+        .await
+        .expect("should be able to get default")
+        .expect("should have a default schema variant");
+
+    let domain_prop_id = Prop::find_prop_id_by_path(
+        ctx,
+        pirate_default_variant_id,
+        &PropPath::new(["root", "domain"]),
+    )
+    .await
+    .expect("should have the domain prop");
+
+    let descendants = Prop::walk_subtree_ordered(ctx, domain_prop_id)
+        .await
+        .expect("could not walk subtree");
+
+    for (path, prop_id) in &descendants {
+        assert_eq!(
+            path.to_owned(), // expected
+            Prop::path_by_id(ctx, *prop_id) // actual
+                .await
+                .expect("get prop path by id")
+        );
+    }
+
+    // Element props directly follow their array or map, in declaration order.
+    let paths: Vec<PropPath> = descendants.into_iter().map(|(path, _)| path).collect();
+    assert_eq!(
+        vec![
+            PropPath::new(["root", "domain", "name"]),
+            PropPath::new(["root", "domain", "working_eyes"]),
+            PropPath::new(["root", "domain", "parrot_names"]),
+            PropPath::new(["root", "domain", "parrot_names", "parrot_name"]),
+            PropPath::new(["root", "domain", "treasure"]),
+            PropPath::new(["root", "domain", "treasure", "location"]),
+        ], // expected
+        paths // actual
+    );
+}
+
 #[test]
 async fn verify_prop_used_as_input_flag(ctx: &DalContext) {
     let pirate_schema = Schema::list(ctx)
@@ -152,3 +272,446 @@ async fn ordered_child_props(ctx: &DalContext) {
         ordered_child_prop_names   // actual
     );
 }
+
+#[test]
+async fn effective_docs_are_inherited_from_nearest_ancestor(ctx: &DalContext) {
+    let pirate_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "pirate")
+        .expect("pirate does not exist")
+        .to_owned();
+    let pirate_default_variant_id = pirate_schema
+        .get_default_schema_variant_id(ctx)
+        .await
+        .expect("should be able to get default")
+        .expect("should have a default schema variant");
+
+    let mut props = vec![];
+    for path in [
+        PropPath::new(["root", "domain"]),
+        PropPath::new(["root", "domain", "parrot_names"]),
+        PropPath::new(["root", "domain", "parrot_names", "parrot_name"]),
+    ] {
+        let prop_id = Prop::find_prop_id_by_path(ctx, pirate_default_variant_id, &path)
+            .await
+            .expect("could not find prop");
+        props.push(Prop::get_by_id(ctx, prop_id).await.expect("get prop"));
+    }
+    let [domain, parrot_names, parrot_name]: [Prop; 3] =
+        props.try_into().expect("should have three props");
+
+    let doc_link = "https://example.com/parrots".to_string();
+    let documentation = "all about pirates".to_string();
+    domain
+        .modify(ctx, |prop| {
+            prop.documentation = Some(documentation.clone());
+            Ok(())
+        })
+        .await
+        .expect("could not modify prop");
+    let parrot_names = parrot_names
+        .modify(ctx, |prop| {
+            prop.doc_link = Some(doc_link.clone());
+            prop.documentation = None;
+            Ok(())
+        })
+        .await
+        .expect("could not modify prop");
+    let parrot_name = parrot_name
+        .modify(ctx, |prop| {
+            prop.doc_link = None;
+            prop.documentation = None;
+            Ok(())
+        })
+        .await
+        .expect("could not modify prop");
+
+    assert_eq!(
+        Some(doc_link.clone()), // expected
+        parrot_names
+            .effective_doc_link(ctx)
+            .await
+            .expect("could not get effective doc link")  // actual
+    );
+    assert_eq!(
+        Some(doc_link), // expected
+        parrot_name
+            .effective_doc_link(ctx)
+            .await
+            .expect("could not get effective doc link")  // actual
+    );
+    assert_eq!(
+        Some(documentation), // expected
+        parrot_name
+            .effective_documentation(ctx)
+            .await
+            .expect("could not get effective documentation")  // actual
+    );
+    assert_eq!(
+        None,                 // expected
+        parrot_name.doc_link  // actual
+    );
+}
+
+#[test]
+async fn find_by_kind(ctx: &DalContext) {
+    let pirate_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "pirate")
+        .expect("pirate does not exist")
+        .to_owned();
+    let pirate_default_variant_id = pirate_schema
+        .get_default_schema_variant_id(ctx)
+        .await
+        .expect("should be able to get default")
+        .expect("should have a default schema variant");
+
+    let domain_path = PropPath::new(["root", "domain"]);
+    for (kind, expected_domain_paths) in [
+        (
+            PropKind::Array,
+            vec![PropPath::new(["root", "domain", "parrot_names"])],
+        ),
+        (
+            PropKind::String,
+            vec![
+                PropPath::new(["root", "domain", "name"]),
+                PropPath::new(["root", "domain", "parrot_names", "parrot_name"]),
+                PropPath::new(["root", "domain", "treasure", "location"]),
+            ],
+        ),
+    ] {
+        let prop_ids = Prop::find_by_kind(ctx, pirate_default_variant_id, kind)
+            .await
+            .expect("could not find props by kind");
+
+        let mut domain_paths = vec![];
+        for prop_id in prop_ids {
+            let prop = Prop::get_by_id(ctx, prop_id).await.expect("get prop");
+            assert_eq!(
+                kind,      // expected
+                prop.kind  // actual
+            );
+
+            let path = Prop::path_by_id(ctx, prop_id)
+                .await
+                .expect("get prop path by id");
+            if path.is_descendant_of(&domain_path) {
+                domain_paths.push(path);
+            }
+        }
+
+        assert_eq!(
+            expected_domain_paths, // expected
+            domain_paths           // actual
+        );
+    }
+}
+
+#[test]
+async fn referrers(ctx: &DalContext) {
+    let pirate_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "pirate")
+        .expect("pirate does not exist")
+        .to_owned();
+    let pirate_default_variant_id = pirate_schema
+        .get_default_schema_variant_id(ctx)
+        .await
+        .expect("should be able to get default")
+        .expect("should have a default schema variant");
+
+    let mut prop_ids = vec![];
+    for path in [
+        PropPath::new(["root", "domain", "parrot_names"]),
+        PropPath::new(["root", "domain", "name"]),
+        PropPath::new(["root", "domain", "treasure", "location"]),
+    ] {
+        prop_ids.push(
+            Prop::find_prop_id_by_path(ctx, pirate_default_variant_id, &path)
+                .await
+                .expect("could not find prop"),
+        );
+    }
+    let (parrot_names_id, referrer_ids) = (prop_ids[0], &prop_ids[1..]);
+
+    for referrer_id in referrer_ids {
+        Prop::get_by_id(ctx, *referrer_id)
+            .await
+            .expect("get prop")
+            .modify(ctx, |prop| {
+                prop.refers_to_prop_id = Some(parrot_names_id);
+                Ok(())
+            })
+            .await
+            .expect("could not modify prop");
+    }
+
+    assert_eq!(
+        referrer_ids.to_vec(), // expected
+        Prop::referrers(ctx, parrot_names_id)
+            .await
+            .expect("could not get referrers")  // actual
+    );
+    assert!(Prop::referrers(ctx, referrer_ids[0])
+        .await
+        .expect("could not get referrers")
+        .is_empty());
+}
+
+#[test]
+async fn list_content_many_props(ctx: &DalContext) {
+    let pirate_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "pirate")
+        .expect("pirate does not exist")
+        .to_owned();
+    let pirate_default_variant_id = pirate_schema
+        .get_default_schema_variant_id(ctx)
+        .await
+        .expect("should be able to get default")
+        .expect("should have a default schema variant");
+
+    let prop_ids: Vec<PropId> = SchemaVariant::all_prop_ids(ctx, pirate_default_variant_id)
+        .await
+        .expect("list all prop ids")
+        .into_iter()
+        .collect();
+    assert!(prop_ids.len() > 10);
+
+    let props = Prop::list_content(ctx, prop_ids.clone())
+        .await
+        .expect("list content");
+
+    // Props come back in the order they were requested, and each matches a single lookup.
+    assert_eq!(
+        prop_ids,                                       // expected
+        props.iter().map(Prop::id).collect::<Vec<_>>()  // actual
+    );
+    for prop in props {
+        let expected = Prop::get_by_id(ctx, prop.id())
+            .await
+            .expect("get prop by id");
+        assert_eq!(
+            expected.name, // expected
+            prop.name      // actual
+        );
+        assert_eq!(
+            expected.kind, // expected
+            prop.kind      // actual
+        );
+    }
+}
+
+#[test]
+async fn json_props(ctx: &DalContext) {
+    let starfield_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "starfield")
+        .expect("starfield does not exist")
+        .to_owned();
+
+    let variant = SchemaVariant::list_for_schema(ctx, starfield_schema.id())
+        .await
+        .expect("get schema variants")
+        .pop()
+        .expect("get default variant");
+
+    let domain_id =
+        Prop::find_prop_id_by_path(ctx, variant.id(), &PropPath::new(["root", "domain"]))
+            .await
+            .expect("get domain prop id");
+
+    let blobs = Prop::new_without_ui_optionals(ctx, "blobs", PropKind::Array, domain_id)
+        .await
+        .expect("create array prop");
+    let blob = Prop::new_without_ui_optionals(ctx, "blob", PropKind::Json, blobs.id())
+        .await
+        .expect("create json element prop");
+    let blob_map = Prop::new_without_ui_optionals(ctx, "blob_map", PropKind::Map, domain_id)
+        .await
+        .expect("create map prop");
+    Prop::new_without_ui_optionals(ctx, "blob", PropKind::Json, blob_map.id())
+        .await
+        .expect("create json element prop");
+
+    assert_eq!(
+        blob.id(), // expected
+        Prop::element_prop_id(ctx, blobs.id()) // actual
+            .await
+            .expect("get element prop id")
+    );
+    assert_eq!(
+        "any[]",                                        // expected
+        blobs.ts_type(ctx).await.expect("get ts type")  // actual
+    );
+    assert_eq!(
+        "Record<string, any>",                             // expected
+        blob_map.ts_type(ctx).await.expect("get ts type")  // actual
+    );
+
+    // Json props are leaves.
+    let result = Prop::new_without_ui_optionals(ctx, "nested", PropKind::String, blob.id()).await;
+    assert!(matches!(
+        result,
+        Err(PropError::JsonPropCannotHaveChildren(prop_id)) if prop_id == blob.id()
+    ));
+    let result = Prop::element_prop_id(ctx, blob.id()).await;
+    assert!(matches!(
+        result,
+        Err(PropError::ElementPropNotOnKind(prop_id, PropKind::Json)) if prop_id == blob.id()
+    ));
+}
+
+#[test]
+async fn missing_content_reports_hash(ctx: &DalContext) {
+    let starfield_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "starfield")
+        .expect("starfield does not exist")
+        .to_owned();
+
+    let variant = SchemaVariant::list_for_schema(ctx, starfield_schema.id())
+        .await
+        .expect("get schema variants")
+        .pop()
+        .expect("get default variant");
+
+    let domain_id =
+        Prop::find_prop_id_by_path(ctx, variant.id(), &PropPath::new(["root", "domain"]))
+            .await
+            .expect("get domain prop id");
+    let prop = Prop::new_without_ui_optionals(ctx, "lost", PropKind::String, domain_id)
+        .await
+        .expect("create prop");
+
+    let missing_hash = ContentHash::new(b"nobody home");
+    ctx.workspace_snapshot()
+        .expect("get snapshot")
+        .update_content(prop.id().into(), missing_hash)
+        .await
+        .expect("update content");
+
+    let result = Prop::get_by_id(ctx, prop.id()).await;
+    assert!(matches!(
+        result,
+        Err(PropError::WorkspaceSnapshot(WorkspaceSnapshotError::MissingContentFromStoreForHash(
+            id,
+            ContentAddressDiscriminants::Prop,
+            hash,
+        ))) if id == prop.id().into() && hash == missing_hash
+    ));
+    let result = Prop::list_content(ctx, vec![prop.id()]).await;
+    assert!(matches!(
+        result,
+        Err(PropError::WorkspaceSnapshot(WorkspaceSnapshotError::MissingContentFromStoreForHash(
+            id,
+            ContentAddressDiscriminants::Prop,
+            hash,
+        ))) if id == prop.id().into() && hash == missing_hash
+    ));
+}
+
+#[test]
+async fn required(ctx: &DalContext) {
+    let starfield_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "starfield")
+        .expect("starfield does not exist")
+        .to_owned();
+
+    let variant = SchemaVariant::list_for_schema(ctx, starfield_schema.id())
+        .await
+        .expect("get schema variants")
+        .pop()
+        .expect("get default variant");
+
+    let domain_id =
+        Prop::find_prop_id_by_path(ctx, variant.id(), &PropPath::new(["root", "domain"]))
+            .await
+            .expect("get domain prop id");
+    let prop = Prop::new_without_ui_optionals(ctx, "must", PropKind::String, domain_id)
+        .await
+        .expect("create prop");
+    assert!(!prop.required);
+
+    prop.modify(ctx, |prop| {
+        prop.required = true;
+        Ok(())
+    })
+    .await
+    .expect("modify prop");
+
+    let prop = Prop::get_by_id(ctx, prop.id).await.expect("get prop");
+    assert!(prop.required);
+    assert!(
+        prop.into_frontend_type(ctx)
+            .await
+            .expect("into frontend type")
+            .required
+    );
+}
+
+#[test]
+async fn find_prop_id_by_path_ci(ctx: &DalContext) {
+    let starfield_schema = Schema::list(ctx)
+        .await
+        .expect("list schemas")
+        .iter()
+        .find(|schema| schema.name() == "starfield")
+        .expect("starfield does not exist")
+        .to_owned();
+
+    let variant = SchemaVariant::list_for_schema(ctx, starfield_schema.id())
+        .await
+        .expect("get schema variants")
+        .pop()
+        .expect("get default variant");
+
+    let domain_id =
+        Prop::find_prop_id_by_path(ctx, variant.id(), &PropPath::new(["root", "domain"]))
+            .await
+            .expect("get domain prop id");
+    let region = Prop::new_without_ui_optionals(ctx, "Region", PropKind::String, domain_id)
+        .await
+        .expect("create prop");
+
+    // Strict lookup stays the default and fails on mismatched casing.
+    let sloppy_path = PropPath::new(["ROOT", "Domain", "region"]);
+    let result = Prop::find_prop_id_by_path(ctx, variant.id(), &sloppy_path).await;
+    assert!(matches!(
+        result,
+        Err(PropError::ChildPropNotFoundByName(_, name)) if name == "ROOT"
+    ));
+    assert_eq!(
+        region.id(), // expected
+        Prop::find_prop_id_by_path_ci(ctx, variant.id(), &sloppy_path) // actual
+            .await
+            .expect("find prop id ignoring case")
+    );
+
+    // Two children differing only by case make the lookup ambiguous.
+    Prop::new_without_ui_optionals(ctx, "region", PropKind::String, domain_id)
+        .await
+        .expect("create prop");
+    let result = Prop::find_prop_id_by_path_ci(ctx, variant.id(), &sloppy_path).await;
+    assert!(matches!(
+        result,
+        Err(PropError::AmbiguousChildPropName(_, name, candidates))
+            if name == "region" && candidates == vec!["Region".to_string(), "region".to_string()]
+    ));
+}
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use dal::status::{StatusMessageState, StatusUpdate};
+use dal::workspace_snapshot::DependentValueRoot;
+use dal::{DalContext, WsEvent};
+use dal_test::helpers::{
+    create_component_for_default_schema_name_in_default_view, ChangeSetTestHelpers,
+};
+use dal_test::test;
+use futures::StreamExt;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn clear_stuck_for_change_set(ctx: &mut DalContext) {
+    let mut components = Vec::new();
+    for name in ["stuck", "in", "progress", "idle"] {
+        components.push(
+            create_component_for_default_schema_name_in_default_view(ctx, "swifty", name)
+                .await
+                .expect("could not create component"),
+        );
+    }
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    assert_eq!(
+        0, // expected
+        StatusUpdate::clear_stuck_for_change_set(ctx)
+            .await
+            .expect("could not clear stuck components")  // actual
+    );
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk_opt()
+        .expect("could not get workspace pk");
+    let mut subscriber = ctx
+        .nats_conn()
+        .subscribe(format!("si.workspace_pk.{workspace_pk}.event"))
+        .await
+        .expect("could not subscribe");
+
+    // Leave all but the last component as a dependent values update that crashed would have:
+    // started, with its roots still on the snapshot.
+    let mut stuck_component_ids = HashSet::new();
+    for component in &components[..3] {
+        let name_value_id = component
+            .attribute_value_for_prop(ctx, &["root", "si", "name"])
+            .await
+            .expect("could not get name attribute value");
+        ctx.workspace_snapshot()
+            .expect("could not get workspace snapshot")
+            .add_dependent_value_root(DependentValueRoot::Unfinished(name_value_id.into()))
+            .await
+            .expect("could not add dependent value root");
+        WsEvent::status_update(
+            ctx,
+            StatusUpdate::new_dvu(StatusMessageState::StatusStarted, component.id()),
+        )
+        .await
+        .expect("could not create status update")
+        .publish_immediately(ctx)
+        .await
+        .expect("could not publish status update");
+        stuck_component_ids.insert(component.id().to_string());
+    }
+
+    let cleared = StatusUpdate::clear_stuck_for_change_set(ctx)
+        .await
+        .expect("could not clear stuck components");
+    assert_eq!(
+        stuck_component_ids.len(), // expected
+        cleared                    // actual
+    );
+
+    // Collect finished updates until the subject has been quiet for a while, so that any update
+    // for the idle component would have arrived.
+    let mut finished_component_ids = HashSet::new();
+    while let Ok(Some(message)) =
+        tokio::time::timeout(Duration::from_secs(1), subscriber.next()).await
+    {
+        let event: serde_json::Value =
+            serde_json::from_slice(message.payload()).expect("could not deserialize event");
+        let data = &event["payload"]["data"];
+        if event["payload"]["kind"] == "StatusUpdate" && data["status"] == "statusFinished" {
+            if let Some(component_id) = data["componentId"].as_str() {
+                finished_component_ids.insert(component_id.to_owned());
+            }
+        }
+    }
+
+    assert_eq!(
+        stuck_component_ids,    // expected
+        finished_component_ids  // actual
+    );
+}
@@ -1,5 +1,9 @@
 use dal::{
-    diagram::{geometry::Geometry, view::View, Diagram, DiagramError},
+    diagram::{
+        geometry::{Geometry, GeometryId},
+        view::View,
+        Diagram, DiagramError,
+    },
     workspace_snapshot::graph::WorkspaceSnapshotGraphError,
     Component, DalContext, Ulid, WorkspaceSnapshotError,
 };
@@ -364,3 +368,82 @@ async fn remove_view_that_previously_contained_another_view_that_has_been_remove
         View::list(ctx).await.expect("Unable to list Views").len(),
     );
 }
+
+#[test]
+async fn update_geometries_in_bulk(ctx: &mut DalContext) {
+    let view_id = View::get_id_for_default(ctx)
+        .await
+        .expect("could not get default view id");
+    let first = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "first")
+        .await
+        .expect("could not create component");
+    let second = create_component_for_default_schema_name_in_default_view(ctx, "swifty", "second")
+        .await
+        .expect("could not create component");
+    let first_geometry = first
+        .geometry(ctx, view_id)
+        .await
+        .expect("could not get geometry");
+    let second_geometry = second
+        .geometry(ctx, view_id)
+        .await
+        .expect("could not get geometry");
+
+    let moved = |x| RawGeometry {
+        x,
+        y: 100,
+        width: Some(250),
+        height: Some(250),
+    };
+
+    // An invalid id anywhere in the batch means nothing gets updated.
+    let invalid_id = GeometryId::generate();
+    let result = Geometry::update_bulk(
+        ctx,
+        vec![
+            (first_geometry.id(), moved(10)),
+            (invalid_id, moved(20)),
+            (second_geometry.id(), moved(30)),
+        ],
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(DiagramError::GeometryNotFound(geometry_id)) if geometry_id == invalid_id
+    ));
+    let unchanged = Geometry::get_by_id(ctx, first_geometry.id())
+        .await
+        .expect("could not get geometry");
+    assert_eq!(
+        first_geometry.clone().into_raw(), // expected
+        unchanged.into_raw()               // actual
+    );
+
+    let updated = Geometry::update_bulk(
+        ctx,
+        vec![
+            (first_geometry.id(), moved(10)),
+            (second_geometry.id(), moved(30)),
+        ],
+    )
+    .await
+    .expect("could not update geometries");
+    let updated: Vec<RawGeometry> = updated.into_iter().map(Geometry::into_raw).collect();
+    assert_eq!(
+        vec![moved(10), moved(30)], // expected
+        updated                     // actual
+    );
+
+    for (geometry_id, expected) in [
+        (first_geometry.id(), moved(10)),
+        (second_geometry.id(), moved(30)),
+    ] {
+        let geometry = Geometry::get_by_id(ctx, geometry_id)
+            .await
+            .expect("could not get geometry");
+        assert_eq!(
+            expected,            // expected
+            geometry.into_raw()  // actual
+        );
+    }
+}
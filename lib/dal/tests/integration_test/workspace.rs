@@ -1,12 +1,21 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
 use dal::change_set::view::OpenChangeSetsView;
 use dal::diagram::Diagram;
-use dal::{DalContext, Workspace};
+use dal::layer_db_types::ContentTypes;
+use dal::{
+    ChangeSet, ChangeSetId, ChangeSetStatus, ContentHash, DalContext, HistoryActor, Workspace,
+    WorkspaceError, WorkspaceExportExt, WorkspacePk,
+};
 use dal_test::helpers::{
-    create_component_for_default_schema_name_in_default_view, ChangeSetTestHelpers,
+    create_component_for_default_schema_name_in_default_view, create_user, ChangeSetTestHelpers,
     PropEditorTestView,
 };
 use dal_test::test;
 use pretty_assertions_sorted::assert_eq;
+use si_layer_cache::db::serialize;
+use si_pkg::WorkspaceExportFrameV0;
 
 #[test]
 async fn export_import_loop(ctx: &mut DalContext) {
@@ -111,3 +120,529 @@ async fn export_import_loop(ctx: &mut DalContext) {
             .expect("get value for domain/name")
     );
 }
+
+#[test]
+async fn export_import_round_trip_is_content_equivalent(ctx: &mut DalContext) {
+    create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Long John Silver")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot to visibility");
+
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let mut workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+
+    let exported = workspace
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export workspace");
+    workspace
+        .import(ctx, exported.clone())
+        .await
+        .expect("import workspace");
+    let reexported = workspace
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export imported workspace");
+
+    assert!(exported
+        .content_equivalent(&reexported)
+        .expect("could not compare exports"));
+
+    // Changing the content of a change set makes the exports diverge.
+    ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("fork change set");
+    create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Blackbeard")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot to visibility");
+    let diverged = workspace
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export workspace");
+
+    assert!(!exported
+        .content_equivalent(&diverged)
+        .expect("could not compare exports"));
+}
+
+#[test]
+async fn streamed_export_round_trips_through_import_from_reader(ctx: &mut DalContext) {
+    let change_set_name = "streamed".to_string();
+    ChangeSetTestHelpers::fork_from_head_change_set_with_name(ctx, &change_set_name)
+        .await
+        .expect("fork change set");
+    create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Grace O'Malley")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot to visibility");
+
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let mut workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+
+    let mut export = Vec::new();
+    workspace
+        .export_to_writer(ctx, "0.0", &mut export)
+        .await
+        .expect("export workspace");
+
+    // HEAD and the forked change set share almost all of their content, so every value must
+    // only be written once across the content store values frames.
+    let mut reader = &export[..];
+    let mut change_set_frames = 0;
+    let mut content_hashes = Vec::new();
+    while let Some(frame) = Workspace::read_export_frame(&mut reader)
+        .await
+        .expect("read export frame")
+    {
+        match frame {
+            WorkspaceExportFrameV0::ContentStoreValues(content_store_values) => {
+                let values: HashMap<ContentHash, (Arc<ContentTypes>, String)> =
+                    serialize::from_bytes(&content_store_values)
+                        .expect("deserialize content store values");
+                content_hashes.extend(values.into_keys());
+            }
+            WorkspaceExportFrameV0::ChangeSet(_) => change_set_frames += 1,
+            WorkspaceExportFrameV0::Metadata(_) => {}
+        }
+    }
+    assert_eq!(
+        2,                 // expected
+        change_set_frames  // actual
+    );
+    let unique_content_hashes: HashSet<&ContentHash> = content_hashes.iter().collect();
+    assert_eq!(
+        content_hashes.len(),        // expected
+        unique_content_hashes.len()  // actual
+    );
+
+    ChangeSetTestHelpers::abandon_change_set(ctx)
+        .await
+        .expect("abandon change set");
+
+    workspace
+        .import_from_reader(ctx, &export[..])
+        .await
+        .expect("import streamed export");
+
+    let view = OpenChangeSetsView::assemble(ctx)
+        .await
+        .expect("could not assemble view");
+    let imported_change_set_id = view
+        .change_sets
+        .iter()
+        .find(|cs| cs.name == change_set_name)
+        .expect("find changeset")
+        .id;
+    ctx.update_visibility_and_snapshot_to_visibility(imported_change_set_id)
+        .await
+        .expect("update context to use imported data");
+
+    let diagram = Diagram::assemble_for_default_view(ctx)
+        .await
+        .expect("load diagram");
+    let component = diagram.components.first().expect("get component");
+    assert_eq!(
+        "Grace O'Malley", // expected
+        PropEditorTestView::for_component_id(ctx, component.id) // actual
+            .await
+            .expect("could not get property editor test view")
+            .get_value(&["root", "domain", "name"])
+            .expect("could not get value")
+            .get("value")
+            .expect("get value for domain/name")
+    );
+}
+
+#[test]
+async fn import_single_change_set(ctx: &mut DalContext) {
+    let exported_change_set =
+        ChangeSetTestHelpers::fork_from_head_change_set_with_name(ctx, "single")
+            .await
+            .expect("fork change set");
+    create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Anne Bonny")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot to visibility");
+
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+    let workspace_export = workspace
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export workspace");
+
+    let change_set_count_before = OpenChangeSetsView::assemble(ctx)
+        .await
+        .expect("assemble view")
+        .change_sets
+        .len();
+
+    let missing_change_set_id = ChangeSetId::new();
+    let result = Workspace::import_change_set(
+        ctx,
+        &workspace_export,
+        missing_change_set_id,
+        workspace.default_change_set_id(),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::ChangeSetNotInExport(change_set_id))
+            if change_set_id == missing_change_set_id
+    ));
+
+    let imported_change_set_id = Workspace::import_change_set(
+        ctx,
+        &workspace_export,
+        exported_change_set.id,
+        workspace.default_change_set_id(),
+    )
+    .await
+    .expect("import change set");
+
+    // Existing change sets are left alone.
+    let view = OpenChangeSetsView::assemble(ctx)
+        .await
+        .expect("assemble view");
+    assert_eq!(
+        change_set_count_before + 1, // expected
+        view.change_sets.len()       // actual
+    );
+
+    let imported_change_set = ChangeSet::find(ctx, imported_change_set_id)
+        .await
+        .expect("could not find change set")
+        .expect("change set exists");
+    assert_eq!(
+        Some(workspace.default_change_set_id()), // expected
+        imported_change_set.base_change_set_id   // actual
+    );
+
+    ctx.update_visibility_and_snapshot_to_visibility(imported_change_set_id)
+        .await
+        .expect("update context to use imported data");
+    let diagram = Diagram::assemble_for_default_view(ctx)
+        .await
+        .expect("load diagram");
+    assert_eq!(
+        1,                        // expected
+        diagram.components.len()  // actual
+    );
+}
+
+#[test]
+async fn import_change_set_into_another_workspace(ctx: &mut DalContext) {
+    let exported_change_set =
+        ChangeSetTestHelpers::fork_from_head_change_set_with_name(ctx, "crossing")
+            .await
+            .expect("fork change set");
+    create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Anne Bonny")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot to visibility");
+
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let workspace_export = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace")
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export workspace");
+
+    // The other workspace has its own component on HEAD, which the export knows nothing about.
+    let other_workspace =
+        Workspace::new_from_builtin(ctx, WorkspacePk::generate(), "other workspace", "token")
+            .await
+            .expect("could not create workspace");
+    create_component_for_default_schema_name_in_default_view(ctx, "pirate", "Mary Read")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("commit and update snapshot to visibility");
+
+    let imported_change_set_id = Workspace::import_change_set(
+        ctx,
+        &workspace_export,
+        exported_change_set.id,
+        other_workspace.default_change_set_id(),
+    )
+    .await
+    .expect("import change set");
+
+    ctx.update_visibility_and_snapshot_to_visibility(imported_change_set_id)
+        .await
+        .expect("update context to use imported data");
+    let diagram = Diagram::assemble_for_default_view(ctx)
+        .await
+        .expect("load diagram");
+    let mut component_names = diagram
+        .components
+        .iter()
+        .map(|component| component.display_name.as_str())
+        .collect::<Vec<_>>();
+    component_names.sort();
+    assert_eq!(
+        vec!["Anne Bonny", "Mary Read"], // expected
+        component_names                  // actual
+    );
+}
+
+#[test]
+async fn import_change_set_without_base_in_export_is_rejected(ctx: &mut DalContext) {
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+    let workspace_export = workspace
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export workspace");
+
+    // HEAD has no base to compute its changes against.
+    let result = Workspace::import_change_set(
+        ctx,
+        &workspace_export,
+        workspace.default_change_set_id(),
+        workspace.default_change_set_id(),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::ChangeSetBaseNotInExport(change_set_id))
+            if change_set_id == workspace.default_change_set_id()
+    ));
+}
+
+#[test]
+async fn reading_truncated_export_frame_fails(_ctx: &DalContext) {
+    // A frame claiming far more bytes than follow must fail instead of allocating the claimed
+    // length up front.
+    let mut export = u64::MAX.to_be_bytes().to_vec();
+    export.extend_from_slice(b"not much of a frame");
+
+    let result = Workspace::read_export_frame(&mut &export[..]).await;
+
+    assert!(matches!(result, Err(WorkspaceError::Io(_))));
+}
+
+#[test]
+async fn export_rejects_change_set_with_dangling_base(ctx: &mut DalContext) {
+    // Fork a change set from the current (non-default) change set, then abandon the current one
+    // so that the fork's base is no longer active.
+    let base_change_set_id = ctx.change_set_id();
+    let dangling_change_set = ChangeSet::fork_from(ctx, base_change_set_id, "dangling")
+        .await
+        .expect("could not fork change set");
+    ChangeSetTestHelpers::abandon_change_set(ctx)
+        .await
+        .expect("abandon change set");
+    ctx.commit_no_rebase().await.expect("could not commit");
+
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+
+    let result = workspace.generate_export_data(ctx, "0.0").await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::ExportingChangeSetWithMissingBase(change_set_id, missing_base_id))
+            if change_set_id == dangling_change_set.id && missing_base_id == base_change_set_id
+    ));
+}
+
+#[test]
+async fn soft_deleted_workspace_is_hidden_until_restored(ctx: &mut DalContext) {
+    let user = create_user(ctx).await.expect("could not create user");
+    let mut workspace =
+        Workspace::new_from_builtin(ctx, WorkspacePk::generate(), "soft delete", "token")
+            .await
+            .expect("could not create workspace");
+    user.associate_workspace(ctx, *workspace.pk())
+        .await
+        .expect("could not associate user with workspace");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+    ctx.update_history_actor(HistoryActor::User(user.pk()));
+
+    let listed_pks = |workspaces: Vec<Workspace>| {
+        workspaces
+            .iter()
+            .map(|workspace| *workspace.pk())
+            .collect::<Vec<_>>()
+    };
+    let workspaces = Workspace::list_for_user(ctx)
+        .await
+        .expect("could not list workspaces");
+    assert_eq!(
+        vec![*workspace.pk()],  // expected
+        listed_pks(workspaces)  // actual
+    );
+
+    workspace
+        .mark_deleted(ctx)
+        .await
+        .expect("could not mark workspace as deleted");
+    assert!(workspace.is_deleted());
+    let workspaces = Workspace::list_for_user(ctx)
+        .await
+        .expect("could not list workspaces");
+    assert!(workspaces.is_empty());
+
+    workspace
+        .restore(ctx)
+        .await
+        .expect("could not restore workspace");
+    assert!(!workspace.is_deleted());
+    let workspaces = Workspace::list_for_user(ctx)
+        .await
+        .expect("could not list workspaces");
+    assert_eq!(
+        vec![*workspace.pk()],  // expected
+        listed_pks(workspaces)  // actual
+    );
+}
+
+#[test]
+async fn update_default_change_set_id_validates_change_set(ctx: &mut DalContext) {
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let mut workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+
+    let open_change_set = ChangeSet::fork_head(ctx, "new head")
+        .await
+        .expect("could not fork head");
+    let mut abandoned_change_set = ChangeSet::fork_head(ctx, "abandoned")
+        .await
+        .expect("could not fork head");
+    abandoned_change_set
+        .abandon(ctx)
+        .await
+        .expect("could not abandon change set");
+
+    let result = workspace
+        .update_default_change_set_id(ctx, abandoned_change_set.id)
+        .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::InvalidDefaultChangeSet(change_set_id, pk))
+            if change_set_id == abandoned_change_set.id && pk == workspace_pk
+    ));
+
+    let result = workspace
+        .update_default_change_set_id(ctx, ChangeSetId::generate())
+        .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceError::InvalidDefaultChangeSet(_, _))
+    ));
+
+    workspace
+        .update_default_change_set_id(ctx, open_change_set.id)
+        .await
+        .expect("could not update default change set");
+    assert_eq!(
+        open_change_set.id,                // expected
+        workspace.default_change_set_id()  // actual
+    );
+}
+
+#[test]
+async fn export_import_carries_component_concurrency_limit(ctx: &mut DalContext) {
+    let workspace_pk = ctx.tenancy().workspace_pk_opt().expect("find workspace pk");
+    let mut workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+
+    workspace
+        .set_component_concurrency_limit(ctx, Some(7))
+        .await
+        .expect("set component concurrency limit");
+    let exported = workspace
+        .generate_export_data(ctx, "0.0")
+        .await
+        .expect("export workspace");
+    assert_eq!(
+        Some(7), // expected
+        exported
+            .clone()
+            .into_latest()
+            .metadata
+            .component_concurrency_limit  // actual
+    );
+
+    workspace
+        .set_component_concurrency_limit(ctx, None)
+        .await
+        .expect("unset component concurrency limit");
+    workspace
+        .import(ctx, exported)
+        .await
+        .expect("import workspace");
+
+    let workspace = Workspace::get_by_pk(ctx, &workspace_pk)
+        .await
+        .expect("execute find workspace")
+        .expect("find workspace");
+    assert_eq!(
+        Some(7),                                     // expected
+        workspace.raw_component_concurrency_limit()  // actual
+    );
+}
+
+#[test]
+async fn check_migratable_skips_failed_change_sets(ctx: &mut DalContext) {
+    let head_change_set_id = ctx
+        .get_workspace_default_change_set_id()
+        .await
+        .expect("could not get default change set id");
+    let open_change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    let mut failed_change_set = ChangeSetTestHelpers::fork_from_head_change_set(ctx)
+        .await
+        .expect("could not fork change set");
+    failed_change_set
+        .update_status(ctx, ChangeSetStatus::Failed)
+        .await
+        .expect("could not mark change set as failed");
+
+    let results = Workspace::check_migratable(ctx)
+        .await
+        .expect("could not check migratable");
+
+    assert!(!results
+        .iter()
+        .any(|(change_set_id, _)| *change_set_id == failed_change_set.id));
+    for change_set_id in [head_change_set_id, open_change_set.id] {
+        assert!(results.contains(&(change_set_id, true)));
+    }
+}
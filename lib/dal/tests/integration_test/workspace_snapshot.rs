@@ -0,0 +1,609 @@
+use dal::workspace_snapshot::edge_weight::{
+    EdgeWeight, EdgeWeightKind, EdgeWeightKindDiscriminants,
+};
+use dal::workspace_snapshot::graph::WorkspaceSnapshotGraphDiscriminants;
+use dal::workspace_snapshot::migrator::SnapshotGraphMigrator;
+use dal::workspace_snapshot::node_weight::category_node_weight::CategoryNodeKind;
+use dal::workspace_snapshot::node_weight::NodeWeight;
+use dal::workspace_snapshot::{IntegrityViolation, WorkspaceSnapshotError};
+use dal::{
+    ChangeSet, ChangeSetStatus, ContentHash, DalContext, PropKind, Workspace, WorkspaceSnapshot,
+    WorkspaceSnapshotAddress,
+};
+use dal_test::helpers::{
+    create_component_for_default_schema_name_in_default_view, ChangeSetTestHelpers,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn get_latest_node_indices_matches_individual_resolution(ctx: &mut DalContext) {
+    create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+    let mut node_indices = vec![snapshot.root().await.expect("could not get root")];
+    node_indices.extend(
+        snapshot
+            .nodes()
+            .await
+            .expect("could not list nodes")
+            .into_iter()
+            .map(|(_, node_index)| node_index)
+            .take(10),
+    );
+    // Duplicates must be resolved in place, too.
+    node_indices.push(node_indices[0]);
+
+    let mut individually_resolved = Vec::with_capacity(node_indices.len());
+    for node_index in &node_indices {
+        individually_resolved.push(
+            snapshot
+                .get_latest_node_index(*node_index)
+                .await
+                .expect("could not get latest node index"),
+        );
+    }
+
+    let batch_resolved = snapshot
+        .get_latest_node_indices(&node_indices)
+        .await
+        .expect("could not get latest node indices");
+
+    assert_eq!(
+        individually_resolved, // expected
+        batch_resolved         // actual
+    );
+}
+
+#[test]
+async fn add_ordered_node_rejects_scalar_props(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+
+    let id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let lineage_id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let scalar = NodeWeight::new_prop(
+        id,
+        lineage_id,
+        PropKind::String,
+        "scalar",
+        ContentHash::new(b"scalar"),
+    );
+    let result = snapshot.add_ordered_node(scalar).await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceSnapshotError::NodeNotOrderable(node_id, PropKind::String)) if node_id == id
+    ));
+
+    let id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let lineage_id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let container = NodeWeight::new_prop(
+        id,
+        lineage_id,
+        PropKind::Object,
+        "container",
+        ContentHash::new(b"container"),
+    );
+    snapshot
+        .add_ordered_node(container)
+        .await
+        .expect("could not add ordered node for container prop");
+}
+
+#[test]
+async fn update_content_for_missing_node(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+
+    let id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let result = snapshot
+        .update_content(id, ContentHash::new(b"nobody home"))
+        .await;
+    assert!(matches!(
+        result,
+        Err(WorkspaceSnapshotError::NodeNotFound(node_id)) if node_id == id
+    ));
+}
+
+#[test]
+async fn find_nodes_with_content_hash(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+
+    let content_hash = ContentHash::new(b"shared content");
+    let mut expected_ids = vec![];
+    for name in ["first", "second"] {
+        let id = snapshot
+            .generate_ulid()
+            .await
+            .expect("could not generate id");
+        let lineage_id = snapshot
+            .generate_ulid()
+            .await
+            .expect("could not generate id");
+        snapshot
+            .add_or_replace_node(NodeWeight::new_prop(
+                id,
+                lineage_id,
+                PropKind::String,
+                name,
+                content_hash,
+            ))
+            .await
+            .expect("could not add node");
+        expected_ids.push(id);
+    }
+
+    let mut found_ids = snapshot
+        .find_nodes_with_content_hash(content_hash)
+        .await
+        .expect("could not find nodes");
+    found_ids.sort();
+    expected_ids.sort();
+    assert_eq!(
+        expected_ids, // expected
+        found_ids     // actual
+    );
+
+    assert!(snapshot
+        .find_nodes_with_content_hash(ContentHash::new(b"nobody uses this"))
+        .await
+        .expect("could not find nodes")
+        .is_empty());
+}
+
+#[test]
+async fn with_mutations_applies_all_changes(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+
+    let (parent_id, child_ids) = snapshot
+        .with_mutations(|graph| {
+            let parent_id = graph.generate_ulid()?;
+            let lineage_id = graph.generate_ulid()?;
+            let parent_index = graph.add_or_replace_node(NodeWeight::new_prop(
+                parent_id,
+                lineage_id,
+                PropKind::Object,
+                "parent",
+                ContentHash::new(b"parent"),
+            ))?;
+
+            let mut child_ids = Vec::new();
+            for name in ["first", "second", "third"] {
+                let child_id = graph.generate_ulid()?;
+                let lineage_id = graph.generate_ulid()?;
+                let child_index = graph.add_or_replace_node(NodeWeight::new_prop(
+                    child_id,
+                    lineage_id,
+                    PropKind::String,
+                    name,
+                    ContentHash::new(name.as_bytes()),
+                ))?;
+                graph.add_edge(
+                    parent_index,
+                    EdgeWeight::new(EdgeWeightKind::new_use()),
+                    child_index,
+                )?;
+                child_ids.push(child_id);
+            }
+
+            Ok((parent_id, child_ids))
+        })
+        .await
+        .expect("could not apply mutations");
+
+    let mut expected = Vec::new();
+    for child_id in child_ids {
+        expected.push(
+            snapshot
+                .get_node_index_by_id(child_id)
+                .await
+                .expect("could not find child node"),
+        );
+    }
+    expected.sort();
+
+    let mut actual = snapshot
+        .outgoing_targets_for_edge_weight_kind(parent_id, EdgeWeightKindDiscriminants::Use)
+        .await
+        .expect("could not get outgoing targets");
+    actual.sort();
+
+    assert_eq!(
+        expected, // expected
+        actual    // actual
+    );
+}
+
+#[test]
+async fn detect_updates_does_not_copy_unmutated_onto_snapshot(ctx: &mut DalContext) {
+    create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let base = WorkspaceSnapshot::find_for_change_set(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find snapshot");
+    let onto = WorkspaceSnapshot::find_for_change_set(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find snapshot");
+    assert!(!onto.has_working_copy().await);
+
+    let updates = base
+        .detect_updates(&onto)
+        .await
+        .expect("could not detect updates");
+
+    assert!(updates.is_empty());
+    assert!(
+        !onto.has_working_copy().await,
+        "the onto snapshot should be read from its read-only graph"
+    );
+    assert!(!base.has_working_copy().await);
+}
+
+#[test]
+async fn pinned_snapshot_survives_visibility_update(ctx: &mut DalContext) {
+    let component =
+        create_component_for_default_schema_name_in_default_view(ctx, "swifty", "pinned")
+            .await
+            .expect("could not create component");
+
+    let pin = ctx.pin_snapshot().expect("could not pin snapshot");
+    assert!(pin.get_node_index_by_id_opt(component.id()).await.is_some());
+
+    // The component was never committed, so the refreshed snapshot does not have it.
+    ctx.update_snapshot_to_visibility()
+        .await
+        .expect("could not update snapshot to visibility");
+    assert!(ctx
+        .workspace_snapshot()
+        .expect("could not get snapshot")
+        .get_node_index_by_id_opt(component.id())
+        .await
+        .is_none());
+
+    assert!(
+        pin.get_node_index_by_id_opt(component.id()).await.is_some(),
+        "reads through the pin should still see the pinned snapshot"
+    );
+}
+
+#[test]
+async fn changed_since_last_write_counts_mutations(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+    snapshot.write(ctx).await.expect("could not write snapshot");
+    assert_eq!(
+        0, // expected
+        snapshot
+            .changed_since_last_write()
+            .await
+            .expect("could not count changes")  // actual
+    );
+
+    let mut ids = Vec::new();
+    for (name, kind) in [("parent", PropKind::Object), ("child", PropKind::String)] {
+        let id = snapshot
+            .generate_ulid()
+            .await
+            .expect("could not generate id");
+        let lineage_id = snapshot
+            .generate_ulid()
+            .await
+            .expect("could not generate id");
+        snapshot
+            .add_or_replace_node(NodeWeight::new_prop(
+                id,
+                lineage_id,
+                kind,
+                name,
+                ContentHash::new(name.as_bytes()),
+            ))
+            .await
+            .expect("could not add node");
+        ids.push(id);
+    }
+    snapshot
+        .add_edge(ids[0], EdgeWeight::new(EdgeWeightKind::new_use()), ids[1])
+        .await
+        .expect("could not add edge");
+
+    // Touching an already dirty node does not count as a new change
+    snapshot
+        .update_content(ids[1], ContentHash::new(b"child, again"))
+        .await
+        .expect("could not update content");
+
+    assert_eq!(
+        3, // expected
+        snapshot
+            .changed_since_last_write()
+            .await
+            .expect("could not count changes")  // actual
+    );
+
+    snapshot.write(ctx).await.expect("could not write snapshot");
+    assert_eq!(
+        0, // expected
+        snapshot
+            .changed_since_last_write()
+            .await
+            .expect("could not count changes")  // actual
+    );
+}
+
+#[test]
+async fn with_mutations_marks_everything_changed(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+    snapshot.write(ctx).await.expect("could not write snapshot");
+
+    snapshot
+        .with_mutations(|graph| {
+            let id = graph.generate_ulid()?;
+            let lineage_id = graph.generate_ulid()?;
+            graph.add_or_replace_node(NodeWeight::new_prop(
+                id,
+                lineage_id,
+                PropKind::String,
+                "untracked",
+                ContentHash::new(b"untracked"),
+            ))?;
+            Ok(())
+        })
+        .await
+        .expect("could not apply mutations");
+
+    // The single node added through the closure is not tracked on its own, so the whole graph
+    // counts as changed.
+    assert!(
+        snapshot
+            .changed_since_last_write()
+            .await
+            .expect("could not count changes")
+            > 1
+    );
+
+    snapshot.write(ctx).await.expect("could not write snapshot");
+    assert_eq!(
+        0, // expected
+        snapshot
+            .changed_since_last_write()
+            .await
+            .expect("could not count changes")  // actual
+    );
+}
+
+#[test]
+async fn read_only_view_is_point_in_time(ctx: &mut DalContext) {
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let snapshot = WorkspaceSnapshot::find_for_change_set(ctx, ctx.change_set_id())
+        .await
+        .expect("could not find snapshot");
+    let unmodified_view = snapshot.read_only_view().await;
+    assert!(
+        !snapshot.has_working_copy().await,
+        "taking a view should not copy the read-only graph"
+    );
+
+    let mut ids = Vec::new();
+    for name in ["first", "second"] {
+        let id = snapshot
+            .generate_ulid()
+            .await
+            .expect("could not generate id");
+        let lineage_id = snapshot
+            .generate_ulid()
+            .await
+            .expect("could not generate id");
+        snapshot
+            .add_or_replace_node(NodeWeight::new_prop(
+                id,
+                lineage_id,
+                PropKind::String,
+                name,
+                ContentHash::new(name.as_bytes()),
+            ))
+            .await
+            .expect("could not add node");
+        ids.push(id);
+    }
+    let modified_view = snapshot.read_only_view().await;
+
+    let third_id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let third_lineage_id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    snapshot
+        .add_or_replace_node(NodeWeight::new_prop(
+            third_id,
+            third_lineage_id,
+            PropKind::String,
+            "third",
+            ContentHash::new(b"third"),
+        ))
+        .await
+        .expect("could not add node");
+
+    for id in &ids {
+        assert!(unmodified_view.get_node_index_by_id_opt(*id).is_none());
+        assert!(modified_view.get_node_index_by_id_opt(*id).is_some());
+    }
+    assert!(modified_view.get_node_index_by_id_opt(third_id).is_none());
+    assert!(snapshot
+        .read_only_view()
+        .await
+        .get_node_index_by_id_opt(third_id)
+        .is_some());
+}
+
+#[test]
+async fn get_or_create_category_node(ctx: &mut DalContext) {
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+
+    let existing_id = snapshot
+        .get_category_node(None, CategoryNodeKind::Component)
+        .await
+        .expect("could not get category node")
+        .expect("component category should exist");
+    assert_eq!(
+        existing_id, // expected
+        snapshot
+            .get_or_create_category_node(CategoryNodeKind::Component)
+            .await
+            .expect("could not get or create category node")  // actual
+    );
+
+    // Pretend the snapshot predates the category.
+    let old_id = snapshot
+        .get_category_node(None, CategoryNodeKind::DeprecatedActionBatch)
+        .await
+        .expect("could not get category node")
+        .expect("deprecated action batch category should exist");
+    snapshot
+        .remove_node_by_id(old_id)
+        .await
+        .expect("could not remove category node");
+    assert!(snapshot
+        .get_category_node(None, CategoryNodeKind::DeprecatedActionBatch)
+        .await
+        .expect("could not get category node")
+        .is_none());
+
+    let created_id = snapshot
+        .get_or_create_category_node(CategoryNodeKind::DeprecatedActionBatch)
+        .await
+        .expect("could not get or create category node");
+    assert_ne!(old_id, created_id);
+    assert_eq!(
+        Some(created_id), // expected
+        snapshot
+            .get_category_node(None, CategoryNodeKind::DeprecatedActionBatch)
+            .await
+            .expect("could not get category node")  // actual
+    );
+    assert_eq!(
+        created_id, // expected
+        snapshot
+            .get_or_create_category_node(CategoryNodeKind::DeprecatedActionBatch)
+            .await
+            .expect("could not get or create category node")  // actual
+    );
+}
+
+#[test]
+async fn verify_integrity(ctx: &mut DalContext) {
+    create_component_for_default_schema_name_in_default_view(ctx, "swifty", "shake it off")
+        .await
+        .expect("could not create component");
+    ChangeSetTestHelpers::commit_and_update_snapshot_to_visibility(ctx)
+        .await
+        .expect("could not commit and update snapshot to visibility");
+
+    let snapshot = ctx.workspace_snapshot().expect("could not get snapshot");
+    assert_eq!(
+        Vec::<IntegrityViolation>::new(), // expected
+        snapshot
+            .verify_integrity()
+            .await
+            .expect("could not verify integrity")  // actual
+    );
+
+    // A container prop without a parent or an ordering node is reported twice.
+    let id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    let lineage_id = snapshot
+        .generate_ulid()
+        .await
+        .expect("could not generate id");
+    snapshot
+        .add_or_replace_node(NodeWeight::new_prop(
+            id,
+            lineage_id,
+            PropKind::Object,
+            "orphan",
+            ContentHash::new(b"orphan"),
+        ))
+        .await
+        .expect("could not add node");
+
+    let violations = snapshot
+        .verify_integrity()
+        .await
+        .expect("could not verify integrity");
+    assert_eq!(
+        2,                // expected
+        violations.len()  // actual
+    );
+    assert!(violations.contains(&IntegrityViolation::PropIsOrphan(id.into())));
+    assert!(violations.contains(&IntegrityViolation::MissingOrderingNode(id)));
+}
+
+#[test]
+async fn migrate_all_reports_change_sets_that_failed_to_migrate(ctx: &mut DalContext) {
+    let mut broken_change_set = ChangeSet::new(
+        ctx,
+        "broken",
+        Some(ctx.change_set_id()),
+        ctx.change_set()
+            .expect("could not get change set")
+            .workspace_snapshot_address,
+    )
+    .await
+    .expect("could not create change set");
+    broken_change_set
+        .update_pointer(ctx, WorkspaceSnapshotAddress::new(b"missing"))
+        .await
+        .expect("could not update pointer");
+
+    // Pretend every workspace is behind so that the migrator runs.
+    Workspace::set_snapshot_version_for_all_workspaces(
+        ctx,
+        WorkspaceSnapshotGraphDiscriminants::V3,
+    )
+    .await
+    .expect("could not set snapshot version");
+
+    let failed_change_set_ids = SnapshotGraphMigrator::new()
+        .migrate_all(ctx)
+        .await
+        .expect("could not migrate snapshots");
+    assert!(failed_change_set_ids.contains(&broken_change_set.id));
+
+    let broken_change_set = ChangeSet::find(ctx, broken_change_set.id)
+        .await
+        .expect("could not find change set")
+        .expect("change set not found");
+    assert_eq!(
+        ChangeSetStatus::Failed,  // expected
+        broken_change_set.status, // actual
+    );
+}
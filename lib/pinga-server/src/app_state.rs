@@ -1,28 +1,50 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use dal::DalContextBuilder;
+use tokio_util::sync::CancellationToken;
 
-use crate::server::ServerMetadata;
+use crate::{in_flight_jobs::InFlightJobs, job_registry::JobRegistry, server::ServerMetadata};
 
 /// Application state.
 #[derive(Clone, Debug)]
 pub struct AppState {
     pub metadata: Arc<ServerMetadata>,
     pub concurrency_limit: usize,
+    /// Maximum number of attempts made to run a job
+    pub job_max_attempts: u32,
+    /// Base delay for the exponential backoff between job attempts
+    pub job_retry_base_delay: Duration,
+    /// Job kinds that can be processed
+    pub job_registry: Arc<JobRegistry>,
+    /// Jobs currently being processed
+    pub in_flight_jobs: InFlightJobs,
+    /// Cancelled when in-flight jobs should stop early (e.g. they did not drain during shutdown)
+    pub job_cancellation_token: CancellationToken,
     /// DAL context builder for each processing request
     pub ctx_builder: DalContextBuilder,
 }
 
 impl AppState {
     /// Creates a new [`AppState`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         metadata: Arc<ServerMetadata>,
         concurrency_limit: usize,
+        job_max_attempts: u32,
+        job_retry_base_delay: Duration,
+        job_registry: Arc<JobRegistry>,
+        in_flight_jobs: InFlightJobs,
+        job_cancellation_token: CancellationToken,
         ctx_builder: DalContextBuilder,
     ) -> Self {
         Self {
             metadata,
             concurrency_limit,
+            job_max_attempts,
+            job_retry_base_delay,
+            job_registry,
+            in_flight_jobs,
+            job_cancellation_token,
             ctx_builder,
         }
     }
@@ -1,4 +1,4 @@
-use std::{env, path::Path};
+use std::{collections::HashMap, env, path::Path, time::Duration};
 
 use buck2_resources::Buck2Resources;
 use derive_builder::Builder;
@@ -17,6 +17,9 @@ use ulid::Ulid;
 pub use si_settings::{StandardConfig, StandardConfigFile};
 
 const DEFAULT_CONCURRENCY_LIMIT: usize = 64;
+const DEFAULT_JOB_MAX_ATTEMPTS: u32 = 1;
+const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_JOB_RETRY_BASE_DELAY_MS: u64 = 100;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -55,6 +58,21 @@ pub struct Config {
     #[builder(default = "default_concurrency_limit()")]
     concurrency_limit: usize,
 
+    #[builder(default = "default_drain_timeout_secs()")]
+    drain_timeout_secs: u64,
+
+    #[builder(default = "default_job_max_attempts()")]
+    job_max_attempts: u32,
+
+    #[builder(default = "default_job_retry_base_delay_ms()")]
+    job_retry_base_delay_ms: u64,
+
+    #[builder(default)]
+    job_timeouts_secs: HashMap<String, u64>,
+
+    #[builder(default)]
+    function_execution_timeout_secs: Option<u64>,
+
     #[builder(default = "random_instance_id()")]
     instance_id: String,
 
@@ -102,6 +120,39 @@ impl Config {
         self.concurrency_limit
     }
 
+    /// Gets how long in-flight jobs may keep running after shutdown has been requested.
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.drain_timeout_secs)
+    }
+
+    /// Gets the maximum number of attempts made to run a job before it is recorded as failed.
+    /// Defaults to a single attempt, and job kinds that are not safe to re-run (e.g. actions) only
+    /// ever get one attempt.
+    pub fn job_max_attempts(&self) -> u32 {
+        self.job_max_attempts
+    }
+
+    /// Gets the base delay for the exponential backoff between job attempts.
+    pub fn job_retry_base_delay(&self) -> Duration {
+        Duration::from_millis(self.job_retry_base_delay_ms)
+    }
+
+    /// Gets the per-kind limits on how long a single job attempt may run. Job kinds without an
+    /// entry have no limit.
+    pub fn job_timeouts(&self) -> HashMap<String, Duration> {
+        self.job_timeouts_secs
+            .iter()
+            .map(|(kind, secs)| (kind.clone(), Duration::from_secs(*secs)))
+            .collect()
+    }
+
+    /// Gets how long a single function execution may run before veritech kills it. `None` leaves
+    /// executions bounded by veritech's own execution timeout.
+    pub fn function_execution_timeout(&self) -> Option<Duration> {
+        self.function_execution_timeout_secs
+            .map(Duration::from_secs)
+    }
+
     /// Gets the config's instance ID.
     pub fn instance_id(&self) -> &str {
         self.instance_id.as_ref()
@@ -123,6 +174,16 @@ pub struct ConfigFile {
     crypto: VeritechCryptoConfig,
     #[serde(default = "default_concurrency_limit")]
     concurrency_limit: usize,
+    #[serde(default = "default_drain_timeout_secs")]
+    drain_timeout_secs: u64,
+    #[serde(default = "default_job_max_attempts")]
+    job_max_attempts: u32,
+    #[serde(default = "default_job_retry_base_delay_ms")]
+    job_retry_base_delay_ms: u64,
+    #[serde(default)]
+    job_timeouts_secs: HashMap<String, u64>,
+    #[serde(default)]
+    function_execution_timeout_secs: Option<u64>,
     #[serde(default = "random_instance_id")]
     instance_id: String,
     #[serde(default = "default_layer_db_config")]
@@ -137,6 +198,11 @@ impl Default for ConfigFile {
             pg: Default::default(),
             nats: Default::default(),
             concurrency_limit: default_concurrency_limit(),
+            drain_timeout_secs: default_drain_timeout_secs(),
+            job_max_attempts: default_job_max_attempts(),
+            job_retry_base_delay_ms: default_job_retry_base_delay_ms(),
+            job_timeouts_secs: Default::default(),
+            function_execution_timeout_secs: None,
             crypto: Default::default(),
             instance_id: random_instance_id(),
             layer_db_config: default_layer_db_config(),
@@ -160,6 +226,11 @@ impl TryFrom<ConfigFile> for Config {
         config.nats(value.nats);
         config.crypto(value.crypto);
         config.concurrency_limit(value.concurrency_limit);
+        config.drain_timeout_secs(value.drain_timeout_secs);
+        config.job_max_attempts(value.job_max_attempts);
+        config.job_retry_base_delay_ms(value.job_retry_base_delay_ms);
+        config.job_timeouts_secs(value.job_timeouts_secs);
+        config.function_execution_timeout_secs(value.function_execution_timeout_secs);
         config.instance_id(value.instance_id);
         config.symmetric_crypto_service(value.symmetric_crypto_service.try_into()?);
         config.layer_db_config(value.layer_db_config);
@@ -183,6 +254,18 @@ fn default_concurrency_limit() -> usize {
     DEFAULT_CONCURRENCY_LIMIT
 }
 
+fn default_drain_timeout_secs() -> u64 {
+    DEFAULT_DRAIN_TIMEOUT_SECS
+}
+
+fn default_job_max_attempts() -> u32 {
+    DEFAULT_JOB_MAX_ATTEMPTS
+}
+
+fn default_job_retry_base_delay_ms() -> u64 {
+    DEFAULT_JOB_RETRY_BASE_DELAY_MS
+}
+
 fn default_layer_db_config() -> LayerDbConfig {
     LayerDbConfig::default()
 }
@@ -1,9 +1,8 @@
-use std::{result, str::Utf8Error, sync::Arc};
+use std::{result, str::Utf8Error, sync::Arc, time::Duration};
 
 use dal::{
     job::{
         consumer::{JobConsumer, JobConsumerError, JobInfo},
-        definition::{compute_validation::ComputeValidation, ActionJob, DependentValuesUpdate},
         producer::BlockingJobError,
     },
     DalContextBuilder,
@@ -19,7 +18,7 @@ use telemetry::prelude::*;
 use telemetry_nats::propagation;
 use thiserror::Error;
 
-use crate::{app_state::AppState, server::ServerMetadata};
+use crate::app_state::AppState;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -66,15 +65,9 @@ pub async fn process_request(
         None => None,
     };
 
-    execute_job(
-        state.metadata,
-        state.concurrency_limit,
-        state.ctx_builder,
-        subject,
-        reply_subject,
-        job_info,
-    )
-    .await;
+    let _in_flight_job = state.in_flight_jobs.track(&job_info.id, &job_info.kind);
+
+    execute_job(&state, subject, reply_subject, job_info).await;
     Ok(())
 }
 
@@ -86,12 +79,12 @@ pub async fn process_request(
         // TODO: revive these fields as needed
         // concurrency.at_capacity = concurrency_limit == concurrency_count,
         // concurrency.count = concurrency_count,
-        concurrency.limit = concurrency_limit,
+        concurrency.limit = state.concurrency_limit,
         job.id = job_info.id,
-        job.instance = metadata.instance_id(),
+        job.instance = state.metadata.instance_id(),
         job.invoked_args = Empty,
         job.invoked_name = job_info.kind,
-        job.invoked_provider = metadata.job_invoked_provider(),
+        job.invoked_provider = state.metadata.job_invoked_provider(),
         job.trigger = "pubsub",
         messaging.destination = Empty,
         messaging.destination_kind = "topic",
@@ -106,9 +99,7 @@ pub async fn process_request(
     )
 )]
 async fn execute_job(
-    metadata: Arc<ServerMetadata>,
-    concurrency_limit: usize,
-    ctx_builder: DalContextBuilder,
+    state: &AppState,
     subject: Subject,
     maybe_reply_subject: Option<Subject>,
     job_info: JobInfo,
@@ -145,7 +136,7 @@ async fn execute_job(
     span.record("otel.name", otel_name.as_str());
     span.record("si.workspace.id", workspace_id_str);
 
-    let reply_message = match execute_job_inner(ctx_builder.clone(), job_info).await {
+    let reply_message = match execute_job_inner(state, job_info).await {
         Ok(_) => {
             span.record_ok();
             Ok(())
@@ -154,7 +145,7 @@ async fn execute_job(
             error!(
                 error = ?err,
                 job.invocation_id = %id,
-                job.instance = metadata.instance_id(),
+                job.instance = state.metadata.instance_id(),
                 "job execution failed"
             );
             let new_err = Err(BlockingJobError::JobExecution(err.to_string()));
@@ -167,7 +158,8 @@ async fn execute_job(
     // If a reply subject is set then the caller has requested we publish a reply
     if let Some(reply_subject) = maybe_reply_subject {
         if let Ok(message) = serde_json::to_vec(&reply_message) {
-            if let Err(err) = ctx_builder
+            if let Err(err) = state
+                .ctx_builder
                 .nats_conn()
                 .publish_with_headers(
                     reply_subject,
@@ -182,29 +174,79 @@ async fn execute_job(
     }
 }
 
-async fn execute_job_inner(mut ctx_builder: DalContextBuilder, job_info: JobInfo) -> Result<()> {
+async fn execute_job_inner(state: &AppState, job_info: JobInfo) -> Result<()> {
+    let job_registry = &state.job_registry;
+    let retry_base_delay = state.job_retry_base_delay;
+    let mut ctx_builder = state.ctx_builder.clone();
     if job_info.blocking {
         ctx_builder.set_blocking();
     }
 
-    let job = match job_info.kind.as_str() {
-        stringify!(DependentValuesUpdate) => {
-            Box::new(DependentValuesUpdate::try_from(job_info.clone())?)
-                as Box<dyn JobConsumer + Send + Sync>
-        }
-        stringify!(ActionJob) => {
-            Box::new(ActionJob::try_from(job_info.clone())?) as Box<dyn JobConsumer + Send + Sync>
-        }
-        stringify!(ComputeValidation) => Box::new(ComputeValidation::try_from(job_info.clone())?)
-            as Box<dyn JobConsumer + Send + Sync>,
-        kind => return Err(HandlerError::UnknownJobKind(kind.to_owned())),
+    let build_job = job_registry
+        .get(&job_info.kind)
+        .ok_or_else(|| HandlerError::UnknownJobKind(job_info.kind.clone()))?;
+    let timeout = job_registry.timeout(&job_info.kind);
+    let max_attempts = if job_registry.retries_enabled(&job_info.kind) {
+        state.job_max_attempts
+    } else {
+        1
     };
+    let job: Arc<dyn JobConsumer + Send + Sync> =
+        Arc::from(build_job(job_info, state.job_cancellation_token.clone())?);
 
     info!("Processing job");
 
-    job.run_job(ctx_builder.clone()).await?;
+    // Each attempt builds a fresh `DalContext` from the builder. Once all attempts are exhausted
+    // the error is returned and the failure is recorded by the caller.
+    let mut attempt = 1;
+    loop {
+        match run_job_with_timeout(job.clone(), ctx_builder.clone(), timeout).await {
+            Ok(()) => break,
+            Err(err) if attempt < max_attempts => {
+                let delay = retry_delay(retry_base_delay, attempt);
+                warn!(
+                    si.error.message = ?err,
+                    job.attempt = attempt,
+                    job.max_attempts = max_attempts,
+                    "job execution failed, retrying in {delay:?}"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
 
     info!("Finished processing job");
 
     Ok(())
 }
+
+/// Runs a single attempt of the job. If a timeout is given, the attempt runs in its own task which
+/// is aborted once the timeout elapses, so that any in-progress work (e.g. a veritech function
+/// execution) is cancelled rather than left holding a concurrency slot.
+async fn run_job_with_timeout(
+    job: Arc<dyn JobConsumer + Send + Sync>,
+    ctx_builder: DalContextBuilder,
+    timeout: Option<Duration>,
+) -> result::Result<(), JobConsumerError> {
+    let Some(timeout) = timeout else {
+        return job.run_job(ctx_builder).await;
+    };
+
+    let kind = job.type_name();
+    let mut handle =
+        tokio::spawn(async move { job.run_job(ctx_builder).await }.instrument(Span::current()));
+    match tokio::time::timeout(timeout, &mut handle).await {
+        Ok(join_result) => join_result?,
+        Err(_) => {
+            handle.abort();
+            Err(JobConsumerError::Timeout(kind, timeout))
+        }
+    }
+}
+
+/// Computes the exponential backoff delay to wait after the given (1-based) failed attempt.
+fn retry_delay(base_delay: Duration, attempt: u32) -> Duration {
+    base_delay.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+}
@@ -0,0 +1,54 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Tracks the jobs currently being processed, keyed by job id.
+#[derive(Clone, Debug, Default)]
+pub struct InFlightJobs {
+    jobs: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl InFlightJobs {
+    /// Records a job as in flight until the returned [`InFlightJobGuard`] is dropped.
+    pub fn track(&self, id: impl Into<String>, kind: impl Into<String>) -> InFlightJobGuard {
+        let id = id.into();
+        self.lock().insert(id.clone(), kind.into());
+        InFlightJobGuard {
+            jobs: self.clone(),
+            id,
+        }
+    }
+
+    /// Returns the `(id, kind)` pairs of the jobs still in flight, sorted by id.
+    pub fn snapshot(&self) -> Vec<(String, String)> {
+        let mut jobs: Vec<_> = self
+            .lock()
+            .iter()
+            .map(|(id, kind)| (id.clone(), kind.clone()))
+            .collect();
+        jobs.sort();
+        jobs
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        // A panic while holding the lock cannot leave the map inconsistent, so recover from
+        // poisoning rather than propagating it.
+        self.jobs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Removes a job from its [`InFlightJobs`] when dropped.
+#[derive(Debug)]
+pub struct InFlightJobGuard {
+    jobs: InFlightJobs,
+    id: String,
+}
+
+impl Drop for InFlightJobGuard {
+    fn drop(&mut self) {
+        self.jobs.lock().remove(&self.id);
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use dal::job::{
+    consumer::{JobConsumer, JobConsumerError, JobConsumerResult, JobInfo},
+    definition::{compute_validation::ComputeValidation, ActionJob, DependentValuesUpdate},
+};
+use tokio_util::sync::CancellationToken;
+
+/// Builds a job consumer from the [`JobInfo`] received off the work queue. Jobs that can stop
+/// early should do so once the given [`CancellationToken`] is cancelled.
+pub type JobFactory =
+    fn(JobInfo, CancellationToken) -> JobConsumerResult<Box<dyn JobConsumer + Send + Sync>>;
+
+/// Maps job kinds to the [`JobFactory`] used to build them, to an optional limit on how long a
+/// single attempt of that kind may run and to whether failed attempts of that kind may be retried.
+#[derive(Clone, Debug, Default)]
+pub struct JobRegistry {
+    factories: HashMap<&'static str, JobFactory>,
+    timeouts: HashMap<String, Duration>,
+    without_retries: HashSet<&'static str>,
+}
+
+impl JobRegistry {
+    /// Creates a [`JobRegistry`] with every job kind that pinga knows how to run.
+    pub fn with_builtin_jobs() -> Self {
+        let mut registry = Self::default();
+        registry.register(
+            stringify!(DependentValuesUpdate),
+            build_dependent_values_update,
+        );
+        registry.register(stringify!(ActionJob), build::<ActionJob>);
+        // Actions change things in the real world, so a failed action must not be run again
+        // without the user asking for it.
+        registry.disable_retries(stringify!(ActionJob));
+        registry.register(stringify!(ComputeValidation), build::<ComputeValidation>);
+        registry
+    }
+
+    /// Registers a [`JobFactory`] for the given job kind, replacing any previous registration.
+    pub fn register(&mut self, kind: &'static str, factory: JobFactory) {
+        self.factories.insert(kind, factory);
+    }
+
+    /// Makes failed jobs of the given kind fail right away rather than being retried.
+    pub fn disable_retries(&mut self, kind: &'static str) {
+        self.without_retries.insert(kind);
+    }
+
+    /// Returns whether failed jobs of the given kind may be retried.
+    pub fn retries_enabled(&self, kind: &str) -> bool {
+        !self.without_retries.contains(kind)
+    }
+
+    /// Sets the per-kind timeouts, replacing any previously set.
+    pub fn with_timeouts(mut self, timeouts: HashMap<String, Duration>) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Gets the timeout for the given job kind, if one has been set.
+    pub fn timeout(&self, kind: &str) -> Option<Duration> {
+        self.timeouts.get(kind).copied()
+    }
+
+    /// Gets the [`JobFactory`] for the given job kind, if one has been registered.
+    pub fn get(&self, kind: &str) -> Option<JobFactory> {
+        self.factories.get(kind).copied()
+    }
+}
+
+fn build<J>(
+    job_info: JobInfo,
+    _cancellation_token: CancellationToken,
+) -> JobConsumerResult<Box<dyn JobConsumer + Send + Sync>>
+where
+    J: TryFrom<JobInfo, Error = JobConsumerError> + JobConsumer + Send + Sync + 'static,
+{
+    Ok(Box::new(J::try_from(job_info)?))
+}
+
+fn build_dependent_values_update(
+    job_info: JobInfo,
+    cancellation_token: CancellationToken,
+) -> JobConsumerResult<Box<dyn JobConsumer + Send + Sync>> {
+    Ok(Box::new(DependentValuesUpdate::try_from(job_info)?)
+        .with_cancellation_token(cancellation_token))
+}
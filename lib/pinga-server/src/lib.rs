@@ -1,6 +1,8 @@
 mod app_state;
 mod config;
 mod handlers;
+mod in_flight_jobs;
+pub mod job_registry;
 pub mod server;
 
 use std::io;
@@ -15,6 +17,7 @@ pub use crate::{
         detect_and_configure_development, Config, ConfigBuilder, ConfigError, ConfigFile,
         StandardConfig, StandardConfigFile,
     },
+    job_registry::JobRegistry,
     server::Server,
 };
 
@@ -3,6 +3,7 @@ use std::{
     future::{Future, IntoFuture as _},
     io,
     sync::Arc,
+    time::Duration,
 };
 
 use dal::{
@@ -34,7 +35,10 @@ use telemetry_utils::metric;
 use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use veritech_client::Client as VeritechClient;
 
-use crate::{app_state::AppState, handlers, Config, ServerError, ServerResult};
+use crate::{
+    app_state::AppState, handlers, in_flight_jobs::InFlightJobs, job_registry::JobRegistry, Config,
+    ServerError, ServerResult,
+};
 
 const CONSUMER_NAME: &str = "pinga-server";
 
@@ -61,6 +65,9 @@ pub struct Server {
     metadata: Arc<ServerMetadata>,
     inner: Box<dyn Future<Output = io::Result<()>> + Unpin + Send>,
     shutdown_token: CancellationToken,
+    in_flight_jobs: InFlightJobs,
+    job_cancellation_token: CancellationToken,
+    drain_timeout: Duration,
 }
 
 impl fmt::Debug for Server {
@@ -68,6 +75,9 @@ impl fmt::Debug for Server {
         f.debug_struct("Server")
             .field("metadata", &self.metadata)
             .field("shutdown_token", &self.shutdown_token)
+            .field("in_flight_jobs", &self.in_flight_jobs)
+            .field("job_cancellation_token", &self.job_cancellation_token)
+            .field("drain_timeout", &self.drain_timeout)
             .finish()
     }
 }
@@ -87,7 +97,8 @@ impl Server {
         let nats_streams = JetstreamStreams::new(nats.clone()).await?;
         let pg_pool = Self::create_pg_pool(config.pg_pool()).await?;
         let rebaser = Self::create_rebaser_client(nats.clone()).await?;
-        let veritech = Self::create_veritech_client(nats.clone());
+        let veritech =
+            Self::create_veritech_client(nats.clone(), config.function_execution_timeout());
         let job_processor = Self::create_job_processor(nats.clone());
         let symmetric_crypto_service =
             Self::create_symmetric_crypto_service(config.symmetric_crypto_service()).await?;
@@ -120,16 +131,25 @@ impl Server {
         Self::from_services(
             config.instance_id().to_string(),
             config.concurrency_limit(),
+            config.job_max_attempts(),
+            config.job_retry_base_delay(),
+            config.drain_timeout(),
+            JobRegistry::with_builtin_jobs().with_timeouts(config.job_timeouts()),
             services_context,
             token,
         )
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     #[instrument(name = "pinga.init.from_services", level = "info", skip_all)]
     pub async fn from_services(
         instance_id: impl Into<String>,
         concurrency_limit: usize,
+        job_max_attempts: u32,
+        job_retry_base_delay: Duration,
+        drain_timeout: Duration,
+        job_registry: JobRegistry,
         services_context: ServicesContext,
         shutdown_token: CancellationToken,
     ) -> ServerResult<Self> {
@@ -157,8 +177,19 @@ impl Server {
             .await?;
 
         let ctx_builder = DalContext::builder(services_context, false);
-
-        let state = AppState::new(metadata.clone(), concurrency_limit, ctx_builder);
+        let in_flight_jobs = InFlightJobs::default();
+        let job_cancellation_token = CancellationToken::new();
+
+        let state = AppState::new(
+            metadata.clone(),
+            concurrency_limit,
+            job_max_attempts,
+            job_retry_base_delay,
+            Arc::new(job_registry),
+            in_flight_jobs.clone(),
+            job_cancellation_token.clone(),
+            ctx_builder,
+        );
 
         let app = ServiceBuilder::new()
             .layer(
@@ -185,6 +216,9 @@ impl Server {
             metadata,
             inner: Box::new(inner.into_future()),
             shutdown_token,
+            in_flight_jobs,
+            job_cancellation_token,
+            drain_timeout,
         })
     }
 
@@ -196,7 +230,42 @@ impl Server {
     }
 
     pub async fn try_run(self) -> ServerResult<()> {
-        self.inner.await.map_err(ServerError::Naxum)?;
+        let Self {
+            mut inner,
+            shutdown_token,
+            in_flight_jobs,
+            job_cancellation_token,
+            drain_timeout,
+            ..
+        } = self;
+
+        tokio::select! {
+            result = &mut inner => result.map_err(ServerError::Naxum)?,
+            _ = shutdown_token.cancelled() => {
+                // Give in-flight jobs a bounded amount of time to finish so that a hung job cannot
+                // wedge the shutdown.
+                match tokio::time::timeout(drain_timeout, &mut inner).await {
+                    Ok(result) => result.map_err(ServerError::Naxum)?,
+                    Err(_) => {
+                        // Ask the remaining jobs to stop early so that the ones that can (e.g.
+                        // dependent values updates) record how far they got before we exit.
+                        warn!(
+                            jobs = ?in_flight_jobs.snapshot(),
+                            "timed out after {drain_timeout:?} waiting for in-flight jobs to drain, cancelling them",
+                        );
+                        job_cancellation_token.cancel();
+                        if tokio::time::timeout(drain_timeout, &mut inner).await.is_err() {
+                            warn!(
+                                jobs = ?in_flight_jobs.snapshot(),
+                                "timed out after {drain_timeout:?} waiting for cancelled jobs to finish, shutting down anyway",
+                            );
+                        }
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
         info!("pinga main loop shutdown complete");
         Ok(())
     }
@@ -234,8 +303,11 @@ impl Server {
     }
 
     #[instrument(name = "pinga.init.create_veritech_client", level = "info", skip_all)]
-    fn create_veritech_client(nats: NatsClient) -> VeritechClient {
-        VeritechClient::new(nats)
+    fn create_veritech_client(
+        nats: NatsClient,
+        function_execution_timeout: Option<Duration>,
+    ) -> VeritechClient {
+        VeritechClient::new(nats).with_execution_timeout(function_execution_timeout)
     }
 
     #[instrument(name = "pinga.init.create_job_processor", level = "info", skip_all)]
@@ -394,11 +394,12 @@ mod handlers {
         if matches!(rebase_status, RebaseStatus::Success { .. }) {
             // If we find dependent value roots, then notify the serial dvu task to run at least
             // one more dvu
-            if ctx
+            let dependent_value_roots = ctx
                 .workspace_snapshot()?
-                .has_dependent_value_roots()
-                .await?
-            {
+                .dependent_value_roots_count()
+                .await?;
+            if dependent_value_roots > 0 {
+                debug!(dependent_value_roots, "dependent value roots pending");
                 run_notify.notify_one();
             }
 
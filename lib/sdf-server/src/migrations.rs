@@ -4,8 +4,12 @@ use audit_database::{
     AuditDatabaseContext, AuditDatabaseContextError, AuditDatabaseMigrationError,
 };
 use dal::{
-    cached_module::CachedModule, slow_rt::SlowRuntimeError,
-    workspace_snapshot::migrator::SnapshotGraphMigrator, ServicesContext,
+    cached_module::CachedModule,
+    slow_rt::SlowRuntimeError,
+    workspace_snapshot::{
+        graph::WorkspaceSnapshotGraphDiscriminants, migrator::SnapshotGraphMigrator,
+    },
+    ChangeSetId, ServicesContext, Workspace, WorkspacePk, WorkspaceSnapshotGraph,
 };
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -19,6 +23,8 @@ use crate::{init, Config};
 pub enum MigratorError {
     #[error("audit database context error: {0}")]
     AuditDatabaseContext(#[from] AuditDatabaseContextError),
+    #[error("snapshots failed to migrate for change sets: {0:?}")]
+    FailedSnapshotMigrations(Vec<ChangeSetId>),
     #[error("error while initializing: {0}")]
     Init(#[from] init::InitError),
     #[error("tokio join error: {0}")]
@@ -35,8 +41,12 @@ pub enum MigratorError {
     MigrateSnapshots(#[source] Box<dyn std::error::Error + 'static + Sync + Send>),
     #[error("module index url not set")]
     ModuleIndexNotSet,
+    #[error("after migrating, workspaces are not at snapshot version {0}: {1:?}")]
+    OutdatedWorkspaceSnapshots(WorkspaceSnapshotGraphDiscriminants, Vec<WorkspacePk>),
     #[error("slow runtime: {0}")]
     SlowRuntime(#[from] SlowRuntimeError),
+    #[error("error while verifying migrations: {0}")]
+    VerifyMigrations(#[source] Box<dyn std::error::Error + 'static + Sync + Send>),
 }
 
 impl MigratorError {
@@ -53,6 +63,13 @@ impl MigratorError {
     {
         Self::MigrateCachedModules(Box::new(err))
     }
+
+    fn verify_migrations<E>(err: E) -> Self
+    where
+        E: std::error::Error + 'static + Sync + Send,
+    {
+        Self::VerifyMigrations(Box::new(err))
+    }
 }
 
 type MigratorResult<T> = std::result::Result<T, MigratorError>;
@@ -119,7 +136,14 @@ impl Migrator {
             .await
             .map_err(|err| span.record_err(err))?;
 
-        self.migrate_snapshots()
+        let failed_change_set_ids = self
+            .migrate_snapshots()
+            .await
+            .map_err(|err| span.record_err(err))?;
+
+        // A partially failed migration must not let the server come up against workspaces it
+        // cannot read.
+        self.verify_snapshot_versions(failed_change_set_ids)
             .await
             .map_err(|err| span.record_err(err))?;
 
@@ -133,6 +157,73 @@ impl Migrator {
         Ok(())
     }
 
+    /// Logs the pending audit, layer db and dal database migrations and the workspaces whose
+    /// snapshots would be migrated, without changing anything.
+    #[instrument(
+        name = "sdf.migrator.dry_run",
+        level = "info",
+        skip_all,
+        fields(
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn dry_run(self) -> MigratorResult<()> {
+        let span = current_span_for_instrument_at!("info");
+
+        let pending = audit_database::pending_migrations(&self.audit_database_context)
+            .await
+            .map_err(|err| span.record_err(MigratorError::MigrateAuditDatabase(err)))?;
+        for migration in &pending {
+            info!(%migration, "pending audit database migration");
+        }
+        info!(count = pending.len(), "audit database migrations pending");
+
+        let pending = self
+            .services_context
+            .layer_db()
+            .pg_pending_migrations()
+            .await
+            .map_err(|err| span.record_err(MigratorError::MigrateLayerDbDatabase(err)))?;
+        for migration in &pending {
+            info!(%migration, "pending layer db database migration");
+        }
+        info!(
+            count = pending.len(),
+            "layer db database migrations pending"
+        );
+
+        let pending = dal::pending_migrations(self.services_context.pg_pool())
+            .await
+            .map_err(|err| span.record_err(MigratorError::MigrateDalDatabase(err)))?;
+        for migration in &pending {
+            info!(%migration, "pending dal database migration");
+        }
+        info!(count = pending.len(), "dal database migrations pending");
+
+        // The workspaces table may not exist yet (or may not have its final shape) until the
+        // pending migrations are applied, so snapshot versions can only be checked afterwards.
+        if pending.is_empty() {
+            let dal_context = self.services_context.clone().into_builder(true);
+            let ctx = dal_context
+                .build_default()
+                .await
+                .map_err(|err| span.record_err(MigratorError::verify_migrations(err)))?;
+
+            let outdated_workspace_pks = Workspace::list_pks_with_outdated_snapshot_version(&ctx)
+                .await
+                .map_err(|err| span.record_err(MigratorError::verify_migrations(err)))?;
+            info!(
+                count = outdated_workspace_pks.len(),
+                workspace_pks = ?outdated_workspace_pks,
+                "workspace snapshots pending migration"
+            );
+        }
+
+        span.record_ok();
+        Ok(())
+    }
+
     #[instrument(name = "sdf.migrator.migrate_audit_database", level = "info", skip_all)]
     async fn migrate_audit_database(&self) -> MigratorResult<()> {
         audit_database::migrate(&self.audit_database_context)
@@ -161,7 +252,7 @@ impl Migrator {
     }
 
     #[instrument(name = "sdf.migrator.migrate_snapshots", level = "info", skip_all)]
-    async fn migrate_snapshots(&self) -> MigratorResult<()> {
+    async fn migrate_snapshots(&self) -> MigratorResult<Vec<ChangeSetId>> {
         let dal_context = self.services_context.clone().into_builder(true);
         let ctx = dal_context
             .build_default()
@@ -169,13 +260,50 @@ impl Migrator {
             .map_err(MigratorError::migrate_snapshots)?;
 
         let mut migrator = SnapshotGraphMigrator::new();
-        migrator
+        let failed_change_set_ids = migrator
             .migrate_all(&ctx)
             .await
             .map_err(MigratorError::migrate_snapshots)?;
+        // Commit even on partial failure so the failed change sets stay marked as such.
         ctx.commit_no_rebase()
             .await
             .map_err(MigratorError::migrate_snapshots)?;
+        Ok(failed_change_set_ids)
+    }
+
+    #[instrument(
+        name = "sdf.migrator.verify_snapshot_versions",
+        level = "info",
+        skip_all
+    )]
+    async fn verify_snapshot_versions(
+        &self,
+        failed_change_set_ids: Vec<ChangeSetId>,
+    ) -> MigratorResult<()> {
+        // The snapshot versions are bumped even when some change sets fail to migrate, so the
+        // version check alone cannot catch a partial failure.
+        if !failed_change_set_ids.is_empty() {
+            return Err(MigratorError::FailedSnapshotMigrations(
+                failed_change_set_ids,
+            ));
+        }
+
+        let dal_context = self.services_context.clone().into_builder(true);
+        let ctx = dal_context
+            .build_default()
+            .await
+            .map_err(MigratorError::verify_migrations)?;
+
+        let outdated_workspace_pks = Workspace::list_pks_with_outdated_snapshot_version(&ctx)
+            .await
+            .map_err(MigratorError::verify_migrations)?;
+        if !outdated_workspace_pks.is_empty() {
+            return Err(MigratorError::OutdatedWorkspaceSnapshots(
+                WorkspaceSnapshotGraph::current_discriminant(),
+                outdated_workspace_pks,
+            ));
+        }
+
         Ok(())
     }
 
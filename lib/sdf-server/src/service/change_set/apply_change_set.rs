@@ -73,7 +73,7 @@ pub async fn apply_change_set(
     // We need to run a commit before apply so changes get saved
     ctx.commit().await?;
 
-    let change_set = ChangeSet::apply_to_base_change_set(&mut ctx).await?;
+    let (change_set, _) = ChangeSet::apply_to_base_change_set(&mut ctx).await?;
 
     track(
         &posthog_client,
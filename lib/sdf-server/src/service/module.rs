@@ -9,9 +9,9 @@ use axum::{
 use convert_case::{Case, Casing};
 use dal::{
     pkg::PkgError as DalPkgError, ChangeSetError, ChangeSetId, DalContextBuilder, FuncError,
-    SchemaError, SchemaId, SchemaVariantError, SchemaVariantId, StandardModelError, TenancyError,
-    TransactionsError, UserError, UserPk, WorkspaceError, WorkspacePk, WorkspaceSnapshotError,
-    WsEventError,
+    MachinePk, SchemaError, SchemaId, SchemaVariantError, SchemaVariantId, StandardModelError,
+    TenancyError, TransactionsError, UserError, UserPk, WorkspaceError, WorkspacePk,
+    WorkspaceSnapshotError, WsEventError,
 };
 use serde::{Deserialize, Serialize};
 use si_layer_cache::LayerDbError;
@@ -57,6 +57,8 @@ pub enum ModuleError {
     InvalidPackageFileName(String),
     #[error("invalid user: {0}")]
     InvalidUser(UserPk),
+    #[error("invalid user: machine actor ({0}) is not a user")]
+    InvalidUserMachine(MachinePk),
     #[error("invalid user system init")]
     InvalidUserSystemInit,
     #[error("IO Error: {0}")]
@@ -46,6 +46,9 @@ pub async fn begin_approval_process(
             .await?
             .ok_or(ModuleError::InvalidUser(*user_pk))?,
 
+        HistoryActor::Machine(machine_pk) => {
+            return Err(ModuleError::InvalidUserMachine(*machine_pk));
+        }
         HistoryActor::SystemInit => {
             return Err(ModuleError::InvalidUserSystemInit);
         }
@@ -108,7 +111,7 @@ pub async fn cancel_approval_process(
             Some(user.pk())
         }
 
-        HistoryActor::SystemInit => None,
+        HistoryActor::Machine(_) | HistoryActor::SystemInit => None,
     };
 
     let workspace_pk = ctx
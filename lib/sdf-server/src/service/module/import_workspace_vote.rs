@@ -32,6 +32,9 @@ pub async fn import_workspace_vote(
             .await?
             .ok_or(ModuleError::InvalidUser(*user_pk))?,
 
+        HistoryActor::Machine(machine_pk) => {
+            return Err(ModuleError::InvalidUserMachine(*machine_pk));
+        }
         HistoryActor::SystemInit => {
             return Err(ModuleError::InvalidUserSystemInit);
         }
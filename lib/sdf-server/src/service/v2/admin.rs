@@ -17,6 +17,7 @@ use thiserror::Error;
 
 use crate::{extract::AdminAccessBuilder, service::ApiError, AppState};
 
+mod clear_stuck_statuses;
 mod get_snapshot;
 mod kill_execution;
 mod list_change_sets;
@@ -49,6 +50,8 @@ pub enum AdminAPIError {
     Multipart(#[from] axum::extract::multipart::MultipartError),
     #[error("No multipart data found in request")]
     NoMultipartData,
+    #[error("status update error: {0}")]
+    StatusUpdate(#[from] dal::status::StatusUpdateError),
     #[error("tokio join error: {0}")]
     TokioJoin(#[from] tokio::task::JoinError),
     #[error("transactions error: {0}")]
@@ -188,6 +191,10 @@ pub fn v2_routes(state: AppState) -> Router<AppState> {
             "/workspaces/:workspace_pk/change_sets/:change_set_id/set_snapshot",
             post(set_snapshot::set_snapshot),
         )
+        .route(
+            "/workspaces/:workspace_pk/change_sets/:change_set_id/clear_stuck_statuses",
+            post(clear_stuck_statuses::clear_stuck_statuses),
+        )
         .nest("/prompts", prompts::routes())
         .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
         .route_layer(axum::middleware::from_extractor_with_state::<
@@ -0,0 +1,63 @@
+use axum::{
+    extract::{Host, OriginalUri, Path},
+    response::Json,
+};
+use dal::{status::StatusUpdate, ChangeSetId, Tenancy, WorkspacePk};
+use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
+
+use super::AdminAPIResult;
+use crate::{
+    extract::{AccessBuilder, HandlerContext, PosthogClient},
+    track_no_ctx,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearStuckStatusesResponse {
+    pub cleared_component_count: usize,
+}
+
+#[instrument(
+    name = "admin.clear_stuck_statuses",
+    level = "info",
+    skip_all,
+    fields(
+        si.change_set.id = %change_set_id,
+        si.workspace.id = %workspace_pk,
+    ),
+)]
+pub async fn clear_stuck_statuses(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Host(host_name): Host,
+    Path((workspace_pk, change_set_id)): Path<(WorkspacePk, ChangeSetId)>,
+) -> AdminAPIResult<Json<ClearStuckStatusesResponse>> {
+    // The status updates go out to the workspace being repaired, not to the admin's own.
+    let access_builder =
+        dal::AccessBuilder::new(Tenancy::new(workspace_pk), *access_builder.history_actor());
+    let ctx = builder
+        .build(access_builder.build(change_set_id.into()))
+        .await?;
+
+    let cleared_component_count = StatusUpdate::clear_stuck_for_change_set(&ctx).await?;
+
+    track_no_ctx(
+        &posthog_client,
+        &original_uri,
+        &host_name,
+        ctx.history_actor().distinct_id(),
+        Some(workspace_pk.to_string()),
+        Some(change_set_id.to_string()),
+        "admin.clear_stuck_statuses",
+        serde_json::json!({
+            "cleared_component_count": cleared_component_count,
+        }),
+    );
+
+    Ok(Json(ClearStuckStatusesResponse {
+        cleared_component_count,
+    }))
+}
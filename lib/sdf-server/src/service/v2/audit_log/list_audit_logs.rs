@@ -5,9 +5,9 @@ use axum::{
     extract::{Path, Query, State},
     Json,
 };
-use dal::{audit_logging, ChangeSet, DalContext, User};
+use dal::{audit_logging, ActorView, ChangeSet, DalContext, User};
 use serde::{Deserialize, Serialize};
-use si_events::{ChangeSetId, UserPk};
+use si_events::{Actor, ChangeSetId, UserPk};
 use si_frontend_types as frontend_types;
 
 use super::{AuditLogError, AuditLogResult};
@@ -85,9 +85,24 @@ impl Assembler {
             .await?;
         let (user_id, user_email, user_name) =
             self.find_user_metadata(ctx, audit_log.user_id).await?;
+        let actor = match audit_log.actor() {
+            Actor::Machine(pk) => frontend_types::AuditLogActor::Machine {
+                pk,
+                label: ActorView::machine_label(pk),
+            },
+            Actor::System => frontend_types::AuditLogActor::System {
+                label: ActorView::system_label(),
+            },
+            Actor::User(id) => frontend_types::AuditLogActor::User {
+                id,
+                label: user_name.to_owned().unwrap_or_default(),
+                email: user_email.to_owned(),
+            },
+        };
 
         Ok(si_frontend_types::AuditLog {
             title: audit_log.title,
+            actor,
             user_id,
             user_email,
             user_name,
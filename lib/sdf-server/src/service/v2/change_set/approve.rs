@@ -1,5 +1,5 @@
 use axum::extract::{Host, OriginalUri, Path};
-use dal::{ChangeSet, ChangeSetId, WorkspacePk, WsEvent};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk};
 use si_events::audit_log::AuditLogKind;
 
 use super::{post_to_webhook, Error, Result};
@@ -75,11 +75,6 @@ pub async fn approve(
     );
     post_to_webhook(&ctx, workspace_pk, message.as_str()).await?;
 
-    WsEvent::change_set_status_changed(&ctx, old_status, change_set_view)
-        .await?
-        .publish_on_commit(&ctx)
-        .await?;
-
     ctx.commit().await?;
 
     Ok(())
@@ -1,5 +1,5 @@
 use axum::extract::{Host, OriginalUri, Path};
-use dal::{ChangeSet, ChangeSetId, WorkspacePk, WsEvent};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk};
 use si_events::audit_log::AuditLogKind;
 
 use super::{post_to_webhook, Error, Result};
@@ -50,10 +50,6 @@ pub async fn cancel_approval_request(
         change_set_view.name.clone(),
     )
     .await?;
-    WsEvent::change_set_status_changed(&ctx, old_status, change_set_view.clone())
-        .await?
-        .publish_on_commit(&ctx)
-        .await?;
     let actor = ctx.history_actor().email(&ctx).await?;
     let change_set_url = format!("https://{}/w/{}/{}", host_name, workspace_pk, change_set_id);
     let message = format!(
@@ -1,5 +1,5 @@
 use axum::extract::{Host, OriginalUri, Path};
-use dal::{ChangeSet, ChangeSetId, WorkspacePk, WsEvent};
+use dal::{ChangeSet, ChangeSetId, WorkspacePk};
 use si_events::audit_log::AuditLogKind;
 
 use super::{Error, Result};
@@ -42,10 +42,6 @@ pub async fn reopen(
         change_set_view.name.clone(),
     )
     .await?;
-    WsEvent::change_set_status_changed(&ctx, old_status, change_set_view)
-        .await?
-        .publish_on_commit(&ctx)
-        .await?;
 
     track(
         &posthog_client,
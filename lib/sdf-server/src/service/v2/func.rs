@@ -250,5 +250,6 @@ pub async fn get_code_response(ctx: &DalContext, func_id: FuncId) -> FuncAPIResu
     Ok(FuncCode {
         func_id: func.id,
         code: code.clone(),
+        normalized: false,
     })
 }
@@ -118,6 +118,7 @@ async fn send_func_code_saved_event(ctx: &DalContext, func_id: FuncId) -> FuncAP
     let func_code = FuncCode {
         func_id,
         code: func.code_plaintext()?.unwrap_or("".to_string()),
+        normalized: false,
     };
     WsEvent::func_code_saved(ctx, func_code, true)
         .await?
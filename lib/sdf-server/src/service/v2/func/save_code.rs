@@ -19,6 +19,11 @@ use crate::{
 #[serde(rename_all = "camelCase")]
 pub struct SaveCodeRequest {
     pub code: String,
+    /// Run the code through [`Func::normalize_code`] before storing it. Off by default, since
+    /// the normalization pass does not know about string or template literals and would change
+    /// whitespace inside them.
+    #[serde(default)]
+    pub normalize: bool,
 }
 
 pub async fn save_code(
@@ -35,8 +40,15 @@ pub async fn save_code(
         .await?;
     let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
 
-    FuncAuthoringClient::save_code(&ctx, func_id, request.code).await?;
-    let func_code = get_code_response(&ctx, func_id).await?;
+    let normalized = FuncAuthoringClient::save_code_with_normalization(
+        &ctx,
+        func_id,
+        request.code,
+        request.normalize,
+    )
+    .await?;
+    let mut func_code = get_code_response(&ctx, func_id).await?;
+    func_code.normalized = normalized;
     let func = Func::get_by_id_or_error(&ctx, func_id).await?;
     WsEvent::func_code_saved(&ctx, func_code, false)
         .await?
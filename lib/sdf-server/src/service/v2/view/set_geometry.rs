@@ -5,7 +5,10 @@ use crate::{
 };
 use axum::{extract::Path, Json};
 use dal::{
-    diagram::view::{View, ViewId},
+    diagram::{
+        geometry::Geometry,
+        view::{View, ViewId},
+    },
     ChangeSet, ChangeSetId, Component, ComponentId, WorkspacePk, WsEvent,
 };
 use serde::{Deserialize, Serialize};
@@ -40,42 +43,36 @@ pub async fn set_component_geometry(
     let force_change_set_id = ChangeSet::force_new(&mut ctx).await?;
 
     let mut geometry_list = vec![];
+    let mut geometry_updates = vec![];
     for (id, string_geometry) in request.data_by_component_id {
         let new_geometry: RawGeometry = string_geometry.try_into()?;
 
-        let mut component = Component::get_by_id(&ctx, id).await?;
+        let component = Component::get_by_id(&ctx, id).await?;
 
         let current_geometry = component.geometry(&ctx, view_id).await?;
 
-        let new_geometry_cache = new_geometry.clone();
-
         let (width, height) = (
             new_geometry.width.or_else(|| current_geometry.width()),
             new_geometry.height.or_else(|| current_geometry.height()),
         );
+        let new_geometry = RawGeometry {
+            x: new_geometry.x,
+            y: new_geometry.y,
+            width,
+            height,
+        };
 
-        component
-            .set_geometry(
-                &ctx,
-                view_id,
-                new_geometry_cache.x,
-                new_geometry_cache.y,
-                width,
-                height,
-            )
-            .await?;
+        let geometry_id = current_geometry.id();
+        if current_geometry.into_raw() != new_geometry {
+            geometry_updates.push((geometry_id, new_geometry.clone()));
+        }
 
-        geometry_list.push((
-            id.into(),
-            RawGeometry {
-                x: new_geometry.x,
-                y: new_geometry.y,
-                width,
-                height,
-            },
-        ))
+        geometry_list.push((id.into(), new_geometry))
     }
 
+    // Update the whole selection at once rather than one component at a time
+    Geometry::update_bulk(&ctx, geometry_updates).await?;
+
     WsEvent::set_component_position(
         &ctx,
         ctx.change_set_id(),
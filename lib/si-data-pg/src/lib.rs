@@ -491,6 +491,57 @@ impl PgPool {
         }
     }
 
+    /// Returns the migrations from the runner that have not been applied yet, without running any
+    /// of them.
+    #[instrument(
+        name = "pg_pool.pending_migrations",
+        skip_all,
+        level = "debug",
+        fields(
+            db.system = %self.metadata.db_system,
+            db.connection_string = %self.metadata.db_connection_string,
+            db.name = %self.metadata.db_name,
+            db.user = %self.metadata.db_user,
+            db.pool.max_size = %self.metadata.db_pool_max_size,
+            net.peer.ip = %self.metadata.net_peer_ip,
+            net.peer.port = %self.metadata.net_peer_port,
+            net.transport = %self.metadata.net_transport,
+        )
+    )]
+    pub async fn pending_migrations(
+        &self,
+        runner: refinery::Runner,
+    ) -> PgPoolResult<Vec<refinery::Migration>> {
+        let mut conn = self.pool.get().await?;
+
+        // Nothing has been applied to a database that has never been migrated, and asking the
+        // runner would fail on the missing history table.
+        let has_history: bool = conn
+            .query_one(
+                "SELECT to_regclass('refinery_schema_history') IS NOT NULL AS has_history",
+                &[],
+            )
+            .await?
+            .try_get("has_history")?;
+        let applied = if has_history {
+            let client = &mut **conn;
+            runner.get_applied_migrations_async(client).await?
+        } else {
+            vec![]
+        };
+
+        Ok(runner
+            .get_migrations()
+            .iter()
+            .filter(|migration| {
+                !applied
+                    .iter()
+                    .any(|applied| applied.version() == migration.version())
+            })
+            .cloned()
+            .collect())
+    }
+
     #[instrument(
         name = "pg_pool.drop_and_create_public_schema",
         skip_all,
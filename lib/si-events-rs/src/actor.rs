@@ -1,10 +1,65 @@
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
-pub use si_id::UserPk;
+pub use si_id::{MachinePk, UserPk};
 
+// NOTE: this enum is serialized with postcard in the layer db, so new variants must be
+// appended rather than sorted in.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, Display)]
 pub enum Actor {
     System,
     User(UserPk),
+    /// A non-human identity, such as a service account driving automation.
+    Machine(MachinePk),
+}
+
+/// Pseudonymizes an identifier so that it can leave the organization (e.g. in audit exports).
+///
+/// The same identifier and salt always produce the same token, so records can still be grouped
+/// per identity without revealing which identity they belong to. Rotating the salt breaks that
+/// linkage.
+pub trait Anonymize {
+    /// Produces a stable, hex-encoded token for `self` given a `salt`.
+    fn anonymize(&self, salt: &[u8]) -> String;
+}
+
+impl Anonymize for UserPk {
+    fn anonymize(&self, salt: &[u8]) -> String {
+        let mut hasher = blake3::Hasher::new();
+        // Length-prefix the salt so that salt and identifier bytes can never be shifted between
+        // each other to produce a colliding input.
+        hasher.update(&(salt.len() as u64).to_le_bytes());
+        hasher.update(salt);
+        hasher.update(self.to_string().as_bytes());
+        hasher.finalize().to_hex().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_serde_is_tagged() {
+        let machine_pk = MachinePk::new();
+        let actor = Actor::Machine(machine_pk);
+
+        let pk_value = serde_json::to_value(machine_pk).expect("should serialize pk");
+        let value = serde_json::to_value(actor).expect("should serialize");
+        assert_eq!(serde_json::json!({ "Machine": pk_value }), value);
+
+        let deserialized: Actor = serde_json::from_value(value).expect("should deserialize");
+        assert_eq!(actor, deserialized);
+    }
+
+    #[test]
+    fn anonymize_user_pk() {
+        let user_pk = UserPk::new();
+
+        let token = user_pk.anonymize(b"salt");
+        assert_eq!(token, user_pk.anonymize(b"salt"));
+        assert_ne!(token, user_pk.anonymize(b"pepper"));
+        assert_ne!(token, UserPk::new().anonymize(b"salt"));
+        assert!(!token.contains(&user_pk.to_string()));
+    }
 }
@@ -1,9 +1,279 @@
+use std::{fmt, str::FromStr};
+
 use bytes::BytesMut;
 use postgres_types::ToSql;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::xxhash_type::XXH3_HASH_SIZE;
+
+/// Size of a tagged [`ContentHash`] encoding: the algorithm tag byte followed by the digest.
+const TAGGED_HASH_SIZE: usize = XXH3_HASH_SIZE + 1;
+
+/// Length of the hex string for an untagged [`ContentHash`] digest.
+const UNTAGGED_HEX_LEN: usize = XXH3_HASH_SIZE * 2;
+
+/// Length of the hex string for a tagged [`ContentHash`].
+const TAGGED_HEX_LEN: usize = TAGGED_HASH_SIZE * 2;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ContentHashParseError {
+    #[error("invalid content hash length: {0}")]
+    InvalidLength(usize),
+    #[error("failed to parse hash hex string")]
+    ParseInt(#[from] std::num::ParseIntError),
+    #[error("unknown content hash algorithm tag: {0}")]
+    UnknownAlgorithm(u8),
+}
+
+/// The hashing algorithm used to produce a [`ContentHash`].
+///
+/// Each algorithm has a stable tag byte which is written into tagged encodings. Tags must never
+/// be reused or reordered, since they are persisted alongside the digest.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum ContentHashAlgorithm {
+    /// 128-bit xxh3, stored as little-endian bytes.
+    #[default]
+    Xxh3_128 = 0,
+}
+
+impl ContentHashAlgorithm {
+    /// The algorithm used for all newly created hashes.
+    pub const CURRENT: Self = Self::Xxh3_128;
+
+    /// The tag byte identifying this algorithm in a tagged encoding.
+    pub fn tag(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<u8> for ContentHashAlgorithm {
+    type Error = ContentHashParseError;
+
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        match tag {
+            0 => Ok(Self::Xxh3_128),
+            unknown => Err(ContentHashParseError::UnknownAlgorithm(unknown)),
+        }
+    }
+}
+
+/// A hash of a piece of content, tagged with the [`algorithm`](ContentHashAlgorithm) that
+/// produced it.
+///
+/// Hashes produced by [`ContentHashAlgorithm::CURRENT`] are encoded without a tag, exactly as
+/// they were before algorithm versioning existed, so that previously stored hashes and content
+/// keys keep resolving. Hashes produced by any other algorithm are encoded with a leading tag
+/// byte. Decoding accepts both forms, treating untagged hashes as the current algorithm.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct ContentHash {
+    algorithm: ContentHashAlgorithm,
+    digest: [u8; XXH3_HASH_SIZE],
+}
+
+impl ContentHash {
+    pub fn new(input: &[u8]) -> Self {
+        Self::from_bytes_with(ContentHashAlgorithm::CURRENT, input)
+    }
+
+    /// Hash the input with the given algorithm.
+    pub fn from_bytes_with(algorithm: ContentHashAlgorithm, input: &[u8]) -> Self {
+        let digest = match algorithm {
+            ContentHashAlgorithm::Xxh3_128 => ::xxhash_rust::xxh3::xxh3_128(input).to_le_bytes(),
+        };
+
+        Self { algorithm, digest }
+    }
+
+    /// The algorithm which produced this hash.
+    pub fn algorithm(&self) -> ContentHashAlgorithm {
+        self.algorithm
+    }
+
+    /// The raw digest bytes, without the algorithm tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.digest
+    }
+
+    pub fn nil() -> Self {
+        Self::new(&[0])
+    }
+
+    /// Provide a [`hasher`](ContentHashHasher) to create [`hashes`](ContentHash).
+    pub fn hasher() -> ContentHashHasher {
+        ContentHashHasher::new()
+    }
+
+    fn is_tagged(&self) -> bool {
+        self.algorithm != ContentHashAlgorithm::CURRENT
+    }
+
+    fn from_tagged_bytes(bytes: &[u8]) -> Result<Self, ContentHashParseError> {
+        match bytes.len() {
+            XXH3_HASH_SIZE => {
+                let mut digest = [0u8; XXH3_HASH_SIZE];
+                digest.copy_from_slice(bytes);
+                Ok(Self {
+                    algorithm: ContentHashAlgorithm::CURRENT,
+                    digest,
+                })
+            }
+            TAGGED_HASH_SIZE => {
+                let algorithm = ContentHashAlgorithm::try_from(bytes[0])?;
+                let mut digest = [0u8; XXH3_HASH_SIZE];
+                digest.copy_from_slice(&bytes[1..]);
+                Ok(Self { algorithm, digest })
+            }
+            other => Err(ContentHashParseError::InvalidLength(other)),
+        }
+    }
+}
+
+impl AsRef<[u8]> for ContentHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.digest
+    }
+}
+
+impl From<&serde_json::Value> for ContentHash {
+    fn from(value: &serde_json::Value) -> Self {
+        let input = value.to_string();
+        Self::new(input.as_bytes())
+    }
+}
+
+impl From<&str> for ContentHash {
+    fn from(input: &str) -> Self {
+        Self::new(input.as_bytes())
+    }
+}
+
+impl From<u128> for ContentHash {
+    fn from(value: u128) -> Self {
+        Self {
+            algorithm: ContentHashAlgorithm::CURRENT,
+            digest: value.to_le_bytes(),
+        }
+    }
+}
+
+impl Default for ContentHash {
+    fn default() -> Self {
+        Self::new("".as_bytes())
+    }
+}
+
+impl fmt::Debug for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContentHash({})", &self)
+    }
+}
 
-use crate::create_xxhash_type;
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digest_u128 = u128::from_le_bytes(self.digest);
+        if self.is_tagged() {
+            write!(f, "{:02x}{:032x}", self.algorithm.tag(), digest_u128)
+        } else {
+            write!(f, "{:032x}", digest_u128)
+        }
+    }
+}
+
+impl FromStr for ContentHash {
+    type Err = ContentHashParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.len() {
+            // Untagged hashes were historically parsed as a plain hex `u128`, so shorter strings
+            // (leading zeroes omitted) are still accepted.
+            len if len <= UNTAGGED_HEX_LEN => Ok(u128::from_str_radix(s, 16)?.into()),
+            TAGGED_HEX_LEN => {
+                let (tag, digest) = s.split_at(2);
+                let algorithm = ContentHashAlgorithm::try_from(u8::from_str_radix(tag, 16)?)?;
+                Ok(Self {
+                    algorithm,
+                    digest: u128::from_str_radix(digest, 16)?.to_le_bytes(),
+                })
+            }
+            other => Err(ContentHashParseError::InvalidLength(other)),
+        }
+    }
+}
+
+impl Serialize for ContentHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.is_tagged() {
+            let mut bytes = [0u8; TAGGED_HASH_SIZE];
+            bytes[0] = self.algorithm.tag();
+            bytes[1..].copy_from_slice(&self.digest);
+            serializer.serialize_bytes(&bytes)
+        } else {
+            serializer.serialize_bytes(&self.digest)
+        }
+    }
+}
+
+struct ContentHashVisitor;
+
+impl<'de> de::Visitor<'de> for ContentHashVisitor {
+    type Value = ContentHash;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a 16 byte slice representing an xxh3 hash, optionally prefixed by an algorithm tag byte")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        ContentHash::from_tagged_bytes(v).map_err(|err| {
+            E::custom(format!(
+                "deserializer received invalid bytes when attempting to deserialize a ContentHash: {err}"
+            ))
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ContentHash {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(ContentHashVisitor)
+    }
+}
 
-create_xxhash_type!(ContentHash);
+#[derive(Default)]
+pub struct ContentHashHasher(Box<::xxhash_rust::xxh3::Xxh3>);
+
+impl fmt::Debug for ContentHashHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContentHashHasher")
+    }
+}
+
+impl ContentHashHasher {
+    pub fn new() -> Self {
+        Self(Box::new(::xxhash_rust::xxh3::Xxh3::new()))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(&self) -> ContentHash {
+        ContentHash {
+            algorithm: ContentHashAlgorithm::Xxh3_128,
+            digest: self.0.digest128().to_le_bytes(),
+        }
+    }
+}
 
 impl ToSql for ContentHash {
     fn to_sql(
@@ -35,3 +305,71 @@ impl ToSql for ContentHash {
         self_string.to_sql_checked(ty, out)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_hashes_use_current_algorithm() {
+        let hash = ContentHash::new(b"poop canoe");
+
+        assert_eq!(ContentHashAlgorithm::CURRENT, hash.algorithm());
+        assert_eq!(
+            ContentHash::from_bytes_with(ContentHashAlgorithm::CURRENT, b"poop canoe"),
+            hash
+        );
+    }
+
+    #[test]
+    fn display_from_str_round_trip() {
+        let hash = ContentHash::new(b"poop canoe");
+        let hash_string = hash.to_string();
+        assert_eq!(UNTAGGED_HEX_LEN, hash_string.len());
+
+        let parsed = ContentHash::from_str(&hash_string).expect("should parse");
+        assert_eq!(hash, parsed);
+        assert_eq!(ContentHashAlgorithm::CURRENT, parsed.algorithm());
+    }
+
+    #[test]
+    fn legacy_strings_parse_as_current_algorithm() {
+        let digest = ::xxhash_rust::xxh3::xxh3_128(b"poop canoe");
+        let legacy = format!("{digest:032x}");
+
+        let parsed = ContentHash::from_str(&legacy).expect("should parse");
+        assert_eq!(ContentHash::new(b"poop canoe"), parsed);
+
+        let tagged = format!("{:02x}{legacy}", ContentHashAlgorithm::Xxh3_128.tag());
+        let parsed_tagged = ContentHash::from_str(&tagged).expect("should parse tagged");
+        assert_eq!(parsed, parsed_tagged);
+    }
+
+    #[test]
+    fn unknown_algorithm_tag_is_rejected() {
+        let tagged = format!("ff{:032x}", 0u128);
+
+        assert!(matches!(
+            ContentHash::from_str(&tagged),
+            Err(ContentHashParseError::UnknownAlgorithm(0xff))
+        ));
+    }
+
+    #[test]
+    fn byte_encoding_accepts_legacy_and_tagged_forms() {
+        let hash = ContentHash::new(b"poop canoe");
+
+        let legacy = ContentHash::from_tagged_bytes(hash.as_bytes()).expect("should decode legacy");
+        assert_eq!(hash, legacy);
+
+        let mut tagged = vec![ContentHashAlgorithm::Xxh3_128.tag()];
+        tagged.extend_from_slice(hash.as_bytes());
+        let tagged = ContentHash::from_tagged_bytes(&tagged).expect("should decode tagged");
+        assert_eq!(hash, tagged);
+
+        assert!(matches!(
+            ContentHash::from_tagged_bytes(&[0u8; 3]),
+            Err(ContentHashParseError::InvalidLength(3))
+        ));
+    }
+}
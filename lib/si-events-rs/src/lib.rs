@@ -28,6 +28,8 @@ mod web_event;
 
 pub use crate::{
     actor::Actor,
+    actor::Anonymize,
+    actor::MachinePk,
     actor::UserPk,
     cas::CasValue,
     change_set_status::ChangeSetStatus,
@@ -53,6 +55,6 @@ pub use crate::{
     tenancy::WorkspacePk,
     timestamp::Timestamp,
     vector_clock_id::{VectorClockActorId, VectorClockChangeSetId, VectorClockId},
-    web_event::WebEvent,
+    web_event::{WebEvent, WebEventFilter, WebEventKind},
     workspace_snapshot_address::WorkspaceSnapshotAddress,
 };
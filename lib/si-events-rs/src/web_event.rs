@@ -1,4 +1,7 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
+use strum::EnumDiscriminants;
 
 use crate::{tenancy::ChangeSetId, tenancy::WorkspacePk};
 
@@ -25,6 +28,10 @@ impl WebEvent {
         &self.payload
     }
 
+    pub fn kind(&self) -> WebEventKind {
+        (&self.payload).into()
+    }
+
     pub fn change_set_written(workspace_pk: WorkspacePk, change_set_pk: ChangeSetId) -> Self {
         Self {
             version: DEFAULT_WEB_EVENT_VERSION,
@@ -36,7 +43,49 @@ impl WebEvent {
 }
 
 #[remain::sorted]
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, EnumDiscriminants, Eq, PartialEq, Serialize)]
+#[strum_discriminants(
+    name(WebEventKind),
+    derive(Hash, strum::Display, Serialize, Deserialize)
+)]
 pub enum WebEventPayload {
     ChangeSetWritten(ChangeSetId),
 }
+
+/// Selects which [`WebEvents`](WebEvent) a subscriber wants to receive, by [`WebEventKind`].
+///
+/// The default filter lets every event through.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WebEventFilter {
+    #[default]
+    All,
+    Kinds(HashSet<WebEventKind>),
+}
+
+impl WebEventFilter {
+    pub fn kinds(kinds: impl IntoIterator<Item = WebEventKind>) -> Self {
+        Self::Kinds(kinds.into_iter().collect())
+    }
+
+    pub fn matches(&self, event: &WebEvent) -> bool {
+        match self {
+            Self::All => true,
+            Self::Kinds(kinds) => kinds.contains(&event.kind()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_by_kind() {
+        let event = WebEvent::change_set_written(WorkspacePk::new(), ChangeSetId::new());
+        assert_eq!(WebEventKind::ChangeSetWritten, event.kind());
+
+        assert!(WebEventFilter::default().matches(&event));
+        assert!(WebEventFilter::kinds([WebEventKind::ChangeSetWritten]).matches(&event));
+        assert!(!WebEventFilter::kinds([]).matches(&event));
+    }
+}
@@ -1,10 +1,11 @@
 use serde::Serialize;
-use si_events::{ChangeSetId, UserPk};
+use si_events::{ChangeSetId, MachinePk, UserPk};
 
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct AuditLog {
     pub title: String,
+    pub actor: AuditLogActor,
     pub user_id: Option<UserPk>,
     pub user_email: Option<String>,
     pub user_name: Option<String>,
@@ -16,3 +17,40 @@ pub struct AuditLog {
     pub change_set_name: Option<String>,
     pub metadata: serde_json::Value,
 }
+
+/// Who performed an [`AuditLog`], in the shape of the web app's `ActorView`.
+#[remain::sorted]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AuditLogActor {
+    Machine {
+        pk: MachinePk,
+        label: String,
+    },
+    System {
+        label: String,
+    },
+    User {
+        id: UserPk,
+        label: String,
+        email: Option<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_actor_serializes_as_actor_view() {
+        let pk = MachinePk::new();
+        assert_eq!(
+            serde_json::json!({ "kind": "machine", "pk": pk, "label": "machine" }),
+            serde_json::to_value(AuditLogActor::Machine {
+                pk,
+                label: "machine".to_string(),
+            })
+            .expect("could not serialize actor")
+        );
+    }
+}
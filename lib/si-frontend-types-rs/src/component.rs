@@ -23,6 +23,16 @@ pub struct GridPoint {
     pub y: isize,
 }
 
+impl GridPoint {
+    /// Returns this point moved by `dx` horizontally and `dy` vertically.
+    pub fn translate(&self, dx: isize, dy: isize) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RawGeometry {
@@ -74,11 +84,85 @@ pub struct Size2D {
     pub height: isize,
 }
 
+impl Size2D {
+    /// Returns this size with both dimensions multiplied by `factor`, rounded to the nearest
+    /// grid unit.
+    pub fn scale(&self, factor: f64) -> Self {
+        Self {
+            width: (self.width as f64 * factor).round() as isize,
+            height: (self.height as f64 * factor).round() as isize,
+        }
+    }
+}
+
+/// The smallest axis-aligned box containing a set of components.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundingBox {
+    /// The top-left corner.
+    pub min: GridPoint,
+    /// The bottom-right corner.
+    pub max: GridPoint,
+}
+
+impl BoundingBox {
+    /// Computes the extents of the given components, each described by its top-left position
+    /// and its size. Returns `None` when there are no components.
+    pub fn from_components(components: &[(GridPoint, Size2D)]) -> Option<Self> {
+        components
+            .iter()
+            .map(|(position, size)| Self {
+                min: position.clone(),
+                max: position.translate(size.width, size.height),
+            })
+            .reduce(|acc, next| Self {
+                min: GridPoint {
+                    x: acc.min.x.min(next.min.x),
+                    y: acc.min.y.min(next.min.y),
+                },
+                max: GridPoint {
+                    x: acc.max.x.max(next.max.x),
+                    y: acc.max.y.max(next.max.y),
+                },
+            })
+    }
+
+    pub fn size(&self) -> Size2D {
+        Size2D {
+            width: self.max.x - self.min.x,
+            height: self.max.y - self.min.y,
+        }
+    }
+}
+
 #[derive(Clone, Eq, Debug, PartialEq, Deserialize, Serialize)]
 pub struct ConnectionAnnotation {
     pub tokens: Vec<String>,
 }
 
+impl ConnectionAnnotation {
+    /// Returns whether an output socket with this annotation can feed an input socket with the
+    /// `other` annotation. See [`Self::tokens_fit`] for the matching rules.
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        Self::tokens_fit(&self.tokens, &other.tokens)
+    }
+
+    /// The connection annotation matching rules. Tokens are hierarchical, outermost first (e.g.
+    /// `user_arn<arn<string>>` is `["user_arn", "arn", "string"]`), and the output (`target`)
+    /// fits the input (`reference`) if the input's tokens are a suffix of the output's, compared
+    /// case-insensitively. A `user_arn<arn<string>>` output therefore fits `arn<string>` and
+    /// `string` inputs, but not the other way around.
+    pub fn tokens_fit(target: &[String], reference: &[String]) -> bool {
+        !reference.is_empty()
+            && reference.len() <= target.len()
+            && target.iter().rev().zip(reference.iter().rev()).all(
+                |(target_token, reference_token)| {
+                    target_token.to_lowercase() == reference_token.to_lowercase()
+                },
+            )
+    }
+}
+
 #[remain::sorted]
 #[derive(
     AsRefStr,
@@ -136,6 +220,36 @@ pub struct DiagramSocket {
     pub managed_schemas: Option<Vec<SchemaId>>,
 }
 
+impl DiagramSocket {
+    /// Returns whether this (output) socket can feed the `input` socket, i.e. whether any of its
+    /// [`ConnectionAnnotations`](ConnectionAnnotation) is compatible with any of the input's.
+    pub fn fits_input(&self, input: &DiagramSocket) -> bool {
+        self.connection_annotations.iter().any(|output_annotation| {
+            input
+                .connection_annotations
+                .iter()
+                .any(|input_annotation| output_annotation.is_compatible_with(input_annotation))
+        })
+    }
+}
+
+/// Returns every `(output, input)` pair of [`DiagramSockets`](DiagramSocket) where the output can
+/// feed the input.
+pub fn compatible_socket_pairs<'a>(
+    outputs: &'a [DiagramSocket],
+    inputs: &'a [DiagramSocket],
+) -> Vec<(&'a DiagramSocket, &'a DiagramSocket)> {
+    outputs
+        .iter()
+        .flat_map(|output| {
+            inputs
+                .iter()
+                .filter(move |input| output.fits_input(input))
+                .map(move |input| (output, input))
+        })
+        .collect()
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct DiagramComponentView {
@@ -162,3 +276,79 @@ pub struct DiagramComponentView {
     pub from_base_change_set: bool,
     pub view_data: Option<GeometryAndView>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translate_grid_point() {
+        assert_eq!(
+            GridPoint { x: -5, y: 15 },
+            GridPoint { x: 5, y: 5 }.translate(-10, 10)
+        );
+    }
+
+    #[test]
+    fn scale_size() {
+        let size = Size2D {
+            width: 100,
+            height: 25,
+        };
+        assert_eq!(
+            Size2D {
+                width: 50,
+                height: 13
+            },
+            size.scale(0.5)
+        );
+        assert_eq!(
+            Size2D {
+                width: 200,
+                height: 50
+            },
+            size.scale(2.0)
+        );
+    }
+
+    #[test]
+    fn bounding_box_of_no_components() {
+        assert_eq!(None, BoundingBox::from_components(&[]));
+    }
+
+    #[test]
+    fn bounding_box_with_negative_coordinates() {
+        let bounding_box = BoundingBox::from_components(&[
+            (
+                GridPoint { x: -100, y: -50 },
+                Size2D {
+                    width: 20,
+                    height: 10,
+                },
+            ),
+            (
+                GridPoint { x: 30, y: -200 },
+                Size2D {
+                    width: 50,
+                    height: 40,
+                },
+            ),
+        ])
+        .expect("should have a bounding box");
+
+        assert_eq!(
+            BoundingBox {
+                min: GridPoint { x: -100, y: -200 },
+                max: GridPoint { x: 80, y: -40 },
+            },
+            bounding_box
+        );
+        assert_eq!(
+            Size2D {
+                width: 180,
+                height: 160
+            },
+            bounding_box.size()
+        );
+    }
+}
@@ -52,6 +52,9 @@ pub struct FuncArgument {
 pub struct FuncCode {
     pub func_id: FuncId,
     pub code: String,
+    /// Whether the code was rewritten by the normalization pass when it was last saved.
+    #[serde(default)]
+    pub normalized: bool,
 }
 #[derive(Clone, Debug, Deserialize, Eq, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
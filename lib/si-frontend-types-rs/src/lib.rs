@@ -7,12 +7,12 @@ mod module;
 mod schema_variant;
 mod workspace;
 
-pub use crate::audit_log::AuditLog;
+pub use crate::audit_log::{AuditLog, AuditLogActor};
 pub use crate::change_set::ChangeSet;
 pub use crate::component::{
-    ChangeStatus, ConnectionAnnotation, DiagramComponentView, DiagramSocket,
-    DiagramSocketDirection, DiagramSocketNodeSide, GeometryAndView, GridPoint, RawGeometry, Size2D,
-    StringGeometry,
+    compatible_socket_pairs, BoundingBox, ChangeStatus, ConnectionAnnotation, DiagramComponentView,
+    DiagramSocket, DiagramSocketDirection, DiagramSocketNodeSide, GeometryAndView, GridPoint,
+    RawGeometry, Size2D, StringGeometry,
 };
 pub use crate::conflict::ConflictWithHead;
 pub use crate::func::{
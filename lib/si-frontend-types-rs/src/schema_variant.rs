@@ -90,6 +90,7 @@ pub struct Prop {
     pub name: String,
     pub path: String,
     pub hidden: bool,
+    pub required: bool,
     pub eligible_to_receive_data: bool,
     pub eligible_to_send_data: bool,
 }
@@ -69,6 +69,7 @@ id_with_pg_types!(ChangeSetId);
 id_with_pg_types!(ComponentId);
 id_with_pg_types!(FuncId);
 id_with_pg_types!(FuncRunId);
+id_with_pg_types!(MachinePk);
 id_with_pg_types!(UserPk);
 id_with_pg_types!(WorkspaceIntegrationId);
 
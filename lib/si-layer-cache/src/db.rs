@@ -275,6 +275,12 @@ where
 
         Ok(())
     }
+
+    /// Returns the names of the migrations that [`Self::pg_migrate`] would apply, without
+    /// applying any of them.
+    pub async fn pg_pending_migrations(&self) -> LayerDbResult<Vec<String>> {
+        self.cas.cache.pg().pending_migrations().await
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
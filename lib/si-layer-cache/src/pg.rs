@@ -60,6 +60,18 @@ impl PgLayer {
         Ok(())
     }
 
+    /// Returns the names of the migrations that [`Self::migrate`] would apply, without applying
+    /// any of them.
+    pub async fn pending_migrations(&self) -> LayerDbResult<Vec<String>> {
+        Ok(self
+            .pool
+            .pending_migrations(embedded::migrations::runner())
+            .await?
+            .iter()
+            .map(ToString::to_string)
+            .collect())
+    }
+
     pub async fn get(&self, key: &str) -> LayerDbResult<Option<Vec<u8>>> {
         let key: String = key.into();
         let client = self.pool.get().await?;
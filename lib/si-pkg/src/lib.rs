@@ -6,7 +6,7 @@ mod workspace;
 pub use pkg::*;
 pub use spec::*;
 pub use workspace::{
-    WorkspaceExport, WorkspaceExportChangeSetV0, WorkspaceExportContentV0,
+    WorkspaceExport, WorkspaceExportChangeSetV0, WorkspaceExportContentV0, WorkspaceExportFrameV0,
     WorkspaceExportMetadataV0,
 };
 
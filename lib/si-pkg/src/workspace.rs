@@ -29,6 +29,15 @@ pub struct WorkspaceExportContentV0 {
     pub metadata: WorkspaceExportMetadataV0,
 }
 
+// A single frame of a streamed workspace export. The metadata frame comes first, followed by the
+// content store values and the change set itself for each exported change set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WorkspaceExportFrameV0 {
+    Metadata(WorkspaceExportMetadataV0),
+    ContentStoreValues(Vec<u8>),
+    ChangeSet(WorkspaceExportChangeSetV0),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceExportChangeSetV0 {
     pub id: Ulid,
@@ -48,4 +57,7 @@ pub struct WorkspaceExportMetadataV0 {
     pub default_change_set_base: Ulid,
     pub workspace_pk: Ulid,
     pub workspace_name: String,
+    // Exports made before this was added don't have it, so it defaults to unset
+    #[serde(default)]
+    pub component_concurrency_limit: Option<i32>,
 }
@@ -24,7 +24,7 @@ pub use cyclone_core::{
     ActionRunRequest, ActionRunResultSuccess, BeforeFunction, ComponentView, CycloneRequest,
     CycloneRequestable, FunctionResult, FunctionResultFailure, FunctionResultFailureError,
     FunctionResultFailureErrorKind, KillExecutionRequest, ManagementRequest,
-    ManagementResultSuccess, OutputStream, ProgressMessage, ResolverFunctionRequest,
+    ManagementResultSuccess, Message, OutputStream, ProgressMessage, ResolverFunctionRequest,
     ResolverFunctionResultSuccess, ResourceStatus, SchemaVariantDefinitionRequest,
     SchemaVariantDefinitionResultSuccess, SensitiveStrings, ValidationRequest,
     ValidationResultSuccess,
@@ -1,6 +1,7 @@
 //! Common Tokio runtime related behavior.
 
 use std::{
+    num::NonZeroUsize,
     sync::atomic::{AtomicUsize, Ordering},
     time::Duration,
 };
@@ -25,7 +26,21 @@ pub use tokio_dedicated_executor::{
 
 /// Builds a main/primary Tokio [`Runtime`] with sensible defaults.
 pub fn main_tokio_runtime(runtime_name: impl Into<String>) -> std::io::Result<Runtime> {
-    common_tokio_builder("main", runtime_name)
+    main_tokio_runtime_with_worker_threads(runtime_name, None)
+}
+
+/// Builds a main/primary Tokio [`Runtime`] with sensible defaults and, if given, a fixed number of
+/// worker threads. Otherwise Tokio starts one worker thread per CPU core.
+pub fn main_tokio_runtime_with_worker_threads(
+    runtime_name: impl Into<String>,
+    worker_threads: Option<NonZeroUsize>,
+) -> std::io::Result<Runtime> {
+    let mut builder = common_tokio_builder("main", runtime_name);
+    if let Some(worker_threads) = worker_threads {
+        builder.worker_threads(worker_threads.get());
+    }
+
+    builder
         .thread_stack_size(DEFAULT_TOKIO_RT_THREAD_STACK_SIZE)
         .max_blocking_threads(DEFAULT_TOKIO_RT_BLOCKING_POOL_SIZE)
         // Enables using net, process, signal, and some I/O types
@@ -1,6 +1,6 @@
 //! Common Tokio runtime related behavior.
 
-use std::future::Future;
+use std::{future::Future, num::NonZeroUsize};
 
 use color_eyre::{eyre::eyre, Result};
 use si_runtime::DEFAULT_TOKIO_RT_THREAD_STACK_SIZE;
@@ -13,6 +13,20 @@ use si_runtime::DEFAULT_TOKIO_RT_THREAD_STACK_SIZE;
 /// to be run as the entry point for a `main()` program as an alternative to the
 /// `#[tokio::main]` attribute macro.
 pub fn block_on<S, Fut>(thread_name: S, future: Fut) -> Result<()>
+where
+    S: Into<String>,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    block_on_with_worker_threads(thread_name, None, future)
+}
+
+/// Like [`block_on`], but the Tokio runtime uses the given number of worker threads, if any,
+/// rather than one per CPU core.
+pub fn block_on_with_worker_threads<S, Fut>(
+    thread_name: S,
+    worker_threads: Option<NonZeroUsize>,
+    future: Fut,
+) -> Result<()>
 where
     S: Into<String>,
     Fut: Future<Output = Result<()>> + Send + 'static,
@@ -21,8 +35,10 @@ where
 
     let thread_builder =
         ::std::thread::Builder::new().stack_size(DEFAULT_TOKIO_RT_THREAD_STACK_SIZE);
-    let thread_handler =
-        thread_builder.spawn(|| si_runtime::main_tokio_runtime(thread_name)?.block_on(future))?;
+    let thread_handler = thread_builder.spawn(move || {
+        si_runtime::main_tokio_runtime_with_worker_threads(thread_name, worker_threads)?
+            .block_on(future)
+    })?;
 
     match thread_handler.join() {
         Ok(result) => result,
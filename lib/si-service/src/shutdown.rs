@@ -108,6 +108,10 @@ where
     HanErr: error::Error + Send + Sync + 'static,
 {
     /// Adds a shutdown group, consisting of a related [`TaskTracker`] and [`CancellationToken`].
+    ///
+    /// Groups are shut down sequentially in the order they were added: a group's token is
+    /// cancelled and its tracker fully drained before the next group is cancelled. Add groups that
+    /// accept new work before the groups that work depends on.
     pub fn group(mut self, tracker: TaskTracker, token: CancellationToken) -> Self {
         self.groups.extend([(tracker, token)]);
         self
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use cyclone_core::CycloneRequestable;
 use futures::{StreamExt, TryStreamExt};
 use nats_subscriber::{Subscriber, SubscriberError};
@@ -10,7 +12,8 @@ use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use veritech_core::{
     reply_mailbox_for_output, reply_mailbox_for_result, GetNatsSubjectFor,
-    FINAL_MESSAGE_HEADER_KEY, REPLY_INBOX_HEADER_NAME,
+    COMPRESS_OUTPUT_HEADER_NAME, EXECUTION_TIMEOUT_MILLIS_HEADER_NAME, FINAL_MESSAGE_HEADER_KEY,
+    REPLY_INBOX_HEADER_NAME,
 };
 
 pub use cyclone_core::{
@@ -57,12 +60,35 @@ enum RequestMode {
 pub struct Client {
     nats: NatsClient,
     context: jetstream::Context,
+    compress_output: bool,
+    execution_timeout: Option<Duration>,
 }
 
 impl Client {
     pub fn new(nats: NatsClient) -> Self {
         let context = jetstream::new(nats.clone());
-        Self { nats, context }
+        Self {
+            nats,
+            context,
+            compress_output: false,
+            execution_timeout: None,
+        }
+    }
+
+    /// Asks veritech to compress large output messages. Output is decompressed before it is
+    /// forwarded, so this only trades CPU for less data on the wire. Older servers ignore the
+    /// request and send plain text output.
+    pub fn with_compressed_output(mut self, compress_output: bool) -> Self {
+        self.compress_output = compress_output;
+        self
+    }
+
+    /// Asks veritech to kill function executions that run for longer than `execution_timeout`.
+    /// This can only shorten an execution, since veritech's own execution timeout still applies.
+    /// `None` leaves executions bounded by veritech's timeout alone.
+    pub fn with_execution_timeout(mut self, execution_timeout: Option<Duration>) -> Self {
+        self.execution_timeout = execution_timeout;
+        self
     }
 
     fn nats_subject_prefix(&self) -> Option<&str> {
@@ -289,6 +315,15 @@ impl Client {
             RequestMode::Jetstream => {
                 let mut headers = propagation::empty_injected_headers();
                 headers.insert(REPLY_INBOX_HEADER_NAME, reply_mailbox_root.clone());
+                if self.compress_output {
+                    headers.insert(COMPRESS_OUTPUT_HEADER_NAME, "true");
+                }
+                if let Some(execution_timeout) = self.execution_timeout {
+                    headers.insert(
+                        EXECUTION_TIMEOUT_MILLIS_HEADER_NAME,
+                        execution_timeout.as_millis().to_string(),
+                    );
+                }
 
                 self.context
                     .publish_with_headers(subject, headers, msg.into())
@@ -358,7 +393,14 @@ async fn forward_output_task(
                 match msg {
                     Ok(output) => {
                         output.process_span.follows_from(&request_span);
-                        if let Err(err) = output_tx.send(output.payload).await {
+                        let payload = match output.payload.decompress() {
+                            Ok(payload) => payload,
+                            Err(err) => {
+                                warn!(error = ?err, "output forwarder failed to decompress message");
+                                continue;
+                            }
+                        };
+                        if let Err(err) = output_tx.send(payload).await {
                             warn!(error = ?err, "output forwarder failed to send message on channel");
                         }
                     }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, env};
+use std::{collections::HashMap, env, time::Duration};
 
 use base64::{engine::general_purpose, Engine};
 use cyclone_core::{
@@ -226,6 +226,57 @@ async fn executes_simple_resolver_function() {
     }
 }
 
+#[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+#[test(tokio::test)]
+async fn fails_resolver_function_exceeding_execution_timeout() {
+    let prefix = nats_prefix();
+    run_veritech_server_for_uds_cyclone(prefix.clone()).await;
+    let client = client(prefix)
+        .await
+        .with_execution_timeout(Some(Duration::from_secs(1)));
+
+    // Not going to check output here--we aren't emitting anything
+    let (tx, mut rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        while let Some(output) = rx.recv().await {
+            info!("output: {:?}", output)
+        }
+    });
+
+    let request = ResolverFunctionRequest {
+        execution_id: "1234".to_string(),
+        handler: "neverResolves".to_string(),
+        component: ResolverFunctionComponent {
+            data: ComponentView {
+                properties: serde_json::json!({}),
+                kind: ComponentKind::Standard,
+            },
+            parents: vec![],
+        },
+        response_type: ResolverFunctionResponseType::Integer,
+        code_base64: base64_encode("function neverResolves() { return new Promise(() => {}); }"),
+        before: vec![],
+    };
+
+    // The caller must hear back about the timeout rather than wait forever
+    let result = tokio::time::timeout(
+        Duration::from_secs(30),
+        client.execute_resolver_function(tx, &request, WORKSPACE_ID, CHANGE_SET_ID),
+    )
+    .await
+    .expect("caller never received a result for the timed out function")
+    .expect("failed to execute resolver function");
+
+    match result {
+        FunctionResult::Success(success) => {
+            panic!("function should have timed out, but succeeded: {success:?}")
+        }
+        FunctionResult::Failure(failure) => {
+            assert_eq!(failure.execution_id(), "1234");
+        }
+    }
+}
+
 #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
 #[test(tokio::test)]
 async fn type_checks_resolve_function() {
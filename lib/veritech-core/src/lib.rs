@@ -39,6 +39,10 @@ const SUBJECT_PREFIX: &str = "veritech.requests";
 
 pub const REPLY_INBOX_HEADER_NAME: &str = "X-Reply-Inbox";
 pub const FINAL_MESSAGE_HEADER_KEY: &str = "X-Final-Message";
+/// Optional header carrying a per-request execution timeout, in milliseconds.
+pub const EXECUTION_TIMEOUT_MILLIS_HEADER_NAME: &str = "X-Execution-Timeout-Millis";
+/// Optional header set by requesters that can decompress gzip encoded output messages.
+pub const COMPRESS_OUTPUT_HEADER_NAME: &str = "X-Compress-Output";
 
 // NOTE(nick,fletcher): we can probably take this type formalization a step further, but this is
 // essentially the "FuncRunId" from the "dal".
@@ -10,8 +10,10 @@ use si_data_nats::{InnerMessage, Subject};
 // seems strange to get these cyclone_core types from si_pool_noodle?
 use si_pool_noodle::{
     ActionRunResultSuccess, CycloneClient, CycloneRequest, CycloneRequestable, ExecutionError,
-    ManagementResultSuccess, ProgressMessage, ResolverFunctionResultSuccess,
-    SchemaVariantDefinitionResultSuccess, SensitiveStrings, ValidationResultSuccess,
+    FunctionResult, FunctionResultFailure, FunctionResultFailureError,
+    FunctionResultFailureErrorKind, ManagementResultSuccess, Message, ProgressMessage,
+    ResolverFunctionResultSuccess, SchemaVariantDefinitionResultSuccess, SensitiveStrings,
+    ValidationResultSuccess,
 };
 use std::{collections::HashMap, result, str::Utf8Error, sync::Arc, time::Duration};
 use telemetry::prelude::*;
@@ -20,7 +22,7 @@ use thiserror::Error;
 use tokio::sync::{oneshot, Mutex};
 use veritech_core::{
     ExecutionId, VeritechRequest, VeritechRequestError, VeritechValueDecryptError,
-    REPLY_INBOX_HEADER_NAME,
+    COMPRESS_OUTPUT_HEADER_NAME, EXECUTION_TIMEOUT_MILLIS_HEADER_NAME, REPLY_INBOX_HEADER_NAME,
 };
 
 use crate::{app_state::AppState, request::DecryptRequest, Publisher, PublisherError};
@@ -29,6 +31,10 @@ pub use kill::process_kill_request;
 
 mod kill;
 
+/// How long past a per-request timeout veritech waits before killing the execution itself. Cyclone
+/// enforces the same timeout, and its failure carries more detail than ours, so it should win.
+const REQUEST_TIMEOUT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum HandlerError {
@@ -64,6 +70,8 @@ pub enum HandlerError {
     PoolNoodleExecutionValidation(#[from] si_pool_noodle::ExecutionError<ValidationResultSuccess>),
     #[error("publisher error: {0}")]
     Publisher(#[from] PublisherError),
+    #[error("request timed out: {0:?}")]
+    RequestTimeout(Duration),
     #[error("utf8 error when creating subject")]
     Utf8(#[from] Utf8Error),
     #[error("veritech request error: {0}")]
@@ -89,6 +97,15 @@ pub async fn process_request(
 ) -> HandlerResult<()> {
     let span = Span::current();
 
+    let request_timeout = maybe_headers
+        .as_ref()
+        .and_then(|headers| headers.get(EXECUTION_TIMEOUT_MILLIS_HEADER_NAME))
+        .and_then(|value| value.to_string().parse::<u64>().ok())
+        .map(Duration::from_millis);
+    let compress_output = maybe_headers
+        .as_ref()
+        .is_some_and(|headers| headers.get(COMPRESS_OUTPUT_HEADER_NAME).is_some());
+
     let reply_subject = match maybe_headers
         .and_then(|headers| headers.get(REPLY_INBOX_HEADER_NAME).map(|v| v.to_string()))
     {
@@ -135,19 +152,54 @@ pub async fn process_request(
 
     match veritech_request {
         VeritechRequest::ActionRun(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(
+                state,
+                request,
+                reply_subject,
+                request_timeout,
+                compress_output,
+            )
+            .await?
         }
         VeritechRequest::Management(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(
+                state,
+                request,
+                reply_subject,
+                request_timeout,
+                compress_output,
+            )
+            .await?
         }
         VeritechRequest::Resolver(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(
+                state,
+                request,
+                reply_subject,
+                request_timeout,
+                compress_output,
+            )
+            .await?
         }
         VeritechRequest::SchemaVariantDefinition(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(
+                state,
+                request,
+                reply_subject,
+                request_timeout,
+                compress_output,
+            )
+            .await?
         }
         VeritechRequest::Validation(request) => {
-            dispatch_request(state, request, reply_subject).await?
+            dispatch_request(
+                state,
+                request,
+                reply_subject,
+                request_timeout,
+                compress_output,
+            )
+            .await?
         }
         // Kill requests do not get handled here
         VeritechRequest::KillExecution(_) => {
@@ -162,6 +214,8 @@ async fn dispatch_request<Request>(
     state: AppState,
     mut request: Request,
     reply_mailbox: Subject,
+    request_timeout: Option<Duration>,
+    compress_output: bool,
 ) -> HandlerResult<()>
 where
     Request: CycloneRequestable + DecryptRequest + Serialize + Clone + Send + Sync,
@@ -187,7 +241,10 @@ where
     let publisher = Publisher::new(&nats_for_publisher, &reply_mailbox);
     let execution_id = request.execution_id().to_owned();
 
-    let cyclone_request = CycloneRequest::from_parts(request.clone(), sensitive_strings);
+    let cyclone_request = CycloneRequest::from_parts(request.clone(), sensitive_strings)
+        .with_timeout(request_timeout)
+        .with_compressed_output(compress_output);
+    let request_timeout = cyclone_request.timeout();
 
     let (kill_sender, kill_receiver) = oneshot::channel::<()>();
     {
@@ -212,6 +269,7 @@ where
             span.record_err(err)
         })?;
 
+        let mut cyclone_failure = None;
         while let Some(msg) = progress.next().await {
             match msg {
                 Ok(ProgressMessage::OutputStream(output)) => {
@@ -223,6 +281,13 @@ where
                 Ok(ProgressMessage::Heartbeat) => {
                     trace!("received heartbeat message");
                 }
+                // Cyclone reports failures of its own, such as hitting the request timeout, this
+                // way and closes the stream without a result.
+                Err(ExecutionError::UnexpectedMessage(Message::Fail(fail))) => {
+                    warn!(message = %fail.message, "cyclone failed the execution, bailing out");
+                    cyclone_failure = Some(fail.message);
+                    break;
+                }
                 Err(err) => {
                     warn!(error = ?err, "next progress message was an error, bailing out");
                     break;
@@ -234,6 +299,16 @@ where
             span.record_err(err)
         })?;
 
+        if let Some(message) = cyclone_failure {
+            return HandlerResult::Ok(FunctionResult::Failure(
+                FunctionResultFailure::new_for_veritech_server_error(
+                    request.execution_id(),
+                    message,
+                    timestamp(),
+                ),
+            ));
+        }
+
         let function_result = progress.finish().await.map_err(|err| {
             request.dec_run_metric();
             span.record_err(err)
@@ -242,6 +317,19 @@ where
         HandlerResult::Ok(function_result)
     };
 
+    // A per-request timeout kills the execution the same way a kill request does: the kill
+    // sender is removed and the in-flight execution is dropped. It can only shorten an execution,
+    // since the worker-level timeout below still applies. This is a backstop for a cyclone that
+    // fails to report its own timeout, hence the grace period.
+    let request_deadline = async {
+        match request_timeout {
+            Some(request_timeout) => {
+                tokio::time::sleep(request_timeout + REQUEST_TIMEOUT_GRACE_PERIOD).await
+            }
+            None => std::future::pending().await,
+        }
+    };
+
     // we do not want to return errors at this point as it will retry functions that may have
     // failed for legitimate reasons and should not be retried
     let timeout = state.cyclone_client_execution_timeout;
@@ -253,6 +341,12 @@ where
                 timeout,
             ))
         },
+        _ = request_deadline => {
+            kill_sender_remove_blocking(&state.kill_senders, execution_id).await?;
+            Err(HandlerError::RequestTimeout(
+                request_timeout.unwrap_or(timeout),
+            ))
+        },
         Ok(_) = kill_receiver => {
             Err(HandlerError::Killed(execution_id))
         }
@@ -276,6 +370,25 @@ where
             request.dec_run_metric();
             warn!(error = ?timeout, "timed out trying to run function to completion");
         }
+        Err(err @ HandlerError::RequestTimeout(_)) => {
+            request.dec_run_metric();
+            info!(error = ?err, "function killed after exceeding its request timeout");
+
+            // Nothing else will answer the caller, who would otherwise wait on the reply subject
+            // forever.
+            let function_result: FunctionResult<Request::Response> =
+                FunctionResult::Failure(FunctionResultFailure::new(
+                    request.execution_id(),
+                    FunctionResultFailureError {
+                        kind: FunctionResultFailureErrorKind::KilledExecution,
+                        message: err.to_string(),
+                    },
+                    timestamp(),
+                ));
+            if let Err(err) = publisher.publish_result(&function_result).await {
+                error!(error = ?err, "failed to publish timed out result");
+            }
+        }
         Err(HandlerError::Killed(execution_id)) => {
             request.dec_run_metric();
             info!(error = ?execution_id, "function killed during execution via signal");